@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+use trustfall::{FieldValue, TransparentValue};
+
+type Row = BTreeMap<String, Value>;
+
+/// Reads results previously saved with `--format ndjson`, one JSON object per line.
+pub fn read_ndjson(path: &str) -> Vec<Row> {
+    let content = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Failed to read previous results from {path}: {e}"));
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line).unwrap_or_else(|e| {
+                panic!("Line {} of {path} is not a JSON object: {e}", index + 1)
+            })
+        })
+        .collect()
+}
+
+/// Key column used when none was given: `id` if the query outputs one,
+/// otherwise the first of its `outputs`, in sorted order like result rows' columns.
+pub fn default_key(outputs: &[Arc<str>]) -> Option<String> {
+    if outputs.iter().any(|output| output.as_ref() == "id") {
+        Some("id".to_string())
+    } else {
+        outputs.iter().min().map(|output| output.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ColumnChange {
+    pub column: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+/// Rows added, removed and changed between two runs of the same query,
+/// matched up by the value of the key column.
+///
+/// If several rows share a key, the last one wins.
+#[derive(Debug, Clone)]
+pub struct ResultDiff {
+    pub key: String,
+    pub added: Vec<Row>,
+    pub removed: Vec<Row>,
+    pub changed: Vec<(String, Vec<ColumnChange>)>,
+}
+
+impl ResultDiff {
+    pub fn compute(
+        key: &str,
+        previous: &[Row],
+        current: &[BTreeMap<Arc<str>, FieldValue>],
+    ) -> Self {
+        let current: Vec<Row> = current
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|(k, v)| {
+                        let v = serde_json::to_value(TransparentValue::from(v.clone())).unwrap();
+                        (k.to_string(), v)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let previous_by_key = index_by_key(key, previous);
+        let current_by_key = index_by_key(key, &current);
+
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        for (key_value, row) in &current_by_key {
+            match previous_by_key.get(key_value) {
+                None => added.push((*row).clone()),
+                Some(previous_row) => {
+                    let changes = column_changes(previous_row, row);
+                    if !changes.is_empty() {
+                        changed.push((key_value.clone(), changes));
+                    }
+                }
+            }
+        }
+
+        let removed = previous_by_key
+            .iter()
+            .filter(|(key_value, _)| !current_by_key.contains_key(*key_value))
+            .map(|(_, row)| (*row).clone())
+            .collect();
+
+        Self {
+            key: key.to_string(),
+            added,
+            removed,
+            changed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// One JSON object per added/removed/changed row, for feeding into other tools.
+    pub fn to_ndjson(&self) -> Vec<String> {
+        let added = self
+            .added
+            .iter()
+            .map(|row| json!({"change": "added", "key": row.get(&self.key), "row": row}));
+        let removed = self
+            .removed
+            .iter()
+            .map(|row| json!({"change": "removed", "key": row.get(&self.key), "row": row}));
+        let changed = self.changed.iter().map(|(key_value, changes)| {
+            let columns: BTreeMap<&str, Value> = changes
+                .iter()
+                .map(|c| {
+                    (
+                        c.column.as_str(),
+                        json!({"before": c.before, "after": c.after}),
+                    )
+                })
+                .collect();
+            json!({"change": "changed", "key": key_value, "columns": columns})
+        });
+
+        added
+            .chain(removed)
+            .chain(changed)
+            .map(|v| v.to_string())
+            .collect()
+    }
+}
+
+impl fmt::Display for ResultDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No differences (keyed by {}).", self.key);
+        }
+
+        writeln!(
+            f,
+            "{} added, {} removed, {} changed (keyed by {}):",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len(),
+            self.key
+        )?;
+
+        for row in &self.added {
+            let row = serde_json::to_string(row).map_err(|_| fmt::Error)?;
+            writeln!(f, "+ {row}")?;
+        }
+        for row in &self.removed {
+            let row = serde_json::to_string(row).map_err(|_| fmt::Error)?;
+            writeln!(f, "- {row}")?;
+        }
+        for (key_value, changes) in &self.changed {
+            writeln!(f, "~ {} = {}", self.key, key_value)?;
+            for change in changes {
+                writeln!(
+                    f,
+                    "    {}: {} -> {}",
+                    change.column, change.before, change.after
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn index_by_key<'a>(key: &str, rows: &'a [Row]) -> BTreeMap<String, &'a Row> {
+    rows.iter()
+        .map(|row| (key_string(row.get(key).unwrap_or(&Value::Null)), row))
+        .collect()
+}
+
+/// Strings are used as-is so keys read naturally in the printed diff.
+fn key_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn column_changes(before: &Row, after: &Row) -> Vec<ColumnChange> {
+    let mut columns: Vec<&String> = before.keys().chain(after.keys()).collect();
+    columns.sort();
+    columns.dedup();
+
+    columns
+        .into_iter()
+        .filter_map(|column| {
+            let old = before.get(column).cloned().unwrap_or(Value::Null);
+            let new = after.get(column).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| ColumnChange {
+                column: column.clone(),
+                before: old,
+                after: new,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,119 @@
+//! Encodes query results as an Arrow IPC stream, for analytical clients that would rather
+//! not parse JSON.
+//!
+//! Column types are derived from the outputs' schema types, like `materialize` does for
+//! SQLite: lists and enums are carried as JSON-encoded strings.
+
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, BooleanBuilder, Float64Builder, Int64Builder, StringBuilder};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::error::ArrowError;
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::RecordBatch;
+use trustfall::{FieldValue, TransparentValue};
+use trustfall_core::ir::indexed::IndexedQuery;
+
+use crate::ResultRow;
+
+pub(crate) const CONTENT_TYPE: &str = "application/vnd.apache.arrow.stream";
+
+/// Rows per record batch, so clients can start on the first batch before the last arrives.
+const BATCH_SIZE: usize = 1024;
+
+pub(crate) fn schema_for(query: &IndexedQuery) -> Schema {
+    Schema::new(
+        query
+            .outputs
+            .values()
+            .map(|output| {
+                let graphql_type = output.value_type.to_string();
+                let nullable = !graphql_type.ends_with('!');
+                let data_type = match graphql_type.trim_end_matches('!') {
+                    list if list.starts_with('[') => DataType::Utf8,
+                    "Int" => DataType::Int64,
+                    "Float" => DataType::Float64,
+                    "Boolean" => DataType::Boolean,
+                    _ => DataType::Utf8,
+                };
+                Field::new(output.name.as_ref(), data_type, nullable)
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+pub(crate) fn encode(schema: Schema, rows: Vec<ResultRow>) -> Result<Vec<u8>, ArrowError> {
+    let schema = Arc::new(schema);
+    let mut writer = StreamWriter::try_new(Vec::new(), &schema)?;
+
+    for chunk in rows.chunks(BATCH_SIZE) {
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| column(field, chunk))
+            .collect();
+        writer.write(&RecordBatch::try_new(schema.clone(), columns)?)?;
+    }
+
+    writer.finish()?;
+    writer.into_inner()
+}
+
+fn column(field: &Field, rows: &[ResultRow]) -> ArrayRef {
+    let values = rows
+        .iter()
+        .map(|row| row.get(field.name().as_str()).unwrap_or(&FieldValue::Null));
+
+    match field.data_type() {
+        DataType::Int64 => {
+            let mut builder = Int64Builder::with_capacity(rows.len());
+            for value in values {
+                builder.append_option(match value {
+                    FieldValue::Int64(i) => Some(*i),
+                    FieldValue::Uint64(u) => i64::try_from(*u).ok(),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Float64 => {
+            let mut builder = Float64Builder::with_capacity(rows.len());
+            for value in values {
+                builder.append_option(match value {
+                    FieldValue::Float64(f) => Some(*f),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Boolean => {
+            let mut builder = BooleanBuilder::with_capacity(rows.len());
+            for value in values {
+                builder.append_option(match value {
+                    FieldValue::Boolean(b) => Some(*b),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        _ => {
+            let mut builder = StringBuilder::new();
+            for value in values {
+                builder.append_option(to_text(value));
+            }
+            Arc::new(builder.finish())
+        }
+    }
+}
+
+fn to_text(value: &FieldValue) -> Option<String> {
+    match value {
+        FieldValue::Null => None,
+        FieldValue::String(s) => Some(s.clone()),
+        // Enums, timestamps and lists, as JSON.
+        other => match serde_json::to_value(TransparentValue::from(other.clone())).unwrap() {
+            serde_json::Value::String(s) => Some(s),
+            json => Some(json.to_string()),
+        },
+    }
+}
@@ -0,0 +1,357 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{env, fs};
+use trustfall::{FieldValue, TransparentValue};
+use trustfall_core::frontend::parse;
+use trustfall_core::interpreter::execution::interpret_ir;
+use trustfall_core::ir::indexed::IndexedQuery;
+use trustfall_gitlab_adapter::{
+    is_schema_compatible, schema, schema_text, GitlabAdapter, LocalAdapter, SCHEMA_VERSION,
+};
+
+mod diff;
+mod ipc;
+mod materialize;
+mod result_cache;
+mod schedule;
+mod serve;
+mod table;
+
+#[derive(Debug, Clone, Deserialize)]
+struct InputQuery<'a> {
+    query: &'a str,
+
+    args: BTreeMap<Arc<str>, FieldValue>,
+
+    /// The schema version the query was written against, if recorded.
+    #[serde(default, borrow)]
+    schema_version: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Json,
+    Ndjson,
+    Table,
+}
+
+#[derive(Debug, Clone)]
+struct QueryOptions {
+    format: OutputFormat,
+    columns: Option<Vec<String>>,
+    diff: Option<DiffOptions>,
+    /// Directories to serve instead of GitLab.
+    local: Vec<String>,
+    redact_secrets: bool,
+    /// Serve results from, and save them to, the on-disk result cache.
+    cache_ttl: Option<Duration>,
+    /// Stop after this many rows, to spare the API's rate limits.
+    max_results: usize,
+}
+
+#[derive(Debug, Clone)]
+struct DiffOptions {
+    previous_path: String,
+    key: Option<String>,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::Json,
+            columns: None,
+            diff: None,
+            local: Vec::new(),
+            redact_secrets: true,
+            cache_ttl: None,
+            max_results: 20,
+        }
+    }
+}
+
+impl QueryOptions {
+    fn parse(mut reversed_args: Vec<&str>) -> Self {
+        let mut options = Self::default();
+        let mut key = None;
+
+        while let Some(arg) = reversed_args.pop() {
+            match arg {
+                "--format" => match reversed_args.pop() {
+                    Some("json") => options.format = OutputFormat::Json,
+                    Some("ndjson") => options.format = OutputFormat::Ndjson,
+                    Some("table") => options.format = OutputFormat::Table,
+                    Some(other) => panic!("Unrecognized output format: {}", other),
+                    None => panic!("No output format provided"),
+                },
+                "--columns" => match reversed_args.pop() {
+                    Some(columns) => {
+                        options.columns = Some(
+                            columns
+                                .split(',')
+                                .map(|c| c.trim().to_string())
+                                .filter(|c| !c.is_empty())
+                                .collect(),
+                        );
+                        // Picking columns only makes sense for the table renderer.
+                        options.format = OutputFormat::Table;
+                    }
+                    None => panic!("No columns provided"),
+                },
+                "--diff" => match reversed_args.pop() {
+                    Some(path) => {
+                        options.diff = Some(DiffOptions {
+                            previous_path: path.to_string(),
+                            key: None,
+                        })
+                    }
+                    None => panic!("No previous results file provided"),
+                },
+                "--local" => match reversed_args.pop() {
+                    Some(dir) => options.local.push(dir.to_string()),
+                    None => panic!("No local directory provided"),
+                },
+                "--no-redact" => options.redact_secrets = false,
+                "--cache-ttl" => match reversed_args.pop().map(str::parse) {
+                    Some(Ok(seconds)) => options.cache_ttl = Some(Duration::from_secs(seconds)),
+                    _ => panic!("--cache-ttl takes a number of seconds"),
+                },
+                "--max-results" => match reversed_args.pop().map(str::parse) {
+                    Some(Ok(max_results)) => options.max_results = max_results,
+                    _ => panic!("--max-results takes a number"),
+                },
+                "--key" => match reversed_args.pop() {
+                    Some(column) => key = Some(column.to_string()),
+                    None => panic!("No key column provided"),
+                },
+                other => panic!("Unrecognized option given: {}", other),
+            }
+        }
+
+        match options.diff.as_mut() {
+            Some(diff) => diff.key = key,
+            None => assert!(
+                key.is_none(),
+                "--key is only meaningful together with --diff"
+            ),
+        }
+
+        options
+    }
+
+    /// Machine-readable formats keep stdout clean, so results can be redirected to a file.
+    fn log(&self, message: &str) {
+        if self.format == OutputFormat::Ndjson {
+            eprintln!("{message}");
+        } else {
+            println!("{message}");
+        }
+    }
+}
+
+type ResultRow = BTreeMap<Arc<str>, FieldValue>;
+
+/// Runs against GitLab, or against the `local` directories if any are given.
+fn run(
+    query: Arc<IndexedQuery>,
+    arguments: Arc<BTreeMap<Arc<str>, FieldValue>>,
+    local: &[String],
+    redact_secrets: bool,
+) -> Box<dyn Iterator<Item = ResultRow>> {
+    if local.is_empty() {
+        let adapter = Rc::new(GitlabAdapter::new().with_secret_redaction(redact_secrets));
+        interpret_ir(adapter, query, arguments).unwrap()
+    } else {
+        let adapter = Rc::new(LocalAdapter::new(local).with_secret_redaction(redact_secrets));
+        interpret_ir(adapter, query, arguments).unwrap()
+    }
+}
+
+fn execute_query(path: &str, options: QueryOptions) {
+    let content = fs::read_to_string(path).unwrap();
+
+    let input_query: InputQuery = ron::from_str(&content).unwrap();
+
+    if let Some(version) = input_query.schema_version {
+        if !is_schema_compatible(version) {
+            options.log(&format!(
+                "Warning: query was written for schema version {version}, \
+                but this build serves schema version {SCHEMA_VERSION}.\n"
+            ));
+        }
+    }
+
+    let query = parse(schema(), input_query.query).unwrap();
+    let arguments = Arc::new(input_query.args);
+    // Known up front, so a diff against zero current rows still has a key.
+    let outputs: Vec<Arc<str>> = query.outputs.keys().cloned().collect();
+
+    let max_results = options.max_results;
+
+    let cache = options.cache_ttl.map(result_cache::ResultCache::new);
+    let cache_key = result_cache::ResultCache::key(
+        input_query.query,
+        &arguments,
+        &options.local,
+        options.redact_secrets,
+        max_results,
+    );
+    let cached_rows = cache.as_ref().and_then(|cache| cache.get(&cache_key));
+
+    options.log("Executing query:");
+    options.log(input_query.query.trim());
+
+    // Printing "prettily" (without the enum wrapper that captures the value type)
+    // unfortunately takes a bit of ceremony at the moment.
+    options.log("\nQuery args:");
+    options.log(&format!(
+        "{:?}",
+        arguments
+            .as_ref()
+            .clone()
+            .into_iter()
+            .map(|(k, v)| (
+                k,
+                serde_json::to_string_pretty(&TransparentValue::from(v)).unwrap()
+            ))
+            .collect::<BTreeMap<_, _>>()
+    ));
+
+    options.log(&format!(
+        "\nGetting max {max_results} results to avoid exhausting rate limit budgets."
+    ));
+
+    let mut total_query_duration: Duration = Default::default();
+    let mut current_instant = Instant::now();
+    let mut buffered_rows = Vec::new();
+    let mut truncated = false;
+    let mut fetched_rows = Vec::new();
+    let from_cache = cached_rows.is_some();
+    let rows: Box<dyn Iterator<Item = ResultRow>> = match cached_rows {
+        Some(rows) => {
+            options.log(&format!("\nServing {} cached results.", rows.len()));
+            Box::new(rows.into_iter())
+        }
+        None => run(query, arguments, &options.local, options.redact_secrets),
+    };
+    for (index, data_item) in rows.enumerate() {
+        if cache.is_some() && !from_cache {
+            fetched_rows.push(data_item.clone());
+        }
+
+        let next_item_duration = current_instant.elapsed();
+        total_query_duration += next_item_duration;
+        let result_number = index + 1;
+
+        if options.diff.is_some() || options.format == OutputFormat::Table {
+            // Both diffs and column widths depend on every row,
+            // so these are produced once iteration ends.
+            buffered_rows.push(data_item);
+        } else {
+            // Use the value variant with an untagged enum serialization, to make the printout cleaner.
+            let data_item: BTreeMap<Arc<str>, TransparentValue> =
+                data_item.into_iter().map(|(k, v)| (k, v.into())).collect();
+
+            if options.format == OutputFormat::Ndjson {
+                println!("{}", serde_json::to_string(&data_item).unwrap());
+            } else {
+                println!(
+                    "\nResult {result_number} fetched in {next_item_duration:?}, {}",
+                    serde_json::to_string_pretty(&data_item).unwrap()
+                );
+            }
+        }
+
+        // Uncomment the following line when recording the shell session,
+        // to ensure each result gets at least one frame in the output.
+        // Otherwise, all results get dumped in the shell all at once.
+        // std::thread::sleep(Duration::from_millis(16));
+
+        // Safety valve: we're using rate-limited APIs.
+        // Don't exhaust entire API call budget at once!
+        if result_number == max_results {
+            options.log(&format!(
+                "\nFetched {max_results} results in {total_query_duration:?}; \
+                terminating iteration to avoid exhausting rate limit budget."
+            ));
+            truncated = true;
+            break;
+        }
+
+        current_instant = Instant::now();
+    }
+
+    if let (Some(cache), false) = (&cache, from_cache) {
+        if let Err(e) = cache.put(&cache_key, &fetched_rows) {
+            options.log(&format!("\nWarning: failed to cache results: {e}"));
+        }
+    }
+
+    if let Some(diff_options) = &options.diff {
+        if truncated {
+            options.log(
+                "\nWarning: results were truncated, rows past the limit will show up as removed.",
+            );
+        }
+
+        let previous = diff::read_ndjson(&diff_options.previous_path);
+        let key = diff_options
+            .key
+            .clone()
+            .or_else(|| diff::default_key(&outputs))
+            .expect("No --key given and no outputs to pick a default key from");
+        let result_diff = diff::ResultDiff::compute(&key, &previous, &buffered_rows);
+
+        if options.format == OutputFormat::Ndjson {
+            for change in result_diff.to_ndjson() {
+                println!("{change}");
+            }
+        } else {
+            println!("\n{result_diff}");
+        }
+    } else if options.format == OutputFormat::Table {
+        let mut renderer = table::TableRenderer::new();
+        if let Some(columns) = options.columns {
+            renderer = renderer.columns(columns);
+        }
+        println!("\n{}", renderer.render(&buffered_rows));
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let mut reversed_args: Vec<_> = args.iter().map(|x| x.as_str()).rev().collect();
+
+    reversed_args
+        .pop()
+        .expect("Expected the executable name to be the first argument, but was missing");
+
+    match reversed_args.pop() {
+        None => panic!("No command given"),
+        Some("query") => match reversed_args.pop() {
+            None => panic!("No filename provided"),
+            Some(path) => execute_query(path, QueryOptions::parse(reversed_args)),
+        },
+        Some("materialize") => match (reversed_args.pop(), reversed_args.pop()) {
+            (Some(path), Some(database)) => materialize::run(
+                path,
+                database,
+                materialize::MaterializeOptions::parse(reversed_args),
+            ),
+            _ => panic!("Usage: materialize <query file> <database file> [--table name]"),
+        },
+        Some("serve") => serve::run(serve::ServeOptions::parse(reversed_args)),
+        Some("schedule") => match reversed_args.pop() {
+            None => panic!("Usage: schedule <config file>"),
+            Some(path) => schedule::run(path),
+        },
+        Some("schema") => match reversed_args.pop() {
+            None => print!("{}", schema_text()),
+            Some("--version") => println!("{SCHEMA_VERSION}"),
+            Some(other) => panic!("Unrecognized option given: {}", other),
+        },
+        Some(cmd) => panic!("Unrecognized command given: {}", cmd),
+    }
+}
@@ -0,0 +1,207 @@
+//! Writes query results into SQLite, so BI tools can read periodically refreshed
+//! inventories without querying the GitLab API themselves.
+//!
+//! Each run replaces the table's contents in a single transaction, so readers never
+//! see a partially refreshed table, and records the refresh in `_materializations`.
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection};
+use trustfall::{FieldValue, TransparentValue};
+use trustfall_core::frontend::parse;
+use trustfall_core::ir::indexed::IndexedQuery;
+use trustfall_gitlab_adapter::schema;
+
+use crate::{InputQuery, ResultRow};
+
+#[derive(Debug, Clone)]
+pub(crate) struct MaterializeOptions {
+    /// Defaults to the query file's name, without its extension.
+    table: Option<String>,
+    local: Vec<String>,
+    redact_secrets: bool,
+}
+
+impl Default for MaterializeOptions {
+    fn default() -> Self {
+        Self {
+            table: None,
+            local: Vec::new(),
+            redact_secrets: true,
+        }
+    }
+}
+
+impl MaterializeOptions {
+    pub(crate) fn parse(mut reversed_args: Vec<&str>) -> Self {
+        let mut options = Self::default();
+
+        while let Some(arg) = reversed_args.pop() {
+            match arg {
+                "--table" => match reversed_args.pop() {
+                    Some(table) => options.table = Some(table.to_string()),
+                    None => panic!("No table name provided"),
+                },
+                "--local" => match reversed_args.pop() {
+                    Some(dir) => options.local.push(dir.to_string()),
+                    None => panic!("No local directory provided"),
+                },
+                "--no-redact" => options.redact_secrets = false,
+                other => panic!("Unrecognized option given: {}", other),
+            }
+        }
+
+        options
+    }
+}
+
+/// A result column, with its SQLite type derived from the output's schema type.
+#[derive(Debug, Clone)]
+struct Column {
+    name: Arc<str>,
+    sql_type: &'static str,
+    nullable: bool,
+}
+
+impl Column {
+    fn new(name: Arc<str>, graphql_type: &str) -> Self {
+        let nullable = !graphql_type.ends_with('!');
+        let sql_type = match graphql_type.trim_end_matches('!') {
+            // Lists are stored as JSON arrays.
+            list if list.starts_with('[') => "TEXT",
+            "Int" | "Boolean" => "INTEGER",
+            "Float" => "REAL",
+            _ => "TEXT",
+        };
+
+        Self {
+            name,
+            sql_type,
+            nullable,
+        }
+    }
+
+    fn definition(&self) -> String {
+        let constraint = if self.nullable { "" } else { " NOT NULL" };
+        format!("{} {}{}", quote(&self.name), self.sql_type, constraint)
+    }
+}
+
+pub(crate) fn run(path: &str, database: &str, options: MaterializeOptions) {
+    let content = fs::read_to_string(path).unwrap();
+    let input_query: InputQuery = ron::from_str(&content).unwrap();
+
+    let table = options.table.clone().unwrap_or_else(|| {
+        Path::new(path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().replace('-', "_"))
+            .expect("Query file has no name to derive a table name from")
+    });
+
+    let query = parse(schema(), input_query.query).unwrap();
+    let arguments = Arc::new(input_query.args);
+    let rows = crate::run(
+        query.clone(),
+        arguments,
+        &options.local,
+        options.redact_secrets,
+    );
+
+    let row_count = write_table(database, &table, input_query.query, &query, rows).unwrap();
+
+    println!("Wrote {row_count} rows to table {table} in {database}");
+}
+
+/// Replaces `table`'s contents with `rows` in one transaction, and records the refresh.
+/// Returns the number of rows written.
+pub(crate) fn write_table(
+    database: &str,
+    table: &str,
+    query_text: &str,
+    query: &IndexedQuery,
+    rows: impl Iterator<Item = ResultRow>,
+) -> rusqlite::Result<usize> {
+    let columns: Vec<Column> = query
+        .outputs
+        .values()
+        .map(|output| Column::new(output.name.clone(), &output.value_type.to_string()))
+        .collect();
+
+    let mut connection = Connection::open(database)?;
+    let transaction = connection.transaction()?;
+
+    transaction.execute_batch(&format!(
+        "DROP TABLE IF EXISTS {table};\nCREATE TABLE {table} ({columns});\n\
+        CREATE TABLE IF NOT EXISTS _materializations (\
+            table_name TEXT PRIMARY KEY, \
+            query TEXT NOT NULL, \
+            row_count INTEGER NOT NULL, \
+            refreshed_at TEXT NOT NULL\
+        );",
+        table = quote(table),
+        columns = columns
+            .iter()
+            .map(Column::definition)
+            .collect::<Vec<_>>()
+            .join(", "),
+    ))?;
+
+    let insert = format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        quote(table),
+        columns
+            .iter()
+            .map(|column| quote(&column.name))
+            .collect::<Vec<_>>()
+            .join(", "),
+        vec!["?"; columns.len()].join(", "),
+    );
+
+    let mut row_count = 0usize;
+    {
+        let mut statement = transaction.prepare(&insert)?;
+        for mut row in rows {
+            let values = columns
+                .iter()
+                .map(|column| to_sql(row.remove(&column.name).unwrap_or(FieldValue::Null)));
+            statement.execute(params_from_iter(values))?;
+            row_count += 1;
+        }
+    }
+
+    transaction.execute(
+        "INSERT OR REPLACE INTO _materializations (table_name, query, row_count, refreshed_at) \
+        VALUES (?1, ?2, ?3, datetime('now'))",
+        params![table, query_text.trim(), row_count as i64],
+    )?;
+    transaction.commit()?;
+
+    Ok(row_count)
+}
+
+fn to_sql(value: FieldValue) -> Value {
+    match value {
+        FieldValue::Null => Value::Null,
+        FieldValue::Int64(i) => Value::Integer(i),
+        FieldValue::Uint64(u) => match i64::try_from(u) {
+            Ok(i) => Value::Integer(i),
+            Err(_) => Value::Text(u.to_string()),
+        },
+        FieldValue::Float64(f) => Value::Real(f),
+        FieldValue::Boolean(b) => Value::Integer(b as i64),
+        FieldValue::String(s) => Value::Text(s),
+        // Enums, timestamps and lists, as JSON.
+        other => match serde_json::to_value(TransparentValue::from(other)).unwrap() {
+            serde_json::Value::String(s) => Value::Text(s),
+            json => Value::Text(json.to_string()),
+        },
+    }
+}
+
+/// Quotes an identifier, since output names and table names come from user input.
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
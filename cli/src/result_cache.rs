@@ -0,0 +1,110 @@
+//! Caches full result sets on disk, so re-running a saved report within the TTL returns
+//! instantly and spends no API budget.
+//!
+//! Entries are keyed by the query with its whitespace normalized, its arguments, the schema
+//! version, the options that change results: `--local` directories, redaction and the row
+//! limit, and the GitLab host and token the results were fetched with, so they're never
+//! served to another instance or to a user who can see less.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use trustfall::FieldValue;
+use trustfall_gitlab_adapter::{ClientConfig, SCHEMA_VERSION};
+
+use crate::ResultRow;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ResultCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl ResultCache {
+    /// Stores entries in `$TRUSTFALL_GITLAB_CACHE_DIR`, defaulting to
+    /// `$XDG_CACHE_HOME/trustfall-gitlab/results` or `~/.cache/trustfall-gitlab/results`.
+    pub(crate) fn new(ttl: Duration) -> Self {
+        let dir = env::var_os("TRUSTFALL_GITLAB_CACHE_DIR")
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("XDG_CACHE_HOME")
+                    .map(PathBuf::from)
+                    .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+                    .map(|cache| cache.join("trustfall-gitlab").join("results"))
+            })
+            .unwrap_or_else(|| env::temp_dir().join("trustfall-gitlab-results"));
+
+        Self { dir, ttl }
+    }
+
+    pub(crate) fn key(
+        query: &str,
+        arguments: &BTreeMap<Arc<str>, FieldValue>,
+        local: &[String],
+        redact_secrets: bool,
+        max_results: usize,
+    ) -> String {
+        let normalized_query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let mut hasher = DefaultHasher::new();
+        normalized_query.hash(&mut hasher);
+        serde_json::to_string(arguments).unwrap().hash(&mut hasher);
+        SCHEMA_VERSION.hash(&mut hasher);
+        local.hash(&mut hasher);
+        redact_secrets.hash(&mut hasher);
+        max_results.hash(&mut hasher);
+        if local.is_empty() {
+            // Only the key's hash ends up on disk, never the token itself. A config that
+            // fails to load fails the query too, so it needs no key of its own.
+            if let Ok(config) = ClientConfig::load() {
+                config.host.hash(&mut hasher);
+                let credentials = config.auth.and_then(|auth| auth.credentials().ok());
+                credentials
+                    .map(|credentials| credentials.header())
+                    .hash(&mut hasher);
+            }
+        }
+        format!("{:016x}", hasher.finish())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.ndjson"))
+    }
+
+    /// The cached rows, unless missing or older than the TTL.
+    pub(crate) fn get(&self, key: &str) -> Option<Vec<ResultRow>> {
+        let path = self.path(key);
+        let age = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+        if age > self.ttl {
+            return None;
+        }
+
+        // Rows keep their type tags, so they read back exactly as they were produced.
+        fs::read_to_string(path)
+            .ok()?
+            .lines()
+            .map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    pub(crate) fn put(&self, key: &str, rows: &[ResultRow]) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        let mut content = String::new();
+        for row in rows {
+            content.push_str(&serde_json::to_string(row).map_err(io::Error::from)?);
+            content.push('\n');
+        }
+        fs::write(self.path(key), content)
+    }
+}
@@ -0,0 +1,190 @@
+//! Runs saved queries on cron schedules from one long-lived process, writing each run's
+//! results to NDJSON files or SQLite tables.
+//!
+//! Jobs share one adapter, whose GitLab API responses are cached for `cache-ttl` seconds,
+//! so jobs that read the same projects around the same time only fetch them once:
+//!
+//! ```toml
+//! cache-ttl = 600
+//!
+//! [[job]]
+//! name = "repos"
+//! query = "sample-queries/weekly-report.ron"
+//! # sec min hour day-of-month month day-of-week
+//! schedule = "0 0 6 * * Mon"
+//! ndjson = "reports/repos.ndjson"
+//! sqlite = "inventory.sqlite"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use cron::Schedule;
+use serde::Deserialize;
+use trustfall::{FieldValue, TransparentValue};
+use trustfall_core::frontend::parse;
+use trustfall_core::interpreter::execution::interpret_ir;
+use trustfall_gitlab_adapter::cache::ResponseCache;
+use trustfall_gitlab_adapter::{schema, ClientConfig, GitlabAdapter};
+
+use crate::{materialize, InputQuery, ResultRow};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct ScheduleConfig {
+    /// Seconds API responses are shared between jobs.
+    #[serde(default = "default_cache_ttl")]
+    cache_ttl: u64,
+    #[serde(rename = "job")]
+    jobs: Vec<JobConfig>,
+}
+
+fn default_cache_ttl() -> u64 {
+    300
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct JobConfig {
+    name: String,
+    /// A query file, as taken by the `query` command.
+    query: String,
+    /// A cron expression, with a leading seconds field.
+    schedule: String,
+    /// Overwritten with the results of each run.
+    ndjson: Option<String>,
+    /// Materialized into a table named after the job.
+    sqlite: Option<String>,
+}
+
+struct Job {
+    config: JobConfig,
+    schedule: Schedule,
+    next_run: Option<DateTime<Local>>,
+}
+
+pub(crate) fn run(config_path: &str) {
+    let content = fs::read_to_string(config_path)
+        .unwrap_or_else(|e| panic!("Failed to read {config_path}: {e}"));
+    let config: ScheduleConfig =
+        toml::from_str(&content).unwrap_or_else(|e| panic!("Invalid schedule config: {e}"));
+
+    let mut jobs: Vec<Job> = config
+        .jobs
+        .into_iter()
+        .map(|config| {
+            assert!(
+                config.ndjson.is_some() || config.sqlite.is_some(),
+                "Job {} has no output, set `ndjson` or `sqlite`",
+                config.name,
+            );
+            let schedule = Schedule::from_str(&config.schedule).unwrap_or_else(|e| {
+                panic!("Invalid schedule for job {}: {e}", config.name);
+            });
+            let next_run = schedule.upcoming(Local).next();
+            Job {
+                config,
+                schedule,
+                next_run,
+            }
+        })
+        .collect();
+
+    let cache = Arc::new(ResponseCache::new(Duration::from_secs(config.cache_ttl)));
+    let client = ClientConfig::load()
+        .and_then(|config| config.build())
+        .unwrap_or_else(|e| panic!("Failed to initialize the Gitlab Client: {e}"))
+        .cached(cache);
+    let adapter = Rc::new(GitlabAdapter::with_client(client));
+
+    for job in &jobs {
+        match job.next_run {
+            Some(next_run) => eprintln!("Scheduled {} for {next_run}", job.config.name),
+            None => eprintln!("Job {} has no upcoming runs", job.config.name),
+        }
+    }
+
+    loop {
+        let job = match jobs
+            .iter_mut()
+            .filter(|job| job.next_run.is_some())
+            .min_by_key(|job| job.next_run)
+        {
+            Some(job) => job,
+            None => {
+                eprintln!("No jobs left to run");
+                return;
+            }
+        };
+
+        let next_run = job.next_run.unwrap();
+        if let Ok(wait) = (next_run - Local::now()).to_std() {
+            thread::sleep(wait);
+        }
+
+        let started = Instant::now();
+        // A resolver panicking on one job's data mustn't take the other jobs down with it.
+        match panic::catch_unwind(AssertUnwindSafe(|| run_job(&adapter, &job.config))) {
+            Ok(Ok(row_count)) => eprintln!(
+                "{}: wrote {row_count} rows in {:?}",
+                job.config.name,
+                started.elapsed()
+            ),
+            Ok(Err(e)) => eprintln!("{}: failed: {e}", job.config.name),
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                eprintln!("{}: panicked: {message}", job.config.name);
+            }
+        }
+
+        // From now rather than from `next_run`, so a run that overran its interval skips
+        // the runs it missed instead of firing them back to back.
+        job.next_run = job.schedule.after(&Local::now()).next();
+    }
+}
+
+fn run_job(adapter: &Rc<GitlabAdapter>, job: &JobConfig) -> Result<usize, String> {
+    let content = fs::read_to_string(&job.query).map_err(|e| format!("{}: {e}", job.query))?;
+    let input_query: InputQuery = ron::from_str(&content).map_err(|e| e.to_string())?;
+
+    let query = parse(schema(), input_query.query).map_err(|e| e.to_string())?;
+    let arguments: Arc<BTreeMap<Arc<str>, FieldValue>> = Arc::new(input_query.args);
+    let rows: Vec<ResultRow> = interpret_ir(adapter.clone(), query.clone(), arguments)
+        .map_err(|e| e.to_string())?
+        .collect();
+
+    if let Some(path) = &job.ndjson {
+        let mut content = String::new();
+        for row in &rows {
+            let row: BTreeMap<&Arc<str>, TransparentValue> =
+                row.iter().map(|(k, v)| (k, v.clone().into())).collect();
+            content.push_str(&serde_json::to_string(&row).map_err(|e| e.to_string())?);
+            content.push('\n');
+        }
+        fs::write(path, content).map_err(|e| format!("{path}: {e}"))?;
+    }
+
+    if let Some(database) = &job.sqlite {
+        materialize::write_table(
+            database,
+            &job.name.replace('-', "_"),
+            input_query.query,
+            &query,
+            rows.iter().cloned(),
+        )
+        .map_err(|e| format!("{database}: {e}"))?;
+    }
+
+    Ok(rows.len())
+}
@@ -0,0 +1,379 @@
+//! Serves queries over HTTP from a long-running process, so GitLab API responses can be
+//! cached across queries.
+//!
+//! - `POST /query` takes `{"query": "...", "args": {...}}` and returns the result rows,
+//!   as JSON, or as an Arrow IPC stream when the request accepts
+//!   `application/vnd.apache.arrow.stream`.
+//! - `POST /webhook` takes GitLab webhook and system hook events, and drops the cached
+//!   responses under the event's project and groups, and listings across projects like
+//!   `/issues` and `/search`, without waiting for the TTL. Other responses, like users,
+//!   still last until the TTL.
+//! - `GET /metrics` reports queries, API calls, cache hit rate and rate-limit headroom
+//!   in the Prometheus text format.
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::io::{Cursor, Read};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+use trustfall::{FieldValue, TransparentValue};
+use trustfall_core::frontend::parse;
+use trustfall_core::interpreter::execution::interpret_ir;
+use trustfall_gitlab_adapter::cache::{ResponseCache, WebhookEvent};
+use trustfall_gitlab_adapter::{schema, ClientConfig, GitlabAdapter};
+
+use crate::ipc;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ServeOptions {
+    listen: String,
+    cache_ttl: Duration,
+    /// Safety valve, like the `query` command's: the API is rate-limited.
+    max_results: usize,
+    /// Webhook requests must carry this in `X-Gitlab-Token`, if set.
+    webhook_secret: Option<String>,
+    redact_secrets: bool,
+}
+
+impl Default for ServeOptions {
+    fn default() -> Self {
+        Self {
+            listen: "127.0.0.1:8080".to_string(),
+            cache_ttl: Duration::from_secs(300),
+            max_results: 1000,
+            webhook_secret: env::var("GITLAB_WEBHOOK_SECRET").ok(),
+            redact_secrets: true,
+        }
+    }
+}
+
+impl ServeOptions {
+    pub(crate) fn parse(mut reversed_args: Vec<&str>) -> Self {
+        let mut options = Self::default();
+
+        while let Some(arg) = reversed_args.pop() {
+            match arg {
+                "--listen" => match reversed_args.pop() {
+                    Some(address) => options.listen = address.to_string(),
+                    None => panic!("No listen address provided"),
+                },
+                "--cache-ttl" => match reversed_args.pop().map(str::parse) {
+                    Some(Ok(seconds)) => options.cache_ttl = Duration::from_secs(seconds),
+                    _ => panic!("--cache-ttl takes a number of seconds"),
+                },
+                "--max-results" => match reversed_args.pop().map(str::parse) {
+                    Some(Ok(max_results)) => options.max_results = max_results,
+                    _ => panic!("--max-results takes a number"),
+                },
+                "--webhook-secret" => match reversed_args.pop() {
+                    Some(secret) => options.webhook_secret = Some(secret.to_string()),
+                    None => panic!("No webhook secret provided"),
+                },
+                "--no-redact" => options.redact_secrets = false,
+                other => panic!("Unrecognized option given: {}", other),
+            }
+        }
+
+        options
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryRequest {
+    query: String,
+    #[serde(default)]
+    args: BTreeMap<String, Value>,
+}
+
+struct Service {
+    adapter: Rc<GitlabAdapter>,
+    cache: Arc<ResponseCache>,
+    options: ServeOptions,
+    metrics: Metrics,
+}
+
+/// Requests are handled one at a time, so plain cells suffice.
+#[derive(Debug, Default)]
+struct Metrics {
+    queries: Cell<u64>,
+    query_errors: Cell<u64>,
+    webhook_events: Cell<u64>,
+    invalidated: Cell<u64>,
+}
+
+impl Metrics {
+    fn increment(counter: &Cell<u64>, by: u64) {
+        counter.set(counter.get() + by);
+    }
+}
+
+pub(crate) fn run(options: ServeOptions) {
+    let cache = Arc::new(ResponseCache::new(options.cache_ttl));
+    let client = ClientConfig::load()
+        .and_then(|config| config.build())
+        .unwrap_or_else(|e| panic!("Failed to initialize the Gitlab Client: {e}"))
+        .cached(cache.clone());
+
+    let server = Server::http(&options.listen)
+        .unwrap_or_else(|e| panic!("Failed to listen on {}: {e}", options.listen));
+    eprintln!("Listening on http://{}", options.listen);
+
+    let service = Service {
+        adapter: Rc::new(
+            GitlabAdapter::with_client(client).with_secret_redaction(options.redact_secrets),
+        ),
+        cache,
+        options,
+        metrics: Metrics::default(),
+    };
+
+    for mut request in server.incoming_requests() {
+        let response = service.handle(&mut request);
+        if let Err(e) = request.respond(response) {
+            eprintln!("Failed to send response: {e}");
+        }
+    }
+}
+
+impl Service {
+    fn handle(&self, request: &mut Request) -> Response<Cursor<Vec<u8>>> {
+        let mut body = Vec::new();
+        if let Err(e) = request.as_reader().read_to_end(&mut body) {
+            return error(400, &format!("Failed to read request body: {e}"));
+        }
+
+        match (request.method(), request.url()) {
+            (Method::Post, "/query") => {
+                let accept = header(request, "Accept");
+                self.query(&body, accept.as_deref())
+            }
+            (Method::Post, "/webhook") => {
+                let token = header(request, "X-Gitlab-Token");
+                self.webhook(token.as_deref(), &body)
+            }
+            (Method::Get, "/metrics") => self.metrics(),
+            (_, "/query" | "/webhook") => error(405, "Use POST"),
+            (_, "/metrics") => error(405, "Use GET"),
+            _ => error(404, "Not found"),
+        }
+    }
+
+    fn query(&self, body: &[u8], accept: Option<&str>) -> Response<Cursor<Vec<u8>>> {
+        Metrics::increment(&self.metrics.queries, 1);
+        let arrow = accept.map_or(false, |accept| accept.contains(ipc::CONTENT_TYPE));
+        let response = self.execute(body, arrow);
+        if response.status_code().0 != 200 {
+            Metrics::increment(&self.metrics.query_errors, 1);
+        }
+        response
+    }
+
+    fn execute(&self, body: &[u8], arrow: bool) -> Response<Cursor<Vec<u8>>> {
+        let request: QueryRequest = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return error(400, &format!("Invalid query request: {e}")),
+        };
+
+        let query = match parse(schema(), &request.query) {
+            Ok(query) => query,
+            Err(e) => return error(400, &format!("Invalid query: {e}")),
+        };
+        let arguments: BTreeMap<Arc<str>, FieldValue> = request
+            .args
+            .into_iter()
+            .map(|(name, value)| (Arc::from(name), to_field_value(value)))
+            .collect();
+
+        let rows = match interpret_ir(self.adapter.clone(), query.clone(), Arc::new(arguments)) {
+            Ok(rows) => rows,
+            Err(e) => return error(400, &format!("Invalid query arguments: {e}")),
+        };
+
+        let mut results = Vec::new();
+        let mut truncated = false;
+        for row in rows {
+            if results.len() == self.options.max_results {
+                truncated = true;
+                break;
+            }
+            results.push(row);
+        }
+
+        if arrow {
+            return match ipc::encode(ipc::schema_for(&query), results) {
+                Ok(stream) => Response::from_data(stream)
+                    .with_header(
+                        Header::from_bytes(&b"Content-Type"[..], ipc::CONTENT_TYPE.as_bytes())
+                            .unwrap(),
+                    )
+                    .with_header(
+                        Header::from_bytes(&b"X-Truncated"[..], truncated.to_string().as_bytes())
+                            .unwrap(),
+                    ),
+                Err(e) => error(500, &format!("Failed to encode results: {e}")),
+            };
+        }
+
+        let results: Vec<BTreeMap<Arc<str>, TransparentValue>> = results
+            .into_iter()
+            .map(|row| row.into_iter().map(|(k, v)| (k, v.into())).collect())
+            .collect();
+
+        respond_json(200, &json!({ "results": results, "truncated": truncated }))
+    }
+
+    fn webhook(&self, token: Option<&str>, body: &[u8]) -> Response<Cursor<Vec<u8>>> {
+        if let Some(secret) = &self.options.webhook_secret {
+            if token != Some(secret.as_str()) {
+                return error(401, "Missing or invalid X-Gitlab-Token");
+            }
+        }
+
+        let event = match WebhookEvent::from_json(body) {
+            Ok(event) => event,
+            Err(e) => return error(400, &format!("Invalid webhook payload: {e}")),
+        };
+        let invalidated = self.cache.invalidate(&event);
+        Metrics::increment(&self.metrics.webhook_events, 1);
+        Metrics::increment(&self.metrics.invalidated, invalidated as u64);
+        eprintln!(
+            "{} event for project {}: invalidated {invalidated} cached responses",
+            event.kind,
+            event
+                .project_path
+                .clone()
+                .or_else(|| event.project_id.map(|id| id.to_string()))
+                .unwrap_or_else(|| "<none>".to_string()),
+        );
+
+        respond_json(
+            200,
+            &json!({ "kind": event.kind, "invalidated": invalidated }),
+        )
+    }
+
+    fn metrics(&self) -> Response<Cursor<Vec<u8>>> {
+        let stats = self.cache.stats();
+
+        let mut body = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+            writeln!(body, "# HELP trustfall_gitlab_{name} {help}").unwrap();
+            writeln!(body, "# TYPE trustfall_gitlab_{name} {kind}").unwrap();
+            writeln!(body, "trustfall_gitlab_{name} {value}").unwrap();
+        };
+
+        let counters = [
+            (
+                "queries_total",
+                "Queries executed.",
+                self.metrics.queries.get(),
+            ),
+            (
+                "query_errors_total",
+                "Queries rejected or failed.",
+                self.metrics.query_errors.get(),
+            ),
+            (
+                "api_calls_total",
+                "Requests sent to the GitLab API.",
+                stats.api_calls,
+            ),
+            (
+                "cache_hits_total",
+                "API responses served from the cache.",
+                stats.hits,
+            ),
+            (
+                "cache_misses_total",
+                "API responses not found in the cache.",
+                stats.misses,
+            ),
+            (
+                "webhook_events_total",
+                "Webhook events received.",
+                self.metrics.webhook_events.get(),
+            ),
+            (
+                "cache_invalidations_total",
+                "Cached responses dropped by webhook events.",
+                self.metrics.invalidated.get(),
+            ),
+        ];
+        for (name, help, value) in counters {
+            metric(name, "counter", help, value.to_string());
+        }
+
+        metric(
+            "cache_hit_ratio",
+            "gauge",
+            "Share of cacheable API requests served from the cache.",
+            stats.hit_rate().to_string(),
+        );
+        metric(
+            "cache_entries",
+            "gauge",
+            "API responses currently cached.",
+            stats.entries.to_string(),
+        );
+        // Only known once GitLab has answered a request with rate-limit headers.
+        if let Some(rate_limit) = stats.rate_limit {
+            metric(
+                "rate_limit_limit",
+                "gauge",
+                "Requests allowed per rate-limit window.",
+                rate_limit.limit.to_string(),
+            );
+            metric(
+                "rate_limit_remaining",
+                "gauge",
+                "Requests left in the current rate-limit window.",
+                rate_limit.remaining.to_string(),
+            );
+        }
+
+        Response::from_data(body.into_bytes()).with_header(
+            Header::from_bytes(&b"Content-Type"[..], &b"text/plain; version=0.0.4"[..]).unwrap(),
+        )
+    }
+}
+
+/// Query arguments arrive as plain JSON, without the type tags RON query files use.
+fn to_field_value(value: Value) -> FieldValue {
+    match value {
+        Value::Null => FieldValue::Null,
+        Value::Bool(b) => FieldValue::Boolean(b),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(i), _) => FieldValue::Int64(i),
+            (None, Some(u)) => FieldValue::Uint64(u),
+            _ => FieldValue::Float64(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => FieldValue::String(s),
+        Value::Array(values) => FieldValue::List(values.into_iter().map(to_field_value).collect()),
+        Value::Object(_) => FieldValue::String(value.to_string()),
+    }
+}
+
+fn header(request: &Request, name: &str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.to_string())
+}
+
+fn respond_json(status: u16, body: &Value) -> Response<Cursor<Vec<u8>>> {
+    Response::from_data(serde_json::to_vec(body).unwrap())
+        .with_status_code(status)
+        .with_header(Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap())
+}
+
+fn error(status: u16, message: &str) -> Response<Cursor<Vec<u8>>> {
+    respond_json(status, &json!({ "error": message }))
+}
@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::sync::Arc;
+
+use trustfall::{FieldValue, TransparentValue};
+
+const DEFAULT_TERMINAL_WIDTH: usize = 120;
+const MIN_COLUMN_WIDTH: usize = 4;
+const COLUMN_SEPARATOR: &str = " │ ";
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_BOLD: &str = "\x1b[1m";
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_GREEN: &str = "\x1b[32m";
+
+/// Renders query results as a human-friendly table, for interactive exploration.
+///
+/// Columns default to every output name seen in the results, in the order trustfall
+/// returns them, but can be narrowed down (and reordered) with `columns`.
+#[derive(Debug, Clone)]
+pub struct TableRenderer {
+    columns: Option<Vec<String>>,
+    max_width: usize,
+    color: bool,
+}
+
+impl Default for TableRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableRenderer {
+    pub fn new() -> Self {
+        Self {
+            columns: None,
+            max_width: terminal_width(),
+            // https://no-color.org/
+            color: std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal(),
+        }
+    }
+
+    pub fn columns(mut self, columns: Vec<String>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn render(&self, rows: &[BTreeMap<Arc<str>, FieldValue>]) -> String {
+        let columns: Vec<String> = match &self.columns {
+            Some(columns) => columns.clone(),
+            None => {
+                let mut seen: Vec<String> = Vec::new();
+                for row in rows {
+                    for key in row.keys() {
+                        if !seen.iter().any(|c| c.as_str() == key.as_ref()) {
+                            seen.push(key.to_string());
+                        }
+                    }
+                }
+                seen
+            }
+        };
+
+        if columns.is_empty() {
+            return String::new();
+        }
+
+        let cells: Vec<Vec<Cell>> = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| Cell::from(row.get(column.as_str())))
+                    .collect()
+            })
+            .collect();
+
+        let widths = self.column_widths(&columns, &cells);
+
+        let mut output = String::new();
+
+        let header: Vec<String> = columns
+            .iter()
+            .zip(&widths)
+            .map(|(column, width)| self.paint(&pad(&truncate(column, *width), *width), ANSI_BOLD))
+            .collect();
+        output.push_str(header.join(COLUMN_SEPARATOR).trim_end());
+        output.push('\n');
+
+        let rule: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+        output.push_str(&rule.join("─┼─"));
+        output.push('\n');
+
+        for row in &cells {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| {
+                    let text = pad(&truncate(&cell.text, *width), *width);
+                    match cell.style {
+                        Some(style) => self.paint(&text, style),
+                        None => text,
+                    }
+                })
+                .collect();
+            output.push_str(line.join(COLUMN_SEPARATOR).trim_end());
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Natural column widths, shrunk widest-first until the table fits the terminal.
+    fn column_widths(&self, columns: &[String], cells: &[Vec<Cell>]) -> Vec<usize> {
+        let mut widths: Vec<usize> = columns.iter().map(|c| c.chars().count()).collect();
+        for row in cells {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.text.chars().count());
+            }
+        }
+
+        let separators = COLUMN_SEPARATOR.chars().count() * (columns.len() - 1);
+        let available = self.max_width.saturating_sub(separators);
+
+        while widths.iter().sum::<usize>() > available {
+            let (widest, width) = widths
+                .iter()
+                .copied()
+                .enumerate()
+                .max_by_key(|(_, w)| *w)
+                .expect("at least one column");
+            if width <= MIN_COLUMN_WIDTH {
+                break;
+            }
+            widths[widest] -= 1;
+        }
+
+        widths
+    }
+
+    fn paint(&self, text: &str, style: &str) -> String {
+        if self.color {
+            format!("{style}{text}{ANSI_RESET}")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+struct Cell {
+    text: String,
+    style: Option<&'static str>,
+}
+
+impl From<Option<&FieldValue>> for Cell {
+    fn from(value: Option<&FieldValue>) -> Self {
+        match value {
+            None | Some(FieldValue::Null) => Self {
+                text: "null".to_string(),
+                style: Some(ANSI_DIM),
+            },
+            Some(FieldValue::Boolean(true)) => Self {
+                text: "true".to_string(),
+                style: Some(ANSI_GREEN),
+            },
+            Some(FieldValue::Boolean(false)) => Self {
+                text: "false".to_string(),
+                style: Some(ANSI_RED),
+            },
+            Some(FieldValue::String(s)) => Self {
+                text: single_line(s),
+                style: None,
+            },
+            Some(other) => Self {
+                text: single_line(
+                    &serde_json::to_string(&TransparentValue::from(other.clone())).unwrap(),
+                ),
+                style: None,
+            },
+        }
+    }
+}
+
+/// File contents and descriptions routinely span lines, which would break the table layout.
+fn single_line(s: &str) -> String {
+    s.replace("\r\n", "⏎")
+        .replace(['\n', '\r'], "⏎")
+        .replace('\t', " ")
+}
+
+fn truncate(s: &str, width: usize) -> String {
+    if s.chars().count() <= width {
+        s.to_string()
+    } else {
+        let mut truncated: String = s.chars().take(width.saturating_sub(1)).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+fn pad(s: &str, width: usize) -> String {
+    let len = s.chars().count();
+    format!("{s}{}", " ".repeat(width.saturating_sub(len)))
+}
+
+/// Terminal width, overridden by `COLUMNS` when it's set, falling back to a reasonable
+/// default when stdout isn't a terminal.
+fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .or_else(|| {
+            terminal_size::terminal_size().map(|(terminal_size::Width(width), _)| width.into())
+        })
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
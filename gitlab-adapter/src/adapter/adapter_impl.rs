@@ -0,0 +1,284 @@
+//! Dispatch from type and field names to the resolvers in `properties` and `edges`,
+//! coercions, and the `BasicAdapter` impl on top of them.
+
+use trustfall::provider::{
+    resolve_coercion_with, resolve_neighbors_with, resolve_property_with, BasicAdapter,
+};
+use trustfall_core::interpreter::Typename;
+use trustfall_core::{
+    interpreter::{ContextIterator, ContextOutcomeIterator, VertexIterator},
+    ir::{EdgeParameters, FieldValue},
+};
+
+use super::{edges, entrypoints, properties};
+use super::{limit_param, CoercionResolver, GitlabAdapter, NeighborResolver, PropertyResolver};
+use crate::vertex::Vertex;
+
+/// Resolution is done one vertex at a time, so the same resolvers can serve both
+/// the `BasicAdapter` impl and adapter composition (see [`crate::compose`]).
+impl GitlabAdapter {
+    pub fn property_resolver(type_name: &str, property_name: &str) -> PropertyResolver {
+        if property_name == "__typename" {
+            return Box::new(|vertex: &Vertex| -> FieldValue { vertex.typename().into() });
+        }
+
+        match type_name {
+            "GitlabRepo" | "Repository" => properties::resolve_gitlab_repo_property(property_name),
+            "Namespace" => properties::resolve_namespace_property(property_name),
+            "Badge" => properties::resolve_badge_property(property_name),
+            "PagesDomain" => properties::resolve_pages_domain_property(property_name),
+            "PagesDeployment" => properties::resolve_pages_deployment_property(property_name),
+            "Integration" => properties::resolve_integration_property(property_name),
+            #[cfg(feature = "security")]
+            "AccessToken" => properties::resolve_access_token_property(property_name),
+            "Label" => properties::resolve_label_property(property_name),
+            "Milestone" => properties::resolve_milestone_property(property_name),
+            "MilestoneStats" => properties::resolve_milestone_stats_property(property_name),
+            "Timelog" => properties::resolve_timelog_property(property_name),
+            "EmailParticipant" => properties::resolve_email_participant_property(property_name),
+            "LabelEvent" => properties::resolve_label_event_property(property_name),
+            "StateEvent" => properties::resolve_state_event_property(property_name),
+            "Requirement" => properties::resolve_requirement_property(property_name),
+            "Iteration" => properties::resolve_iteration_property(property_name),
+            "Board" => properties::resolve_board_property(property_name),
+            "Note" => properties::resolve_note_property(property_name),
+            "AwardEmoji" => properties::resolve_award_emoji_property(property_name),
+            "Event" => properties::resolve_event_property(property_name),
+            "ContributionStats" => properties::resolve_contribution_stats_property(property_name),
+            "Epic" => properties::resolve_epic_property(property_name),
+            "GroupMember" => properties::resolve_group_member_property(property_name),
+            "BillableMember" => properties::resolve_billable_member_property(property_name),
+            "AccessRequest" => properties::resolve_access_request_property(property_name),
+            "BoardList" => properties::resolve_board_list_property(property_name),
+            "GitlabUser" | "CurrentUser" => properties::resolve_gitlab_user_property(property_name),
+            "Todo" => properties::resolve_todo_property(property_name),
+            "SearchResult" => properties::resolve_search_result_property(property_name),
+            "Issue" => properties::resolve_issue_property(property_name),
+            "MergeRequest" => properties::resolve_merge_request_property(property_name),
+            "SearchBlob" => properties::resolve_search_blob_property(property_name),
+            "GitlabInstance" => properties::resolve_gitlab_instance_property(property_name),
+            #[cfg(feature = "ci")]
+            "Pipeline" => properties::resolve_pipeline_property(property_name),
+            #[cfg(feature = "ci")]
+            "PipelineJob" => properties::resolve_pipeline_job_property(property_name),
+            #[cfg(feature = "ci")]
+            "PipelineCoverage" => properties::resolve_pipeline_coverage_property(property_name),
+            #[cfg(feature = "ci")]
+            "PipelineVariable" => properties::resolve_pipeline_variable_property(property_name),
+            #[cfg(feature = "ci")]
+            "PipelineSchedule" => properties::resolve_pipeline_schedule_property(property_name),
+            #[cfg(feature = "ci")]
+            "Runner" => properties::resolve_runner_property(property_name),
+            #[cfg(feature = "ci")]
+            "JobArtifact" => properties::resolve_job_artifact_property(property_name),
+            #[cfg(feature = "operations")]
+            "DoraMetric" => properties::resolve_dora_metric_property(property_name),
+            #[cfg(feature = "ci")]
+            "MergeTrainCar" => properties::resolve_merge_train_car_property(property_name),
+            #[cfg(feature = "operations")]
+            "Environment" => properties::resolve_environment_property(property_name),
+            #[cfg(feature = "operations")]
+            "Deployment" => properties::resolve_deployment_property(property_name),
+            #[cfg(feature = "operations")]
+            "ProtectedEnvironment" => {
+                properties::resolve_protected_environment_property(property_name)
+            }
+            #[cfg(feature = "operations")]
+            "EnvironmentAccessRule" => {
+                properties::resolve_environment_access_rule_property(property_name)
+            }
+            #[cfg(feature = "operations")]
+            "FeatureFlag" => properties::resolve_feature_flag_property(property_name),
+            #[cfg(feature = "operations")]
+            "FeatureFlagStrategy" => {
+                properties::resolve_feature_flag_strategy_property(property_name)
+            }
+            #[cfg(feature = "operations")]
+            "ErrorTrackingIssue" => {
+                properties::resolve_error_tracking_issue_property(property_name)
+            }
+            #[cfg(feature = "operations")]
+            "TerraformState" => properties::resolve_terraform_state_property(property_name),
+            #[cfg(feature = "operations")]
+            "ClusterAgent" => properties::resolve_cluster_agent_property(property_name),
+            #[cfg(feature = "operations")]
+            "ClusterAgentToken" => properties::resolve_cluster_agent_token_property(property_name),
+            #[cfg(feature = "ci")]
+            "TraceLine" => properties::resolve_trace_line_property(property_name),
+            #[cfg(feature = "ci")]
+            "TestCase" => properties::resolve_test_case_property(property_name),
+            #[cfg(feature = "ci")]
+            "CoverageReport" => properties::resolve_coverage_report_property(property_name),
+            #[cfg(feature = "ci")]
+            "CiConfig" => properties::resolve_ci_config_property(property_name),
+            #[cfg(feature = "ci")]
+            "CiConfigJob" => properties::resolve_ci_config_job_property(property_name),
+            #[cfg(feature = "ci")]
+            "CiInclude" => properties::resolve_ci_include_property(property_name),
+            "RepoFile" | "PythonRequirementsFile" | "CargoManifestFile" | "NpmPackageFile" => {
+                properties::resolve_repo_file_property(property_name)
+            }
+            #[cfg(feature = "ci")]
+            "GitlabCiFile" => properties::resolve_repo_file_property(property_name),
+            "ParsedFile" => properties::resolve_parsed_file_property(property_name),
+            "Directory" => properties::resolve_directory_property(property_name),
+            #[cfg(feature = "manifests")]
+            "ManifestDependency" => properties::resolve_manifest_dependency_property(property_name),
+            "Commit" => properties::resolve_commit_property(property_name),
+            #[cfg(feature = "history")]
+            "Branch" => properties::resolve_branch_property(property_name),
+            #[cfg(feature = "history")]
+            "BlameHunk" => properties::resolve_blame_hunk_property(property_name),
+            "FileDiff" => properties::resolve_file_diff_property(property_name),
+            _ => unreachable!("unknown type with properties: {type_name}"),
+        }
+    }
+
+    pub fn neighbor_resolver(
+        &self,
+        type_name: &str,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> NeighborResolver {
+        if let Err(e) = limit_param(parameters) {
+            eprintln!("Skipping {type_name}.{edge_name}: {e}");
+            return Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                Box::new(std::iter::empty())
+            });
+        }
+
+        match type_name {
+            "GitlabRepo" | "Repository" => {
+                edges::resolve_gitlab_repo_edge(self, edge_name, parameters)
+            }
+            "Namespace" => edges::resolve_namespace_edge(self, edge_name, parameters),
+            "GitlabUser" => edges::resolve_gitlab_user_edge(self, edge_name, parameters),
+            "CurrentUser" => edges::resolve_current_user_edge(self, edge_name, parameters),
+            "Issue" => edges::resolve_issue_edge(self, edge_name, parameters),
+            "Milestone" => edges::resolve_milestone_edge(self, edge_name),
+            "Iteration" => edges::resolve_iteration_edge(self, edge_name, parameters),
+            "Timelog" => edges::resolve_timelog_edge(edge_name),
+            "LabelEvent" => edges::resolve_label_event_edge(edge_name),
+            "StateEvent" => edges::resolve_state_event_edge(edge_name),
+            "Requirement" => edges::resolve_requirement_edge(edge_name),
+            "Board" => edges::resolve_board_edge(edge_name),
+            "Todo" => edges::resolve_todo_edge(self, edge_name),
+            "Note" => edges::resolve_note_edge(self, edge_name, parameters),
+            "AwardEmoji" => edges::resolve_award_emoji_edge(edge_name),
+            "Event" => edges::resolve_event_edge(self, edge_name),
+            "Epic" => edges::resolve_epic_edge(self, edge_name, parameters),
+            "GroupMember" => edges::resolve_group_member_edge(edge_name),
+            "BillableMember" => edges::resolve_billable_member_edge(edge_name),
+            "AccessRequest" => edges::resolve_access_request_edge(edge_name),
+            "BoardList" => edges::resolve_board_list_edge(edge_name),
+            "MergeRequest" => edges::resolve_merge_request_edge(self, edge_name, parameters),
+            "SearchBlob" => edges::resolve_search_blob_edge(self, edge_name),
+            "Directory" => edges::resolve_directory_edge(self, edge_name, parameters),
+            #[cfg(feature = "ci")]
+            "Pipeline" => edges::resolve_pipeline_edge(self, edge_name, parameters),
+            #[cfg(feature = "ci")]
+            "PipelineJob" => edges::resolve_pipeline_job_edge(self, edge_name, parameters),
+            #[cfg(feature = "ci")]
+            "PipelineCoverage" => edges::resolve_pipeline_coverage_edge(self, edge_name),
+            #[cfg(feature = "ci")]
+            "PipelineSchedule" => edges::resolve_pipeline_schedule_edge(edge_name),
+            #[cfg(feature = "ci")]
+            "Runner" => edges::resolve_runner_edge(self, edge_name, parameters),
+            #[cfg(feature = "ci")]
+            "MergeTrainCar" => edges::resolve_merge_train_car_edge(self, edge_name),
+            #[cfg(feature = "operations")]
+            "Environment" => edges::resolve_environment_edge(self, edge_name, parameters),
+            #[cfg(feature = "operations")]
+            "Deployment" => edges::resolve_deployment_edge(self, edge_name, parameters),
+            #[cfg(feature = "operations")]
+            "ProtectedEnvironment" => edges::resolve_protected_environment_edge(edge_name),
+            #[cfg(feature = "operations")]
+            "FeatureFlag" => edges::resolve_feature_flag_edge(edge_name),
+            #[cfg(feature = "operations")]
+            "ClusterAgent" => edges::resolve_cluster_agent_edge(self, edge_name, parameters),
+            #[cfg(feature = "ci")]
+            "CiConfig" => edges::resolve_ci_config_edge(edge_name),
+            #[cfg(feature = "ci")]
+            "GitlabCiFile" => edges::resolve_gitlab_ci_file_edge(self, edge_name),
+            #[cfg(feature = "manifests")]
+            "PythonRequirementsFile" | "CargoManifestFile" | "NpmPackageFile" => {
+                edges::resolve_manifest_file_edge(self, edge_name)
+            }
+            "RepoFile" | "ParsedFile" => edges::resolve_repo_file_edge(self, edge_name),
+            "Commit" => edges::resolve_commit_edge(self, edge_name, parameters),
+            #[cfg(feature = "history")]
+            "Branch" => edges::resolve_branch_edge(edge_name),
+            #[cfg(feature = "history")]
+            "BlameHunk" => edges::resolve_blame_hunk_edge(edge_name),
+            _ => unreachable!("unknown type with edges: {type_name}"),
+        }
+    }
+
+    pub fn coercion_resolver(type_name: &str, coerce_to_type: &str) -> CoercionResolver {
+        match (type_name, coerce_to_type) {
+            ("Repository", "GitlabRepo") => {
+                Box::new(|vertex: &Vertex| vertex.as_gitlab_repo().is_some())
+            }
+            ("SearchResult", "GitlabRepo" | "Issue" | "MergeRequest" | "SearchBlob") => {
+                let coerce_to_type = coerce_to_type.to_string();
+                Box::new(move |vertex: &Vertex| vertex.typename() == coerce_to_type)
+            }
+            ("RepoFile", "ParsedFile") => {
+                Box::new(|vertex: &Vertex| vertex.as_parsed_file().is_some())
+            }
+            #[cfg(feature = "ci")]
+            ("RepoFile", "GitlabCiFile") => {
+                Box::new(|vertex: &Vertex| vertex.as_gitlab_ci_file().is_some())
+            }
+            #[cfg(feature = "manifests")]
+            ("RepoFile", "PythonRequirementsFile" | "CargoManifestFile" | "NpmPackageFile") => {
+                let coerce_to_type = coerce_to_type.to_string();
+                Box::new(move |vertex: &Vertex| vertex.typename() == coerce_to_type)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl BasicAdapter<'static> for GitlabAdapter {
+    type Vertex = Vertex;
+
+    fn resolve_starting_vertices(
+        &self,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, Self::Vertex> {
+        entrypoints::resolve_starting_vertices(self, edge_name, parameters)
+    }
+
+    fn resolve_coercion(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &str,
+        coerce_to_type: &str,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, bool> {
+        resolve_coercion_with(contexts, Self::coercion_resolver(type_name, coerce_to_type))
+    }
+
+    fn resolve_property(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &str,
+        property_name: &str,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, FieldValue> {
+        resolve_property_with(contexts, Self::property_resolver(type_name, property_name))
+    }
+
+    fn resolve_neighbors(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &str,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, VertexIterator<'static, Self::Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            self.neighbor_resolver(type_name, edge_name, parameters),
+        )
+    }
+}
@@ -0,0 +1,3732 @@
+//! Edges, one function per vertex type dispatching on the edge name.
+
+#[cfg(feature = "ci")]
+use std::borrow::Cow;
+#[cfg(feature = "ci")]
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+#[cfg(feature = "ci")]
+use chrono::DateTime;
+use gitlab::api::common::{path_escaped, NameOrId};
+use gitlab::api::groups::Group;
+#[cfg(feature = "ci")]
+use gitlab::api::projects::pipelines::PipelineJobs;
+use gitlab::api::projects::repository::files::FileRawBuilder;
+use gitlab::api::projects::repository::TreeBuilder;
+use gitlab::api::projects::ProjectsBuilder;
+use gitlab::api::raw;
+use gitlab::api::users::User;
+use gitlab::api::{paged, ApiError, Pagination, Query};
+use gitlab::{ObjectType, RepoTreeObject};
+use trustfall_core::{
+    interpreter::VertexIterator,
+    ir::{EdgeParameters, FieldValue},
+};
+
+use super::endpoints::{Graphql, ProjectResources, Resources};
+use super::entrypoints::{commit_vertex, fetch_commit, get_project, repo_vertex};
+#[cfg(feature = "ci")]
+use super::job_trace;
+use super::{api_object, datetime_param, limit_param, GitlabAdapter, NeighborResolver};
+use crate::client::GitlabClient;
+use crate::file_parsers::FileParsers;
+#[cfg(feature = "history")]
+use crate::history::{self, RepoEdge};
+#[cfg(feature = "ci")]
+use crate::redaction::redact;
+#[cfg(feature = "ci")]
+use crate::reports::{cobertura_report, junit_test_cases};
+#[cfg(feature = "security")]
+use crate::vertex::AccessToken;
+use crate::vertex::{
+    AccessRequest, AwardEmoji, Badge, BillableMember, Board, BoardList, CommitSource,
+    ContributionStats, Directory, EmailParticipant, Epic, Event, FileContent, FileDiff, GitlabRepo,
+    GitlabUser, GroupMember, Integration, Issue, Iteration, Label, LabelEvent, LazyBlob,
+    MergeRequest, Milestone, MilestoneStats, Namespace, Note, PagesDeployment, PagesDomain,
+    RepoFile, Requirement, StateEvent, Timelog, Todo, Vertex,
+};
+#[cfg(feature = "ci")]
+use crate::vertex::{
+    CiConfig, CiConfigJob, CiInclude, JobArtifact, LazyPipelineDetails, LazyTrace, MergeTrainCar,
+    Pipeline, PipelineCoverage, PipelineJob, PipelineSchedule, PipelineVariable, Runner, TestCase,
+    TraceLine,
+};
+#[cfg(feature = "operations")]
+use crate::vertex::{
+    ClusterAgent, ClusterAgentToken, Deployment, DoraMetric, Environment, EnvironmentAccessRule,
+    ErrorTrackingIssue, FeatureFlag, FeatureFlagStrategy, ProtectedEnvironment, TerraformState,
+};
+
+pub(super) fn resolve_gitlab_repo_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        "files" => {
+            let ref_ = extract_string_param!(parameters, "ref");
+            let path = extract_string_param!(parameters, "path");
+            let recursive = extract_bool_param!(parameters, "recursive");
+            let pagination = tree_pagination(parameters);
+
+            let client = adapter.client.clone();
+            let parsers = adapter.file_parsers.clone();
+            let redact_secrets = adapter.redact_secrets;
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                match vertex.as_gitlab_repo() {
+                    Some(repo) => {
+                        let ref_ = ref_or_default_branch(&ref_, repo);
+
+                        GitlabAdapter::get_files_for_repo(
+                            &client,
+                            &parsers,
+                            redact_secrets,
+                            Arc::new(repo.clone()),
+                            ref_,
+                            path.clone(),
+                            recursive.unwrap_or(true),
+                            pagination,
+                        )
+                    }
+                    _ => unreachable!(),
+                }
+            })
+        }
+        "file" => {
+            let path = extract_string_param!(parameters, "path").unwrap();
+            let ref_ = extract_string_param!(parameters, "ref");
+
+            let client = adapter.client.clone();
+            let parsers = adapter.file_parsers.clone();
+            let redact_secrets = adapter.redact_secrets;
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                // Without a default branch, the repository is empty.
+                match ref_or_default_branch(&ref_, repo) {
+                    Some(ref_) => GitlabAdapter::get_file(
+                        &client,
+                        &parsers,
+                        redact_secrets,
+                        Arc::new(repo.clone()),
+                        &path,
+                        &ref_,
+                    ),
+                    None => Box::new(std::iter::empty()),
+                }
+            })
+        }
+        "directories" => {
+            let ref_ = extract_string_param!(parameters, "ref");
+            let path = extract_string_param!(parameters, "path");
+            let pagination = tree_pagination(parameters);
+
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                match ref_or_default_branch(&ref_, repo) {
+                    Some(ref_) => get_directories(
+                        &client,
+                        &Arc::new(repo.clone()),
+                        &ref_,
+                        path.as_deref(),
+                        pagination,
+                    ),
+                    None => Box::new(std::iter::empty()),
+                }
+            })
+        }
+        "namespace" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let repo = vertex.as_gitlab_repo().unwrap();
+            // From the raw project, since `gitlab::types::Namespace` keeps its id private.
+            match raw_field(repo.raw_json.as_deref(), "namespace") {
+                Some(namespace) => Box::new(std::iter::once(namespace_vertex(&namespace))),
+                None => Box::new(std::iter::empty()),
+            }
+        }),
+        "owner" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let repo = vertex.as_gitlab_repo().unwrap();
+            Box::new(
+                raw_field(repo.raw_json.as_deref(), "owner")
+                    .and_then(user_vertex)
+                    .into_iter(),
+            )
+        }),
+        "creator" => {
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let creator_id = match &repo.project {
+                    Some(project) => project.creator_id.value(),
+                    None => return Box::new(std::iter::empty()),
+                };
+
+                let endpoint = User::builder().user(creator_id).build().unwrap();
+                let creator: Result<serde_json::Value, _> = endpoint.query(&*client);
+                match creator {
+                    Ok(creator) => Box::new(user_vertex(creator).into_iter()),
+                    // Deleted accounts are gone from the API, leaving the project orphaned.
+                    Err(ApiError::Gitlab { msg }) if msg.starts_with("404") => {
+                        Box::new(std::iter::empty())
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to get user {creator_id}: {:?}", e);
+                        Box::new(std::iter::empty())
+                    }
+                }
+            })
+        }
+        "badges" => {
+            let limit = limit_param(parameters).ok().flatten();
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let endpoint = ProjectResources::new(repo.id.as_str(), "badges");
+                let badges: Vec<serde_json::Value> =
+                    match paged(endpoint, limit.map_or(Pagination::All, Pagination::Limit))
+                        .query(&*client)
+                    {
+                        Ok(badges) => badges,
+                        Err(e) => {
+                            eprintln!("Failed to get badges for repo: {:?}", e);
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+
+                let vertices: Vec<Vertex> = badges
+                    .into_iter()
+                    .filter_map(api_object::<Badge>)
+                    .map(|badge| Vertex::Badge(badge.into()))
+                    .collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        "pages_domains" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let endpoint = ProjectResources::new(repo.id.as_str(), "pages/domains");
+                let domains: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(domains) => domains,
+                        Err(e) => {
+                            eprintln!("Failed to get Pages domains of {}: {:?}", repo.id, e);
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+
+                let vertices: Vec<Vertex> = domains
+                    .into_iter()
+                    .filter_map(api_object::<PagesDomain>)
+                    .map(|domain| Vertex::PagesDomain(domain.into()))
+                    .collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        "integrations" => {
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                // Only the active ones, and not paginated.
+                let endpoint = Resources::new(format!("projects/{}/integrations", repo.id));
+                let integrations: Vec<serde_json::Value> = match endpoint.query(&*client) {
+                    Ok(integrations) => integrations,
+                    Err(e) => {
+                        eprintln!("Failed to get integrations of {}: {:?}", repo.id, e);
+                        return Box::new(std::iter::empty());
+                    }
+                };
+
+                // Only each integration's own endpoint has its settings, without secrets.
+                let mut vertices = Vec::new();
+                for integration in integrations {
+                    let endpoint = Resources::new(format!(
+                        "projects/{}/integrations/{}",
+                        repo.id,
+                        integration["slug"].as_str().unwrap_or_default()
+                    ));
+                    let integration = match endpoint.query(&*client) {
+                        Ok(details) => details,
+                        Err(e) => {
+                            eprintln!("Failed to get integration {}: {:?}", integration["slug"], e);
+                            integration
+                        }
+                    };
+                    let events: Vec<String> = integration
+                        .as_object()
+                        .into_iter()
+                        .flatten()
+                        .filter(|(key, value)| key.ends_with("_events") && **value == true)
+                        .map(|(key, _)| key.trim_end_matches("_events").to_string())
+                        .collect();
+                    if let Some(mut integration) = api_object::<Integration>(integration) {
+                        integration.events = events;
+                        vertices.push(Vertex::Integration(integration.into()));
+                    }
+                }
+                Box::new(vertices.into_iter())
+            })
+        }
+        "events" => events_edge(adapter, parameters, |vertex| {
+            format!("projects/{}/events", vertex.as_gitlab_repo().unwrap().id)
+        }),
+        "access_requests" => access_requests_edge(adapter, parameters, |vertex| {
+            Some(format!(
+                "projects/{}/access_requests",
+                vertex.as_gitlab_repo().unwrap().id
+            ))
+        }),
+        "requirements" => {
+            // The GraphQL API's enum, e.g. `OPENED`.
+            let state =
+                extract_string_param!(parameters, "state").map(|state| state.to_uppercase());
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let full_path = match &repo.project {
+                    Some(project) => project.path_with_namespace.clone(),
+                    None => return Box::new(std::iter::empty()),
+                };
+                let variables = serde_json::json!({ "fullPath": full_path, "state": state });
+                let requirements: Vec<Vertex> = graphql_nodes(
+                    &client,
+                    REQUIREMENTS_QUERY,
+                    variables,
+                    "/project/requirements",
+                    pagination,
+                )
+                .into_iter()
+                .filter_map(api_object::<Requirement>)
+                .map(|requirement| Vertex::Requirement(requirement.into()))
+                .collect();
+                Box::new(requirements.into_iter())
+            })
+        }
+        #[cfg(feature = "security")]
+        "access_tokens" => access_tokens_edge(adapter, parameters, |vertex| {
+            Resources::new(format!(
+                "projects/{}/access_tokens",
+                vertex.as_gitlab_repo().unwrap().id
+            ))
+        }),
+        "boards" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let endpoint = Resources::new(format!("projects/{}/boards", repo.id));
+                boards(&client, endpoint, pagination)
+            })
+        }
+        "pages_deployments" => {
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                // Not a list: the project's Pages settings, with its deployments nested.
+                let endpoint = Resources::new(format!("projects/{}/pages", repo.id));
+                let mut pages: serde_json::Value = match endpoint.query(&*client) {
+                    Ok(pages) => pages,
+                    Err(e) => {
+                        eprintln!("Failed to get Pages of {}: {:?}", repo.id, e);
+                        return Box::new(std::iter::empty());
+                    }
+                };
+
+                let deployments = match pages["deployments"].take() {
+                    serde_json::Value::Array(deployments) => deployments,
+                    _ => Vec::new(),
+                };
+                let vertices: Vec<Vertex> = deployments
+                    .into_iter()
+                    .filter_map(api_object::<PagesDeployment>)
+                    .map(|deployment| Vertex::PagesDeployment(deployment.into()))
+                    .collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        #[cfg(feature = "history")]
+        "commits" | "branches" | "blame" => {
+            let edge = RepoEdge::new(edge_name, parameters);
+            let client = adapter.client.clone();
+            let history = adapter.history.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                edge.or_ref(repo.default_branch.as_deref())
+                    .resolve(history.open(repo, client.token()), Some(&repo.id))
+            })
+        }
+        #[cfg(feature = "ci")]
+        "pipelines" => {
+            let mut filters = Vec::new();
+            for name in ["status", "source", "ref"] {
+                if let Some(value) = extract_string_param!(parameters, name) {
+                    filters.push((name, value));
+                }
+            }
+            for name in ["updated_after", "updated_before"] {
+                match datetime_param(parameters, name) {
+                    Ok(Some(value)) => filters.push((name, value.to_rfc3339())),
+                    Ok(None) => {}
+                    Err(e) => {
+                        eprintln!("Skipping GitlabRepo.pipelines: {e}");
+                        return Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                            Box::new(std::iter::empty())
+                        });
+                    }
+                }
+            }
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let endpoint = filters.iter().fold(
+                    Resources::new(format!("projects/{}/pipelines", repo.id)),
+                    |endpoint, (name, value)| endpoint.param(*name, value),
+                );
+                pipelines(&client, pagination, endpoint)
+            })
+        }
+        #[cfg(feature = "ci")]
+        "latest_pipeline" => {
+            let ref_ = extract_string_param!(parameters, "ref");
+
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let ref_ = match ref_or_default_branch(&ref_, repo) {
+                    Some(ref_) => ref_,
+                    None => return Box::new(std::iter::empty()),
+                };
+
+                let endpoint = Resources::new(format!("projects/{}/pipelines/latest", repo.id))
+                    .param("ref", &ref_);
+                let pipeline: Result<serde_json::Value, _> = endpoint.query(&*client);
+                match pipeline {
+                    Ok(pipeline) => Box::new(
+                        api_object::<Pipeline>(pipeline)
+                            .map(|pipeline| Vertex::Pipeline(pipeline.into()))
+                            .into_iter(),
+                    ),
+                    // The ref has no pipelines yet.
+                    Err(ApiError::Gitlab { msg }) if msg.starts_with("404") => {
+                        Box::new(std::iter::empty())
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to get the latest pipeline of {}: {:?}", repo.id, e);
+                        Box::new(std::iter::empty())
+                    }
+                }
+            })
+        }
+        #[cfg(feature = "ci")]
+        "merged_ci_config" => {
+            let ref_ = extract_string_param!(parameters, "ref");
+
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let ref_ = match ref_or_default_branch(&ref_, repo) {
+                    Some(ref_) => ref_,
+                    None => return Box::new(std::iter::empty()),
+                };
+
+                Box::new(
+                    lint_ci_config(&client, &repo.id, &ref_)
+                        .map(|config| Vertex::CiConfig(config.into()))
+                        .into_iter(),
+                )
+            })
+        }
+        #[cfg(feature = "ci")]
+        "pipeline_schedules" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let endpoint = Resources::new(format!("projects/{}/pipeline_schedules", repo.id));
+                let schedules: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(schedules) => schedules,
+                        Err(e) => {
+                            eprintln!("Failed to get pipeline schedules of {}: {:?}", repo.id, e);
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+
+                // Only each schedule's own endpoint tells its last pipeline.
+                let mut vertices = Vec::new();
+                for schedule in schedules {
+                    let endpoint = Resources::new(format!(
+                        "projects/{}/pipeline_schedules/{}",
+                        repo.id, schedule["id"]
+                    ));
+                    let schedule = match endpoint.query(&*client) {
+                        Ok(details) => details,
+                        Err(e) => {
+                            eprintln!(
+                                "Failed to get pipeline schedule {}: {:?}",
+                                schedule["id"], e
+                            );
+                            schedule
+                        }
+                    };
+                    if let Some(schedule) = api_object::<PipelineSchedule>(schedule) {
+                        vertices.push(Vertex::PipelineSchedule(schedule.into()));
+                    }
+                }
+                Box::new(vertices.into_iter())
+            })
+        }
+        #[cfg(feature = "ci")]
+        "runners" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                runners(
+                    &client,
+                    pagination,
+                    Resources::new(format!("projects/{}/runners", repo.id)),
+                )
+            })
+        }
+        #[cfg(feature = "operations")]
+        "dora_metrics" => {
+            let params = dora_params(parameters);
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                dora_metrics(&client, &format!("projects/{}", repo.id), &params)
+            })
+        }
+        #[cfg(feature = "ci")]
+        "merge_trains" => {
+            let target_branch = extract_string_param!(parameters, "target_branch");
+            let scope =
+                extract_string_param!(parameters, "scope").unwrap_or_else(|| "active".to_string());
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                // One branch's train is filtered by GitLab, so `limit` counts its cars only.
+                let path = match &target_branch {
+                    Some(branch) => {
+                        format!("projects/{}/merge_trains/{}", repo.id, path_escaped(branch))
+                    }
+                    None => format!("projects/{}/merge_trains", repo.id),
+                };
+                // Oldest first, so cars are in train order.
+                let endpoint = Resources::new(path)
+                    .param("scope", &scope)
+                    .param("sort", "asc");
+                let cars: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*client)
+                {
+                    Ok(cars) => cars,
+                    Err(e) => {
+                        eprintln!("Failed to get merge trains of {}: {:?}", repo.id, e);
+                        return Box::new(std::iter::empty());
+                    }
+                };
+
+                let mut positions: BTreeMap<String, u64> = BTreeMap::new();
+                let cars: Vec<Vertex> = cars
+                    .into_iter()
+                    .filter_map(api_object::<MergeTrainCar>)
+                    .map(|mut car| {
+                        if !matches!(car.status.as_str(), "merged" | "skip_merged") {
+                            let position = positions.entry(car.target_branch.clone()).or_default();
+                            *position += 1;
+                            car.position = Some(*position);
+                        }
+                        Vertex::MergeTrainCar(car.into())
+                    })
+                    .collect();
+                Box::new(cars.into_iter())
+            })
+        }
+        #[cfg(feature = "operations")]
+        "environments" => {
+            let search = extract_string_param!(parameters, "search");
+            let state = extract_string_param!(parameters, "state");
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let mut endpoint = Resources::new(format!("projects/{}/environments", repo.id));
+                if let Some(search) = &search {
+                    endpoint = endpoint.param("search", search);
+                }
+                if let Some(state) = &state {
+                    endpoint = endpoint.param("states", state);
+                }
+                let environments: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(environments) => environments,
+                        Err(e) => {
+                            eprintln!("Failed to get environments of {}: {:?}", repo.id, e);
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+
+                let project_id = repo.id.parse().unwrap_or_default();
+                let vertices: Vec<Vertex> = environments
+                    .into_iter()
+                    .filter_map(api_object::<Environment>)
+                    .map(|mut environment| {
+                        environment.project_id = project_id;
+                        Vertex::Environment(environment.into())
+                    })
+                    .collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        #[cfg(feature = "operations")]
+        "protected_environments" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let endpoint =
+                    Resources::new(format!("projects/{}/protected_environments", repo.id));
+                let environments: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(environments) => environments,
+                        Err(e) => {
+                            eprintln!(
+                                "Failed to get protected environments of {}: {:?}",
+                                repo.id, e
+                            );
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+
+                let vertices: Vec<Vertex> = environments
+                    .into_iter()
+                    .filter_map(api_object::<ProtectedEnvironment>)
+                    .map(|environment| Vertex::ProtectedEnvironment(environment.into()))
+                    .collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        #[cfg(feature = "operations")]
+        "feature_flags" => {
+            let scope = extract_string_param!(parameters, "scope");
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let mut endpoint = Resources::new(format!("projects/{}/feature_flags", repo.id));
+                if let Some(scope) = &scope {
+                    endpoint = endpoint.param("scope", scope);
+                }
+                let flags: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(flags) => flags,
+                        Err(e) => {
+                            eprintln!("Failed to get feature flags of {}: {:?}", repo.id, e);
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+
+                let vertices: Vec<Vertex> = flags
+                    .into_iter()
+                    .filter_map(api_object::<FeatureFlag>)
+                    .map(|flag| Vertex::FeatureFlag(flag.into()))
+                    .collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        #[cfg(feature = "operations")]
+        "error_tracking_issues" => {
+            let search = extract_string_param!(parameters, "search");
+            let sort = extract_string_param!(parameters, "sort");
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let full_path = match &repo.project {
+                    Some(project) => project.path_with_namespace.clone(),
+                    None => return Box::new(std::iter::empty()),
+                };
+                let variables = serde_json::json!({
+                    "fullPath": full_path,
+                    "searchTerm": search,
+                    "sort": sort,
+                });
+                let issues: Vec<Vertex> = graphql_nodes(
+                    &client,
+                    SENTRY_ERRORS_QUERY,
+                    variables,
+                    "/project/sentryErrors/errors",
+                    pagination,
+                )
+                .into_iter()
+                .filter_map(api_object::<ErrorTrackingIssue>)
+                .map(|issue| Vertex::ErrorTrackingIssue(issue.into()))
+                .collect();
+                Box::new(issues.into_iter())
+            })
+        }
+        #[cfg(feature = "operations")]
+        "terraform_states" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let full_path = match &repo.project {
+                    Some(project) => project.path_with_namespace.clone(),
+                    None => return Box::new(std::iter::empty()),
+                };
+                let variables = serde_json::json!({ "fullPath": full_path });
+                let states: Vec<Vertex> = graphql_nodes(
+                    &client,
+                    TERRAFORM_STATES_QUERY,
+                    variables,
+                    "/project/terraformStates",
+                    pagination,
+                )
+                .into_iter()
+                .filter_map(api_object::<TerraformState>)
+                .map(|state| Vertex::TerraformState(state.into()))
+                .collect();
+                Box::new(states.into_iter())
+            })
+        }
+        #[cfg(feature = "operations")]
+        "cluster_agents" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let repo = vertex.as_gitlab_repo().unwrap();
+                let project_id = repo.id.parse().unwrap_or_default();
+                let endpoint = Resources::new(format!("projects/{}/cluster_agents", repo.id));
+                let agents: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(agents) => agents,
+                        Err(e) => {
+                            eprintln!("Failed to get cluster agents of {}: {:?}", repo.id, e);
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+
+                let vertices: Vec<Vertex> = agents
+                    .into_iter()
+                    .filter_map(api_object::<ClusterAgent>)
+                    .map(|mut agent| {
+                        agent.project_id = project_id;
+                        Vertex::ClusterAgent(agent.into())
+                    })
+                    .collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        _ => unreachable!("unknown GitlabRepo edge: {edge_name}"),
+    }
+}
+
+/// The `ref` parameter, or else the project's default branch. GitLab would pick that
+/// branch by itself, but reading it from the project makes sure it's the one queries see
+/// as `default_branch`.
+fn ref_or_default_branch(ref_: &Option<String>, repo: &GitlabRepo) -> Option<String> {
+    ref_.clone().or_else(|| repo.default_branch.clone())
+}
+
+pub(super) fn resolve_namespace_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        #[cfg(feature = "operations")]
+        "dora_metrics" => {
+            let params = dora_params(parameters);
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let namespace = vertex.as_namespace().unwrap();
+                // Users' namespaces have no metrics of their own.
+                if namespace.kind != "group" {
+                    return Box::new(std::iter::empty());
+                }
+                dora_metrics(&client, &format!("groups/{}", namespace.id), &params)
+            })
+        }
+        "boards" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let namespace = vertex.as_namespace().unwrap();
+                if namespace.kind != "group" {
+                    return Box::new(std::iter::empty());
+                }
+                let endpoint = Resources::new(format!("groups/{}/boards", namespace.id));
+                boards(&client, endpoint, pagination)
+            })
+        }
+        "epics" => {
+            let state = extract_string_param!(parameters, "state");
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let namespace = vertex.as_namespace().unwrap();
+                if namespace.kind != "group" {
+                    return Box::new(std::iter::empty());
+                }
+                let mut endpoint = Resources::new(format!("groups/{}/epics", namespace.id));
+                if let Some(state) = &state {
+                    endpoint = endpoint.param("state", state);
+                }
+                epics(&client, endpoint, pagination)
+            })
+        }
+        "iterations" => {
+            let state = extract_string_param!(parameters, "state");
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let namespace = vertex.as_namespace().unwrap();
+                if namespace.kind != "group" {
+                    return Box::new(std::iter::empty());
+                }
+                let mut endpoint = Resources::new(format!("groups/{}/iterations", namespace.id));
+                if let Some(state) = &state {
+                    endpoint = endpoint.param("state", state);
+                }
+
+                let iterations: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(iterations) => iterations,
+                        Err(e) => {
+                            eprintln!("Failed to get iterations of {}: {:?}", namespace.id, e);
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+                let vertices: Vec<Vertex> = iterations
+                    .into_iter()
+                    .filter_map(api_object::<Iteration>)
+                    .map(|iteration| Vertex::Iteration(iteration.into()))
+                    .collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        "access_requests" => access_requests_edge(adapter, parameters, |vertex| {
+            let namespace = vertex.as_namespace().unwrap();
+            (namespace.kind == "group").then(|| format!("groups/{}/access_requests", namespace.id))
+        }),
+        "members" | "billable_members" => {
+            let billable = edge_name == "billable_members";
+            let inherited = extract_bool_param!(parameters, "inherited").unwrap_or(false);
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let namespace = vertex.as_namespace().unwrap();
+                if namespace.kind != "group" {
+                    return Box::new(std::iter::empty());
+                }
+                let path = match (billable, inherited) {
+                    (true, _) => format!("groups/{}/billable_members", namespace.id),
+                    (false, true) => format!("groups/{}/members/all", namespace.id),
+                    (false, false) => format!("groups/{}/members", namespace.id),
+                };
+
+                let members: Vec<serde_json::Value> =
+                    match paged(Resources::new(path), pagination).query(&*client) {
+                        Ok(members) => members,
+                        Err(e) => {
+                            eprintln!("Failed to get members of {}: {:?}", namespace.id, e);
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+                let vertices: Vec<Vertex> = if billable {
+                    members
+                        .into_iter()
+                        .filter_map(api_object::<BillableMember>)
+                        .map(|member| Vertex::BillableMember(member.into()))
+                        .collect()
+                } else {
+                    members
+                        .into_iter()
+                        .filter_map(api_object::<GroupMember>)
+                        .map(|member| Vertex::GroupMember(member.into()))
+                        .collect()
+                };
+                Box::new(vertices.into_iter())
+            })
+        }
+        "parent" => {
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let namespace = vertex.as_namespace().unwrap();
+                let parent_path = match namespace.full_path.rsplit_once('/') {
+                    Some((parent_path, _)) if namespace.kind == "group" => parent_path,
+                    _ => return Box::new(std::iter::empty()),
+                };
+
+                let endpoint = Group::builder().group(parent_path).build().unwrap();
+                let parent: Result<serde_json::Value, _> = endpoint.query(&*client);
+                match parent {
+                    Ok(parent) => Box::new(std::iter::once(namespace_vertex(&parent))),
+                    Err(e) => {
+                        eprintln!("Failed to get group {parent_path}: {:?}", e);
+                        Box::new(std::iter::empty())
+                    }
+                }
+            })
+        }
+        _ => unreachable!("unknown Namespace edge: {edge_name}"),
+    }
+}
+
+/// The issue boards listed by `endpoint`, a project's or a group's.
+fn boards(
+    client: &GitlabClient,
+    endpoint: Resources,
+    pagination: Pagination,
+) -> VertexIterator<'static, Vertex> {
+    let boards: Vec<serde_json::Value> = match paged(endpoint, pagination).query(client) {
+        Ok(boards) => boards,
+        Err(e) => {
+            eprintln!("Failed to get boards: {:?}", e);
+            return Box::new(std::iter::empty());
+        }
+    };
+
+    let vertices: Vec<Vertex> = boards
+        .into_iter()
+        .filter_map(api_object::<Board>)
+        .map(|board| Vertex::Board(board.into()))
+        .collect();
+    Box::new(vertices.into_iter())
+}
+
+fn epics(
+    client: &GitlabClient,
+    endpoint: Resources,
+    pagination: Pagination,
+) -> VertexIterator<'static, Vertex> {
+    let epics: Vec<serde_json::Value> = match paged(endpoint, pagination).query(client) {
+        Ok(epics) => epics,
+        Err(e) => {
+            eprintln!("Failed to get epics: {:?}", e);
+            return Box::new(std::iter::empty());
+        }
+    };
+
+    let vertices: Vec<Vertex> = epics
+        .into_iter()
+        .filter_map(api_object::<Epic>)
+        .map(|epic| Vertex::Epic(epic.into()))
+        .collect();
+    Box::new(vertices.into_iter())
+}
+
+pub(super) fn resolve_epic_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    let client = adapter.client.clone();
+    let pagination = adapter.pagination(parameters);
+    match edge_name {
+        "child_epics" => Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let epic = vertex.as_epic().unwrap();
+            let endpoint =
+                Resources::new(format!("groups/{}/epics/{}/epics", epic.group_id, epic.iid));
+            epics(&client, endpoint, pagination)
+        }),
+        "parent_epic" => Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let epic = vertex.as_epic().unwrap();
+            // The parent may be in an ancestor group, which only its API URL tells.
+            let path = raw_field(epic.raw_json.as_deref(), "_links")
+                .and_then(|links| links["parent"].as_str().map(str::to_string))
+                .and_then(|url| url.split_once("/api/v4/").map(|(_, path)| path.to_string()));
+            let path = match path {
+                Some(path) => path,
+                None => return Box::new(std::iter::empty()),
+            };
+
+            let parent: serde_json::Value = match Resources::new(path).query(&*client) {
+                Ok(parent) => parent,
+                Err(e) => {
+                    eprintln!("Failed to get parent epic of {}: {:?}", epic.id, e);
+                    return Box::new(std::iter::empty());
+                }
+            };
+            Box::new(
+                api_object::<Epic>(parent)
+                    .map(|parent| Vertex::Epic(parent.into()))
+                    .into_iter(),
+            )
+        }),
+        "issues" => Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let epic = vertex.as_epic().unwrap();
+            let endpoint = Resources::new(format!(
+                "groups/{}/epics/{}/issues",
+                epic.group_id, epic.iid
+            ));
+            let issues: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*client) {
+                Ok(issues) => issues,
+                Err(e) => {
+                    eprintln!("Failed to get issues of epic {}: {:?}", epic.id, e);
+                    return Box::new(std::iter::empty());
+                }
+            };
+            let vertices: Vec<Vertex> = issues
+                .into_iter()
+                .filter_map(api_object::<Issue>)
+                .map(|issue| Vertex::Issue(issue.into()))
+                .collect();
+            Box::new(vertices.into_iter())
+        }),
+        _ => unreachable!("unknown Epic edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_group_member_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "user" => member_user_edge(|vertex| vertex.as_group_member().unwrap().raw_json.as_deref()),
+        _ => unreachable!("unknown GroupMember edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_billable_member_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "user" => {
+            member_user_edge(|vertex| vertex.as_billable_member().unwrap().raw_json.as_deref())
+        }
+        _ => unreachable!("unknown BillableMember edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_access_request_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "requester" => {
+            member_user_edge(|vertex| vertex.as_access_request().unwrap().raw_json.as_deref())
+        }
+        _ => unreachable!("unknown AccessRequest edge: {edge_name}"),
+    }
+}
+
+/// The pending access requests listed at `path`, if the vertex has any.
+fn access_requests_edge(
+    adapter: &GitlabAdapter,
+    parameters: &EdgeParameters,
+    path: fn(&Vertex) -> Option<String>,
+) -> NeighborResolver {
+    let client = adapter.client.clone();
+    let pagination = adapter.pagination(parameters);
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        let path = match path(vertex) {
+            Some(path) => path,
+            None => return Box::new(std::iter::empty()),
+        };
+        let requests: Vec<serde_json::Value> =
+            match paged(Resources::new(path), pagination).query(&*client) {
+                Ok(requests) => requests,
+                Err(e) => {
+                    eprintln!("Failed to get access requests: {:?}", e);
+                    return Box::new(std::iter::empty());
+                }
+            };
+        let vertices: Vec<Vertex> = requests
+            .into_iter()
+            .filter_map(api_object::<AccessRequest>)
+            .map(|request| Vertex::AccessRequest(request.into()))
+            .collect();
+        Box::new(vertices.into_iter())
+    })
+}
+
+/// The user a member object describes, which it extends with the membership's fields.
+fn member_user_edge(raw_json: fn(&Vertex) -> Option<&str>) -> NeighborResolver {
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        Box::new(
+            raw_json(vertex)
+                .and_then(|raw| serde_json::from_str(raw).ok())
+                .and_then(user_vertex)
+                .into_iter(),
+        )
+    })
+}
+
+pub(super) fn resolve_board_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "lists" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let board = vertex.as_board().unwrap();
+            let lists: Vec<Vertex> = raw_list(board.raw_json.as_deref(), "lists")
+                .into_iter()
+                .filter_map(api_object::<BoardList>)
+                .map(|list| Vertex::BoardList(list.into()))
+                .collect();
+            Box::new(lists.into_iter())
+        }),
+        "labels" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let board = vertex.as_board().unwrap();
+            let labels: Vec<Vertex> = raw_list(board.raw_json.as_deref(), "labels")
+                .into_iter()
+                .filter_map(api_object::<Label>)
+                .map(|label| Vertex::Label(label.into()))
+                .collect();
+            Box::new(labels.into_iter())
+        }),
+        "milestone" => milestone_edge(|vertex| vertex.as_board().unwrap().raw_json.as_deref()),
+        "assignee" => user_edge("assignee", |vertex| {
+            vertex.as_board().unwrap().raw_json.as_deref()
+        }),
+        _ => unreachable!("unknown Board edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_board_list_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "label" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let list = vertex.as_board_list().unwrap();
+            Box::new(
+                raw_field(list.raw_json.as_deref(), "label")
+                    .and_then(api_object::<Label>)
+                    .map(|label| Vertex::Label(label.into()))
+                    .into_iter(),
+            )
+        }),
+        "milestone" => milestone_edge(|vertex| vertex.as_board_list().unwrap().raw_json.as_deref()),
+        "assignee" => user_edge("assignee", |vertex| {
+            vertex.as_board_list().unwrap().raw_json.as_deref()
+        }),
+        _ => unreachable!("unknown BoardList edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_current_user_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    let client = adapter.client.clone();
+    let pagination = adapter.pagination(parameters);
+    match edge_name {
+        "starred_projects" | "memberships" => {
+            let starred = edge_name == "starred_projects";
+            Box::new(move |_: &Vertex| -> VertexIterator<'static, Vertex> {
+                let mut pb = ProjectsBuilder::default();
+                if starred {
+                    pb.starred(true);
+                } else {
+                    pb.membership(true);
+                }
+                let endpoint = pb.build().unwrap();
+
+                let pjs: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*client)
+                {
+                    Ok(pjs) => pjs,
+                    Err(e) => {
+                        eprintln!("Failed to get projects: {:?}", e);
+                        return Box::new(std::iter::empty());
+                    }
+                };
+                let vertices: Vec<Vertex> = pjs.into_iter().filter_map(repo_vertex).collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        "todos" => {
+            let state = extract_string_param!(parameters, "state");
+            let action = extract_string_param!(parameters, "action");
+            Box::new(move |_: &Vertex| -> VertexIterator<'static, Vertex> {
+                todos(&client, state.as_deref(), action.as_deref(), pagination)
+            })
+        }
+        "events" | "authored_merge_requests" | "authored_issues" | "contribution_stats" => {
+            resolve_gitlab_user_edge(adapter, edge_name, parameters)
+        }
+        #[cfg(feature = "security")]
+        "personal_access_tokens" => resolve_gitlab_user_edge(adapter, edge_name, parameters),
+        _ => unreachable!("unknown CurrentUser edge: {edge_name}"),
+    }
+}
+
+/// Also serves `CurrentUser`'s edges that any user has.
+pub(super) fn resolve_gitlab_user_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        "events" => events_edge(adapter, parameters, |vertex| {
+            format!("users/{}/events", vertex.as_gitlab_user().unwrap().id)
+        }),
+        "authored_merge_requests" | "authored_issues" => {
+            let issues = edge_name == "authored_issues";
+            let state = extract_string_param!(parameters, "state");
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let user = vertex.as_gitlab_user().unwrap();
+                let path = if issues { "issues" } else { "merge_requests" };
+                let mut endpoint = Resources::new(path)
+                    .param("scope", "all")
+                    .param("author_id", user.id);
+                if let Some(state) = &state {
+                    endpoint = endpoint.param("state", state);
+                }
+
+                let authored: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(authored) => authored,
+                        Err(e) => {
+                            eprintln!("Failed to get {path} of {}: {:?}", user.username, e);
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+                let vertices: Vec<Vertex> = if issues {
+                    authored
+                        .into_iter()
+                        .filter_map(api_object::<Issue>)
+                        .map(|issue| Vertex::Issue(issue.into()))
+                        .collect()
+                } else {
+                    authored
+                        .into_iter()
+                        .filter_map(api_object::<MergeRequest>)
+                        .map(|merge_request| Vertex::MergeRequest(merge_request.into()))
+                        .collect()
+                };
+                Box::new(vertices.into_iter())
+            })
+        }
+        "contribution_stats" => {
+            let since = extract_string_param!(parameters, "since").unwrap();
+
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let user = vertex.as_gitlab_user().unwrap();
+                // All of them, or the counts would be off.
+                let events = match events(
+                    &client,
+                    format!("users/{}/events", user.id),
+                    None,
+                    Some(&since),
+                    Pagination::All,
+                ) {
+                    Some(events) => events,
+                    None => return Box::new(std::iter::empty()),
+                };
+                let stats = contribution_stats(since.clone(), &events);
+                Box::new(std::iter::once(Vertex::ContributionStats(stats.into())))
+            })
+        }
+        #[cfg(feature = "security")]
+        "personal_access_tokens" => access_tokens_edge(adapter, parameters, |vertex| {
+            Resources::new("personal_access_tokens")
+                .param("user_id", vertex.as_gitlab_user().unwrap().id)
+        }),
+        _ => unreachable!("unknown GitlabUser edge: {edge_name}"),
+    }
+}
+
+/// The access tokens `endpoint` lists, optionally only the `active` or `inactive` ones.
+#[cfg(feature = "security")]
+fn access_tokens_edge(
+    adapter: &GitlabAdapter,
+    parameters: &EdgeParameters,
+    endpoint: fn(&Vertex) -> Resources,
+) -> NeighborResolver {
+    let state = extract_string_param!(parameters, "state");
+
+    let client = adapter.client.clone();
+    let pagination = adapter.pagination(parameters);
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        let mut endpoint = endpoint(vertex);
+        if let Some(state) = &state {
+            endpoint = endpoint.param("state", state);
+        }
+
+        let tokens: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*client) {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                eprintln!("Failed to get access tokens: {:?}", e);
+                return Box::new(std::iter::empty());
+            }
+        };
+        let vertices: Vec<Vertex> = tokens
+            .into_iter()
+            .filter_map(api_object::<AccessToken>)
+            .map(|token| Vertex::AccessToken(token.into()))
+            .collect();
+        Box::new(vertices.into_iter())
+    })
+}
+
+/// The activity feed at `path`, newest first, optionally of one `action` and only after
+/// a date.
+fn events_edge(
+    adapter: &GitlabAdapter,
+    parameters: &EdgeParameters,
+    path: fn(&Vertex) -> String,
+) -> NeighborResolver {
+    let action = extract_string_param!(parameters, "action");
+    let after = extract_string_param!(parameters, "after");
+
+    let client = adapter.client.clone();
+    let pagination = adapter.pagination(parameters);
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        let events = events(
+            &client,
+            path(vertex),
+            action.as_deref(),
+            after.as_deref(),
+            pagination,
+        );
+        let vertices: Vec<Vertex> = events
+            .into_iter()
+            .flatten()
+            .map(|event| Vertex::Event(event.into()))
+            .collect();
+        Box::new(vertices.into_iter())
+    })
+}
+
+/// The events at `path`, or `None` if they couldn't be listed.
+fn events(
+    client: &GitlabClient,
+    path: String,
+    action: Option<&str>,
+    after: Option<&str>,
+    pagination: Pagination,
+) -> Option<Vec<Event>> {
+    let mut endpoint = Resources::new(path);
+    if let Some(action) = action {
+        endpoint = endpoint.param("action", action);
+    }
+    if let Some(after) = after {
+        endpoint = endpoint.param("after", after);
+    }
+
+    let events: Vec<serde_json::Value> = match paged(endpoint, pagination).query(client) {
+        Ok(events) => events,
+        Err(e) => {
+            eprintln!("Failed to get events: {:?}", e);
+            return None;
+        }
+    };
+    Some(events.into_iter().filter_map(api_object::<Event>).collect())
+}
+
+/// Counts a user's pushes, merge requests and reviews among their events.
+fn contribution_stats(since: String, events: &[Event]) -> ContributionStats {
+    let mut stats = ContributionStats {
+        since,
+        push_count: 0,
+        commit_count: 0,
+        merge_requests_opened: 0,
+        merge_requests_merged: 0,
+        review_count: 0,
+        comment_count: 0,
+    };
+    let mut reviewed = HashSet::new();
+    for event in events {
+        if let Some(push) = &event.push_data {
+            stats.push_count += 1;
+            stats.commit_count += push.commit_count;
+            continue;
+        }
+        let target_type = event.target_type.as_deref();
+        match (event.action_name.as_str(), target_type) {
+            ("opened", Some("MergeRequest")) => stats.merge_requests_opened += 1,
+            ("accepted", Some("MergeRequest")) => stats.merge_requests_merged += 1,
+            ("approved", Some("MergeRequest")) => {
+                reviewed.extend(event.target_id);
+            }
+            ("commented on", _) => {
+                stats.comment_count += 1;
+                let note = raw_field(event.raw_json.as_deref(), "note");
+                if let Some(note) = note.filter(|note| note["noteable_type"] == "MergeRequest") {
+                    reviewed.extend(note["noteable_id"].as_u64());
+                }
+            }
+            _ => {}
+        }
+    }
+    stats.review_count = reviewed.len() as u64;
+    stats
+}
+
+pub(super) fn resolve_event_edge(adapter: &GitlabAdapter, edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "author" => user_edge("author", |vertex| {
+            vertex.as_event().unwrap().raw_json.as_deref()
+        }),
+        "project" => {
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                match vertex.as_event().unwrap().project_id {
+                    Some(project_id) => get_project(&client, project_id.into()),
+                    None => Box::new(std::iter::empty()),
+                }
+            })
+        }
+        _ => unreachable!("unknown Event edge: {edge_name}"),
+    }
+}
+
+/// The authenticated user's todos, pending ones unless `state` is `done`.
+pub(super) fn todos(
+    client: &GitlabClient,
+    state: Option<&str>,
+    action: Option<&str>,
+    pagination: Pagination,
+) -> VertexIterator<'static, Vertex> {
+    let mut endpoint = Resources::new("todos");
+    if let Some(state) = state {
+        endpoint = endpoint.param("state", state);
+    }
+    if let Some(action) = action {
+        endpoint = endpoint.param("action", action);
+    }
+
+    let todos: Vec<serde_json::Value> = match paged(endpoint, pagination).query(client) {
+        Ok(todos) => todos,
+        Err(e) => {
+            eprintln!("Failed to get todos: {:?}", e);
+            return Box::new(std::iter::empty());
+        }
+    };
+    let vertices: Vec<Vertex> = todos.into_iter().map(todo_vertex).collect();
+    Box::new(vertices.into_iter())
+}
+
+pub(super) fn resolve_todo_edge(adapter: &GitlabAdapter, edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "target" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let todo = vertex.as_todo().unwrap();
+            let target = match raw_field(todo.raw_json.as_deref(), "target") {
+                Some(target) => target,
+                None => return Box::new(std::iter::empty()),
+            };
+            // Todos for epics, commits, designs, etc. have no vertex to lead to.
+            let target = match todo.target_type.as_str() {
+                "Issue" => api_object::<Issue>(target).map(|issue| Vertex::Issue(issue.into())),
+                "MergeRequest" => api_object::<MergeRequest>(target)
+                    .map(|merge_request| Vertex::MergeRequest(merge_request.into())),
+                _ => None,
+            };
+            Box::new(target.into_iter())
+        }),
+        "project" => {
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                match vertex.as_todo().unwrap().project_id {
+                    Some(project_id) => get_project(&client, project_id.into()),
+                    None => Box::new(std::iter::empty()),
+                }
+            })
+        }
+        _ => unreachable!("unknown Todo edge: {edge_name}"),
+    }
+}
+
+fn todo_vertex(raw: serde_json::Value) -> Vertex {
+    let field = |name: &str| raw[name].as_str().unwrap_or_default().to_string();
+    Vertex::Todo(
+        Todo {
+            id: raw["id"].as_u64().unwrap_or_default(),
+            action_name: field("action_name"),
+            target_type: field("target_type"),
+            target_title: raw["target"]["title"].as_str().map(str::to_string),
+            target_url: field("target_url"),
+            body: field("body"),
+            state: field("state"),
+            created_at: field("created_at"),
+            project_full_path: raw["project"]["path_with_namespace"]
+                .as_str()
+                .map(str::to_string),
+            project_id: raw["project"]["id"].as_u64(),
+            raw_json: Some(raw.to_string()),
+        }
+        .into(),
+    )
+}
+
+/// A field of a vertex's API object, if it's an object itself.
+fn raw_field(raw_json: Option<&str>, name: &str) -> Option<serde_json::Value> {
+    raw_json
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .map(|raw| raw[name].clone())
+        .filter(|field| field.is_object())
+}
+
+/// The list `name` in an API object, for nested objects read into vertices of their own.
+fn raw_list(raw_json: Option<&str>, name: &str) -> Vec<serde_json::Value> {
+    raw_json
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|mut raw| match raw[name].take() {
+            serde_json::Value::Array(items) => Some(items),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+pub(super) fn user_vertex(raw: serde_json::Value) -> Option<Vertex> {
+    api_object::<GitlabUser>(raw).map(|user| Vertex::GitlabUser(user.into()))
+}
+
+/// Reads both the namespaces embedded in projects and groups, which have no `kind`.
+fn namespace_vertex(raw: &serde_json::Value) -> Vertex {
+    let field = |name: &str| raw[name].as_str().unwrap_or_default().to_string();
+    Vertex::Namespace(
+        Namespace {
+            id: raw["id"].to_string(),
+            name: field("name"),
+            path: field("path"),
+            full_path: field("full_path"),
+            kind: raw["kind"].as_str().unwrap_or("group").to_string(),
+            web_url: field("web_url"),
+            raw_json: Some(raw.to_string()),
+        }
+        .into(),
+    )
+}
+
+pub(super) fn resolve_issue_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        "author" => user_edge("author", |vertex| {
+            vertex.as_issue().unwrap().raw_json.as_deref()
+        }),
+        "label_details" => labels_edge(adapter, |vertex| {
+            let issue = vertex.as_issue().unwrap();
+            (issue.project_id, &issue.labels)
+        }),
+        "milestone" => milestone_edge(|vertex| vertex.as_issue().unwrap().raw_json.as_deref()),
+        "iteration" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let issue = vertex.as_issue().unwrap();
+            Box::new(
+                raw_field(issue.raw_json.as_deref(), "iteration")
+                    .and_then(api_object::<Iteration>)
+                    .map(|iteration| Vertex::Iteration(iteration.into()))
+                    .into_iter(),
+            )
+        }),
+        "participants" => participants_edge(adapter, parameters, |vertex| {
+            let issue = vertex.as_issue().unwrap();
+            format!(
+                "projects/{}/issues/{}/participants",
+                issue.project_id, issue.iid
+            )
+        }),
+        "notes" => notes_edge(adapter, parameters, |vertex| {
+            let issue = vertex.as_issue().unwrap();
+            format!("projects/{}/issues/{}/notes", issue.project_id, issue.iid)
+        }),
+        "award_emoji" => award_emoji_edge(adapter, parameters, |vertex| {
+            let issue = vertex.as_issue().unwrap();
+            format!(
+                "projects/{}/issues/{}/award_emoji",
+                issue.project_id, issue.iid
+            )
+        }),
+        "label_events" | "state_events" => {
+            resource_events_edge(adapter, edge_name, parameters, |vertex| {
+                let issue = vertex.as_issue().unwrap();
+                format!("projects/{}/issues/{}", issue.project_id, issue.iid)
+            })
+        }
+        "timelogs" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let issue = vertex.as_issue().unwrap();
+                let variables =
+                    serde_json::json!({ "id": format!("gid://gitlab/Issue/{}", issue.id) });
+                timelogs(
+                    &client,
+                    ISSUE_TIMELOGS_QUERY,
+                    variables,
+                    "/issue/timelogs",
+                    pagination,
+                )
+            })
+        }
+        "email_participants" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let issue = vertex.as_issue().unwrap();
+                let variables =
+                    serde_json::json!({ "id": format!("gid://gitlab/Issue/{}", issue.id) });
+                let participants: Vec<Vertex> = graphql_nodes(
+                    &client,
+                    ISSUE_EMAIL_PARTICIPANTS_QUERY,
+                    variables,
+                    "/issue/emailParticipants",
+                    pagination,
+                )
+                .into_iter()
+                .filter_map(api_object::<EmailParticipant>)
+                .map(|participant| Vertex::EmailParticipant(participant.into()))
+                .collect();
+                Box::new(participants.into_iter())
+            })
+        }
+        "project" => project_edge(adapter, |vertex| vertex.as_issue().unwrap().project_id),
+        _ => unreachable!("unknown Issue edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_merge_request_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        #[cfg(feature = "operations")]
+        "deployments" | "review_apps" => {
+            let review_apps = edge_name == "review_apps";
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let merge_request = vertex.as_merge_request().unwrap();
+                // Deployments can't be filtered by ref, so the latest ones are searched.
+                let endpoint =
+                    Resources::new(format!("projects/{}/deployments", merge_request.project_id))
+                        .param("order_by", "updated_at")
+                        .param("sort", "desc");
+                let merge_ref = format!("refs/merge-requests/{}/head", merge_request.iid);
+                let deployments: Vec<Deployment> =
+                    deployments(&client, merge_request.project_id, endpoint, pagination)
+                        .into_iter()
+                        .filter(|deployment| {
+                            deployment.ref_ == merge_request.source_branch
+                                || deployment.ref_ == merge_ref
+                        })
+                        .collect();
+                if !review_apps {
+                    let vertices: Vec<Vertex> = deployments
+                        .into_iter()
+                        .map(|deployment| Vertex::Deployment(deployment.into()))
+                        .collect();
+                    return Box::new(vertices.into_iter());
+                }
+
+                let mut environment_ids: Vec<u64> = deployments
+                    .iter()
+                    .map(|deployment| deployment.environment.id)
+                    .collect();
+                environment_ids.sort_unstable();
+                environment_ids.dedup();
+                let environments: Vec<Vertex> = environment_ids
+                    .into_iter()
+                    .filter_map(|id| get_environment(&client, merge_request.project_id, id))
+                    .map(|environment| Vertex::Environment(environment.into()))
+                    .collect();
+                Box::new(environments.into_iter())
+            })
+        }
+        "author" => user_edge("author", |vertex| {
+            vertex.as_merge_request().unwrap().raw_json.as_deref()
+        }),
+        "assignees" => users_edge("assignees", |vertex| {
+            vertex.as_merge_request().unwrap().raw_json.as_deref()
+        }),
+        "reviewers" => users_edge("reviewers", |vertex| {
+            vertex.as_merge_request().unwrap().raw_json.as_deref()
+        }),
+        "merged_by" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            // `merge_user` is also who set an open merge request to merge when its
+            // pipeline succeeds, so it only stands in for `merged_by` once merged.
+            let merge_request = vertex.as_merge_request().unwrap();
+            let raw_json = merge_request.raw_json.as_deref();
+            let merged_by = raw_field(raw_json, "merged_by").or_else(|| {
+                (merge_request.state == "merged")
+                    .then(|| raw_field(raw_json, "merge_user"))
+                    .flatten()
+            });
+            Box::new(merged_by.and_then(user_vertex).into_iter())
+        }),
+        "label_details" => labels_edge(adapter, |vertex| {
+            let merge_request = vertex.as_merge_request().unwrap();
+            (merge_request.project_id, &merge_request.labels)
+        }),
+        "milestone" => {
+            milestone_edge(|vertex| vertex.as_merge_request().unwrap().raw_json.as_deref())
+        }
+        "participants" => participants_edge(adapter, parameters, |vertex| {
+            let merge_request = vertex.as_merge_request().unwrap();
+            format!(
+                "projects/{}/merge_requests/{}/participants",
+                merge_request.project_id, merge_request.iid
+            )
+        }),
+        "notes" => notes_edge(adapter, parameters, |vertex| {
+            let merge_request = vertex.as_merge_request().unwrap();
+            format!(
+                "projects/{}/merge_requests/{}/notes",
+                merge_request.project_id, merge_request.iid
+            )
+        }),
+        "award_emoji" => award_emoji_edge(adapter, parameters, |vertex| {
+            let merge_request = vertex.as_merge_request().unwrap();
+            format!(
+                "projects/{}/merge_requests/{}/award_emoji",
+                merge_request.project_id, merge_request.iid
+            )
+        }),
+        "label_events" | "state_events" => {
+            resource_events_edge(adapter, edge_name, parameters, |vertex| {
+                let merge_request = vertex.as_merge_request().unwrap();
+                format!(
+                    "projects/{}/merge_requests/{}",
+                    merge_request.project_id, merge_request.iid
+                )
+            })
+        }
+        "timelogs" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let merge_request = vertex.as_merge_request().unwrap();
+                let variables = serde_json::json!({
+                    "id": format!("gid://gitlab/MergeRequest/{}", merge_request.id)
+                });
+                timelogs(
+                    &client,
+                    MERGE_REQUEST_TIMELOGS_QUERY,
+                    variables,
+                    "/mergeRequest/timelogs",
+                    pagination,
+                )
+            })
+        }
+        "project" => project_edge(adapter, |vertex| {
+            vertex.as_merge_request().unwrap().project_id
+        }),
+        _ => unreachable!("unknown MergeRequest edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_search_blob_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+) -> NeighborResolver {
+    match edge_name {
+        "project" => project_edge(adapter, |vertex| {
+            vertex.as_search_blob().unwrap().project_id
+        }),
+        _ => unreachable!("unknown SearchBlob edge: {edge_name}"),
+    }
+}
+
+/// The user embedded as `field`, e.g. `author`, in the vertex's API object.
+fn user_edge(field: &'static str, raw_json: fn(&Vertex) -> Option<&str>) -> NeighborResolver {
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        Box::new(
+            raw_field(raw_json(vertex), field)
+                .and_then(user_vertex)
+                .into_iter(),
+        )
+    })
+}
+
+/// The users listed as `field`, e.g. `assignees`, in the vertex's API object.
+fn users_edge(field: &'static str, raw_json: fn(&Vertex) -> Option<&str>) -> NeighborResolver {
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        let users: Vec<Vertex> = raw_list(raw_json(vertex), field)
+            .into_iter()
+            .filter_map(user_vertex)
+            .collect();
+        Box::new(users.into_iter())
+    })
+}
+
+/// The labels the vertex lists by name in its project, fetched one by one; those
+/// inherited from the project's groups included.
+fn labels_edge(
+    adapter: &GitlabAdapter,
+    labels: fn(&Vertex) -> (u64, &Vec<String>),
+) -> NeighborResolver {
+    let client = adapter.client.clone();
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        let (project_id, names) = labels(vertex);
+        let mut vertices = Vec::new();
+        for name in names {
+            let endpoint = Resources::new(format!(
+                "projects/{project_id}/labels/{}",
+                path_escaped(name)
+            ));
+            let label: serde_json::Value = match endpoint.query(&*client) {
+                Ok(label) => label,
+                Err(e) => {
+                    eprintln!("Failed to get label {name} of {project_id}: {:?}", e);
+                    continue;
+                }
+            };
+            if let Some(label) = api_object::<Label>(label) {
+                vertices.push(Vertex::Label(label.into()));
+            }
+        }
+        Box::new(vertices.into_iter())
+    })
+}
+
+/// The milestone embedded in the vertex's API object.
+fn milestone_edge(raw_json: fn(&Vertex) -> Option<&str>) -> NeighborResolver {
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        Box::new(
+            raw_field(raw_json(vertex), "milestone")
+                .and_then(api_object::<Milestone>)
+                .map(|milestone| Vertex::Milestone(milestone.into()))
+                .into_iter(),
+        )
+    })
+}
+
+/// Everyone who authored, was assigned, commented on or was mentioned in an issue or
+/// merge request, listed by the endpoint at `path`.
+fn participants_edge(
+    adapter: &GitlabAdapter,
+    parameters: &EdgeParameters,
+    path: fn(&Vertex) -> String,
+) -> NeighborResolver {
+    let client = adapter.client.clone();
+    let pagination = adapter.pagination(parameters);
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        let endpoint = Resources::new(path(vertex));
+        let participants: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*client)
+        {
+            Ok(participants) => participants,
+            Err(e) => {
+                eprintln!("Failed to get participants: {:?}", e);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let users: Vec<Vertex> = participants.into_iter().filter_map(user_vertex).collect();
+        Box::new(users.into_iter())
+    })
+}
+
+/// The comments and system notes listed by the endpoint at `path`, the latest first.
+fn notes_edge(
+    adapter: &GitlabAdapter,
+    parameters: &EdgeParameters,
+    path: fn(&Vertex) -> String,
+) -> NeighborResolver {
+    let client = adapter.client.clone();
+    let pagination = adapter.pagination(parameters);
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        let endpoint = Resources::new(path(vertex));
+        let notes: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*client) {
+            Ok(notes) => notes,
+            Err(e) => {
+                eprintln!("Failed to get notes: {:?}", e);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let vertices: Vec<Vertex> = notes
+            .into_iter()
+            .filter_map(api_object::<Note>)
+            .map(|note| Vertex::Note(note.into()))
+            .collect();
+        Box::new(vertices.into_iter())
+    })
+}
+
+/// The award emoji listed by the endpoint at `path`.
+fn award_emoji_edge(
+    adapter: &GitlabAdapter,
+    parameters: &EdgeParameters,
+    path: fn(&Vertex) -> String,
+) -> NeighborResolver {
+    let client = adapter.client.clone();
+    let pagination = adapter.pagination(parameters);
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        let endpoint = Resources::new(path(vertex));
+        let award_emoji: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*client)
+        {
+            Ok(award_emoji) => award_emoji,
+            Err(e) => {
+                eprintln!("Failed to get award emoji: {:?}", e);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let vertices: Vec<Vertex> = award_emoji
+            .into_iter()
+            .filter_map(api_object::<AwardEmoji>)
+            .map(|award_emoji| Vertex::AwardEmoji(award_emoji.into()))
+            .collect();
+        Box::new(vertices.into_iter())
+    })
+}
+
+pub(super) fn resolve_note_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        "author" => user_edge("author", |vertex| {
+            vertex.as_note().unwrap().raw_json.as_deref()
+        }),
+        "award_emoji" => award_emoji_edge(adapter, parameters, |vertex| {
+            let note = vertex.as_note().unwrap();
+            let noteables = match note.noteable_type.as_str() {
+                "MergeRequest" => "merge_requests",
+                "Snippet" => "snippets",
+                _ => "issues",
+            };
+            format!(
+                "projects/{}/{noteables}/{}/notes/{}/award_emoji",
+                note.project_id.unwrap_or_default(),
+                note.noteable_iid.unwrap_or_default(),
+                note.id
+            )
+        }),
+        _ => unreachable!("unknown Note edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_award_emoji_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "user" => user_edge("user", |vertex| {
+            vertex.as_award_emoji().unwrap().raw_json.as_deref()
+        }),
+        _ => unreachable!("unknown AwardEmoji edge: {edge_name}"),
+    }
+}
+
+fn timelogs(
+    client: &GitlabClient,
+    query: &'static str,
+    variables: serde_json::Value,
+    connection: &str,
+    pagination: Pagination,
+) -> VertexIterator<'static, Vertex> {
+    let timelogs: Vec<Vertex> = graphql_nodes(client, query, variables, connection, pagination)
+        .into_iter()
+        .filter_map(api_object::<Timelog>)
+        .map(|timelog| Vertex::Timelog(timelog.into()))
+        .collect();
+    Box::new(timelogs.into_iter())
+}
+
+/// A user as the GraphQL API returns it, read like the REST API's users.
+fn graphql_user_vertex(raw: serde_json::Value) -> Option<Vertex> {
+    // e.g. `gid://gitlab/User/42`
+    let id = raw["id"]
+        .as_str()?
+        .rsplit('/')
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+    user_vertex(serde_json::json!({
+        "id": id,
+        "username": raw["username"],
+        "name": raw["name"],
+        "state": raw["state"],
+        "web_url": raw["webUrl"],
+    }))
+}
+
+pub(super) fn resolve_milestone_edge(adapter: &GitlabAdapter, edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "stats" => {
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let milestone = vertex.as_milestone().unwrap();
+                let path = match (milestone.project_id, milestone.group_id) {
+                    (Some(project_id), _) => {
+                        format!("projects/{project_id}/milestones/{}/issues", milestone.id)
+                    }
+                    (None, Some(group_id)) => {
+                        format!("groups/{group_id}/milestones/{}/issues", milestone.id)
+                    }
+                    (None, None) => return Box::new(std::iter::empty()),
+                };
+
+                // All of them, or the counts would be off.
+                let issues: Vec<serde_json::Value> =
+                    match paged(Resources::new(path), Pagination::All).query(&*client) {
+                        Ok(issues) => issues,
+                        Err(e) => {
+                            eprintln!(
+                                "Failed to get issues of milestone {}: {:?}",
+                                milestone.id, e
+                            );
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+                let mut stats = MilestoneStats {
+                    open_issues: 0,
+                    closed_issues: 0,
+                    total_weight: 0,
+                    closed_weight: 0,
+                };
+                for issue in issues.into_iter().filter_map(api_object::<Issue>) {
+                    let weight = issue.weight.unwrap_or(0);
+                    stats.total_weight += weight;
+                    if issue.state == "closed" {
+                        stats.closed_issues += 1;
+                        stats.closed_weight += weight;
+                    } else {
+                        stats.open_issues += 1;
+                    }
+                }
+                Box::new(std::iter::once(Vertex::MilestoneStats(stats.into())))
+            })
+        }
+        _ => unreachable!("unknown Milestone edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_iteration_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        "issues" => {
+            let state = extract_string_param!(parameters, "state");
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let iteration = vertex.as_iteration().unwrap();
+                let mut endpoint = Resources::new("issues")
+                    .param("scope", "all")
+                    .param("iteration_id", iteration.id);
+                if let Some(state) = &state {
+                    endpoint = endpoint.param("state", state);
+                }
+
+                let issues: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(issues) => issues,
+                        Err(e) => {
+                            eprintln!(
+                                "Failed to get issues of iteration {}: {:?}",
+                                iteration.id, e
+                            );
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+                let vertices: Vec<Vertex> = issues
+                    .into_iter()
+                    .filter_map(api_object::<Issue>)
+                    .map(|issue| Vertex::Issue(issue.into()))
+                    .collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        _ => unreachable!("unknown Iteration edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_timelog_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "user" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let timelog = vertex.as_timelog().unwrap();
+            Box::new(
+                raw_field(timelog.raw_json.as_deref(), "user")
+                    .and_then(graphql_user_vertex)
+                    .into_iter(),
+            )
+        }),
+        _ => unreachable!("unknown Timelog edge: {edge_name}"),
+    }
+}
+
+/// The `label_events` or `state_events` of the issue or merge request at `path`, oldest
+/// first.
+fn resource_events_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+    path: fn(&Vertex) -> String,
+) -> NeighborResolver {
+    let labels = edge_name == "label_events";
+
+    let client = adapter.client.clone();
+    let pagination = adapter.pagination(parameters);
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        let resource = if labels {
+            "resource_label_events"
+        } else {
+            "resource_state_events"
+        };
+        let endpoint = Resources::new(format!("{}/{resource}", path(vertex)));
+        let events: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*client) {
+            Ok(events) => events,
+            Err(e) => {
+                eprintln!("Failed to get {resource}: {:?}", e);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let vertices: Vec<Vertex> = if labels {
+            events
+                .into_iter()
+                .filter_map(api_object::<LabelEvent>)
+                .map(|event| Vertex::LabelEvent(event.into()))
+                .collect()
+        } else {
+            events
+                .into_iter()
+                .filter_map(api_object::<StateEvent>)
+                .map(|event| Vertex::StateEvent(event.into()))
+                .collect()
+        };
+        Box::new(vertices.into_iter())
+    })
+}
+
+pub(super) fn resolve_label_event_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "user" => user_edge("user", |vertex| {
+            vertex.as_label_event().unwrap().raw_json.as_deref()
+        }),
+        "label" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let event = vertex.as_label_event().unwrap();
+            Box::new(
+                raw_field(event.raw_json.as_deref(), "label")
+                    .and_then(api_object::<Label>)
+                    .map(|label| Vertex::Label(label.into()))
+                    .into_iter(),
+            )
+        }),
+        _ => unreachable!("unknown LabelEvent edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_state_event_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "user" => user_edge("user", |vertex| {
+            vertex.as_state_event().unwrap().raw_json.as_deref()
+        }),
+        _ => unreachable!("unknown StateEvent edge: {edge_name}"),
+    }
+}
+
+pub(super) fn resolve_requirement_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "author" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let requirement = vertex.as_requirement().unwrap();
+            Box::new(
+                raw_field(requirement.raw_json.as_deref(), "author")
+                    .and_then(graphql_user_vertex)
+                    .into_iter(),
+            )
+        }),
+        _ => unreachable!("unknown Requirement edge: {edge_name}"),
+    }
+}
+
+/// The project with the id the vertex refers to, fetched one by one.
+fn project_edge(adapter: &GitlabAdapter, project_id: fn(&Vertex) -> u64) -> NeighborResolver {
+    let client = adapter.client.clone();
+    Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+        get_project(&client, project_id(vertex).into())
+    })
+}
+
+pub(super) fn resolve_directory_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    let pagination = tree_pagination(parameters);
+    let client = adapter.client.clone();
+    match edge_name {
+        "files" => {
+            let recursive = extract_bool_param!(parameters, "recursive");
+            let parsers = adapter.file_parsers.clone();
+            let redact_secrets = adapter.redact_secrets;
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let directory = vertex.as_directory().unwrap();
+                GitlabAdapter::get_files_for_repo(
+                    &client,
+                    &parsers,
+                    redact_secrets,
+                    directory.repo.clone(),
+                    Some(directory.ref_.clone()),
+                    Some(directory.path.clone()),
+                    recursive.unwrap_or(true),
+                    pagination,
+                )
+            })
+        }
+        "directories" => Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let directory = vertex.as_directory().unwrap();
+            get_directories(
+                &client,
+                &directory.repo,
+                &directory.ref_,
+                Some(&directory.path),
+                pagination,
+            )
+        }),
+        _ => unreachable!("unknown Directory edge: {edge_name}"),
+    }
+}
+
+/// Repository trees are listed 50 entries at a time unless `limit`ed.
+fn tree_pagination(parameters: &EdgeParameters) -> Pagination {
+    Pagination::Limit(limit_param(parameters).ok().flatten().unwrap_or(50))
+}
+
+/// The directories directly in `path`, or in the root directory.
+fn get_directories(
+    client: &GitlabClient,
+    repo: &Arc<GitlabRepo>,
+    ref_: &str,
+    path: Option<&str>,
+    pagination: Pagination,
+) -> VertexIterator<'static, Vertex> {
+    let mut tb = TreeBuilder::default();
+    tb.project(repo.id.as_str()).ref_(ref_);
+    if let Some(path) = path {
+        tb.path(path);
+    }
+    let endpoint = tb.build().unwrap();
+
+    let entries: Vec<RepoTreeObject> = match paged(endpoint, pagination).query(client) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Failed to get directories of {}: {:?}", repo.id, e);
+            return Box::new(std::iter::empty());
+        }
+    };
+
+    let vertices: Vec<Vertex> = entries
+        .into_iter()
+        .filter(|entry| matches!(entry.type_, ObjectType::Tree))
+        .map(|entry| {
+            Vertex::Directory(
+                Directory {
+                    repo: repo.clone(),
+                    ref_: ref_.to_string(),
+                    path: entry.path,
+                }
+                .into(),
+            )
+        })
+        .collect();
+    Box::new(vertices.into_iter())
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_pipeline_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        "project" => project_edge(adapter, |vertex| vertex.as_pipeline().unwrap().project_id),
+        "jobs" => {
+            let include_retried = extract_bool_param!(parameters, "include_retried");
+            let list_jobs = job_lister(
+                adapter,
+                adapter.pagination(parameters),
+                include_retried.unwrap_or(false),
+            );
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let pipeline = vertex.as_pipeline().unwrap();
+                let jobs = list_jobs(pipeline.project_id, pipeline.id);
+                Box::new(jobs.into_iter().map(|job| Vertex::PipelineJob(job.into())))
+            })
+        }
+        "downstream_pipelines" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let pipeline = vertex.as_pipeline().unwrap();
+                let endpoint = Resources::new(format!(
+                    "projects/{}/pipelines/{}/bridges",
+                    pipeline.project_id, pipeline.id
+                ));
+                let bridges: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(bridges) => bridges,
+                        Err(e) => {
+                            eprintln!("Failed to get bridges of pipeline {}: {:?}", pipeline.id, e);
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+
+                // Bridges that haven't run yet have no downstream pipeline.
+                let downstream: Vec<Vertex> = bridges
+                    .into_iter()
+                    .filter(|bridge| bridge["downstream_pipeline"].is_object())
+                    .filter_map(|bridge| {
+                        api_object::<Pipeline>(bridge["downstream_pipeline"].clone())
+                    })
+                    .map(|mut downstream| {
+                        downstream.upstream = Some(pipeline.clone());
+                        Vertex::Pipeline(lazy_details(downstream, &client).into())
+                    })
+                    .collect();
+                Box::new(downstream.into_iter())
+            })
+        }
+        "variables" => {
+            let client = adapter.client.clone();
+            let redact_secrets = adapter.redact_secrets;
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let pipeline = vertex.as_pipeline().unwrap();
+                let endpoint = Resources::new(format!(
+                    "projects/{}/pipelines/{}/variables",
+                    pipeline.project_id, pipeline.id
+                ));
+                let variables: Vec<serde_json::Value> = match endpoint.query(&*client) {
+                    Ok(variables) => variables,
+                    // Reading them takes the Developer role.
+                    Err(ApiError::Gitlab { msg }) if msg.starts_with("403") => Vec::new(),
+                    Err(e) => {
+                        eprintln!(
+                            "Failed to get variables of pipeline {}: {:?}",
+                            pipeline.id, e
+                        );
+                        return Box::new(std::iter::empty());
+                    }
+                };
+
+                Box::new(variables.into_iter().filter_map(move |mut variable| {
+                    // Before reading, so `raw_json` is redacted too.
+                    if redact_secrets {
+                        if let Some(value) = variable["value"].as_str() {
+                            if let Cow::Owned(redacted) = redact(value) {
+                                variable["value"] = redacted.into();
+                            }
+                        }
+                    }
+                    api_object::<PipelineVariable>(variable)
+                        .map(|variable| Vertex::PipelineVariable(variable.into()))
+                }))
+            })
+        }
+        "upstream_pipeline" => {
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let pipeline = vertex.as_pipeline().unwrap();
+                // Known without asking when reached through its upstream's bridges.
+                if let Some(upstream) = &pipeline.upstream {
+                    return Box::new(std::iter::once(Vertex::Pipeline(upstream.clone())));
+                }
+                Box::new(
+                    upstream_pipeline(&client, pipeline)
+                        .map(|upstream| Vertex::Pipeline(upstream.into()))
+                        .into_iter(),
+                )
+            })
+        }
+        "coverage" => {
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let pipeline = vertex.as_pipeline().unwrap();
+                // Only the single pipeline endpoint includes coverage, not the listings.
+                let endpoint = Resources::new(format!(
+                    "projects/{}/pipelines/{}",
+                    pipeline.project_id, pipeline.id
+                ));
+                let details: serde_json::Value = match endpoint.query(&*client) {
+                    Ok(details) => details,
+                    Err(e) => {
+                        eprintln!("Failed to get pipeline {}: {:?}", pipeline.id, e);
+                        return Box::new(std::iter::empty());
+                    }
+                };
+
+                let coverage = PipelineCoverage {
+                    project_id: pipeline.project_id,
+                    pipeline_id: pipeline.id,
+                    percentage: details["coverage"]
+                        .as_str()
+                        .and_then(|coverage| coverage.parse().ok()),
+                };
+                Box::new(std::iter::once(Vertex::PipelineCoverage(coverage.into())))
+            })
+        }
+        _ => unreachable!("unknown Pipeline edge: {edge_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_pipeline_coverage_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+) -> NeighborResolver {
+    match edge_name {
+        "jobs" => {
+            let list_jobs = job_lister(adapter, Pagination::All, false);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let coverage = vertex.as_pipeline_coverage().unwrap();
+                let jobs = list_jobs(coverage.project_id, coverage.pipeline_id);
+                Box::new(
+                    jobs.into_iter()
+                        .filter(|job| job.coverage.is_some())
+                        .map(|job| Vertex::PipelineJob(job.into())),
+                )
+            })
+        }
+        _ => unreachable!("unknown PipelineCoverage edge: {edge_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_pipeline_schedule_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "owner" => user_edge("owner", |vertex| {
+            vertex.as_pipeline_schedule().unwrap().raw_json.as_deref()
+        }),
+        _ => unreachable!("unknown PipelineSchedule edge: {edge_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_merge_train_car_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+) -> NeighborResolver {
+    let client = adapter.client.clone();
+    match edge_name {
+        // The train only embeds a few of their fields, so they're fetched in full.
+        "merge_request" => Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let car = vertex.as_merge_train_car().unwrap();
+            let endpoint = Resources::new(format!(
+                "projects/{}/merge_requests/{}",
+                car.merge_request.project_id, car.merge_request.iid
+            ));
+            let merge_request: Result<serde_json::Value, _> = endpoint.query(&*client);
+            match merge_request {
+                Ok(merge_request) => Box::new(
+                    api_object::<MergeRequest>(merge_request)
+                        .map(|merge_request| Vertex::MergeRequest(merge_request.into()))
+                        .into_iter(),
+                ),
+                Err(e) => {
+                    eprintln!("Failed to get the merge request of car {}: {:?}", car.id, e);
+                    Box::new(std::iter::empty())
+                }
+            }
+        }),
+        "pipeline" => Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let car = vertex.as_merge_train_car().unwrap();
+            let pipeline = match &car.pipeline {
+                Some(pipeline) => pipeline,
+                None => return Box::new(std::iter::empty()),
+            };
+            let endpoint = Resources::new(format!(
+                "projects/{}/pipelines/{}",
+                pipeline.project_id, pipeline.id
+            ));
+            let pipeline: Result<serde_json::Value, _> = endpoint.query(&*client);
+            match pipeline {
+                Ok(pipeline) => Box::new(
+                    api_object::<Pipeline>(pipeline)
+                        .map(|pipeline| Vertex::Pipeline(pipeline.into()))
+                        .into_iter(),
+                ),
+                Err(e) => {
+                    eprintln!("Failed to get the pipeline of car {}: {:?}", car.id, e);
+                    Box::new(std::iter::empty())
+                }
+            }
+        }),
+        _ => unreachable!("unknown MergeTrainCar edge: {edge_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_environment_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        "deployments" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let environment = vertex.as_environment().unwrap();
+                let endpoint =
+                    Resources::new(format!("projects/{}/deployments", environment.project_id))
+                        .param("environment", &environment.name)
+                        .param("order_by", "id")
+                        .param("sort", "desc");
+                let deployments: Vec<Vertex> =
+                    deployments(&client, environment.project_id, endpoint, pagination)
+                        .into_iter()
+                        .map(|deployment| Vertex::Deployment(deployment.into()))
+                        .collect();
+                Box::new(deployments.into_iter())
+            })
+        }
+        _ => unreachable!("unknown Environment edge: {edge_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_deployment_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    let client = adapter.client.clone();
+    match edge_name {
+        "environment" => Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let deployment = vertex.as_deployment().unwrap();
+            Box::new(
+                get_environment(&client, deployment.project_id, deployment.environment.id)
+                    .map(|environment| Vertex::Environment(environment.into()))
+                    .into_iter(),
+            )
+        }),
+        "merge_requests" => {
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let deployment = vertex.as_deployment().unwrap();
+                let endpoint = Resources::new(format!(
+                    "projects/{}/deployments/{}/merge_requests",
+                    deployment.project_id, deployment.id
+                ));
+                let merge_requests: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(merge_requests) => merge_requests,
+                        Err(e) => {
+                            eprintln!(
+                                "Failed to get merge requests of deployment {}: {:?}",
+                                deployment.id, e
+                            );
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+
+                let vertices: Vec<Vertex> = merge_requests
+                    .into_iter()
+                    .filter_map(api_object::<MergeRequest>)
+                    .map(|merge_request| Vertex::MergeRequest(merge_request.into()))
+                    .collect();
+                Box::new(vertices.into_iter())
+            })
+        }
+        _ => unreachable!("unknown Deployment edge: {edge_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_protected_environment_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "deploy_access_levels" | "approval_rules" => {
+            let list = if edge_name == "deploy_access_levels" {
+                "deploy_access_levels"
+            } else {
+                "approval_rules"
+            };
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let environment = vertex.as_protected_environment().unwrap();
+                let rules: Vec<Vertex> = raw_list(environment.raw_json.as_deref(), list)
+                    .into_iter()
+                    .filter_map(api_object::<EnvironmentAccessRule>)
+                    .map(|rule| Vertex::EnvironmentAccessRule(rule.into()))
+                    .collect();
+                Box::new(rules.into_iter())
+            })
+        }
+        _ => unreachable!("unknown ProtectedEnvironment edge: {edge_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_cluster_agent_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        "tokens" => {
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let agent = vertex.as_cluster_agent().unwrap();
+                let endpoint = Resources::new(format!(
+                    "projects/{}/cluster_agents/{}/tokens",
+                    agent.project_id, agent.id
+                ));
+                let tokens: Vec<serde_json::Value> =
+                    match paged(endpoint, pagination).query(&*client) {
+                        Ok(tokens) => tokens,
+                        Err(e) => {
+                            eprintln!(
+                                "Failed to get tokens of cluster agent {}: {:?}",
+                                agent.id, e
+                            );
+                            return Box::new(std::iter::empty());
+                        }
+                    };
+
+                // Only each token's own endpoint tells when the agent last used it.
+                let mut vertices = Vec::new();
+                for token in tokens {
+                    let endpoint = Resources::new(format!(
+                        "projects/{}/cluster_agents/{}/tokens/{}",
+                        agent.project_id, agent.id, token["id"]
+                    ));
+                    let token = match endpoint.query(&*client) {
+                        Ok(details) => details,
+                        Err(e) => {
+                            eprintln!("Failed to get cluster agent token {}: {:?}", token["id"], e);
+                            token
+                        }
+                    };
+                    if let Some(token) = api_object::<ClusterAgentToken>(token) {
+                        vertices.push(Vertex::ClusterAgentToken(token.into()));
+                    }
+                }
+                Box::new(vertices.into_iter())
+            })
+        }
+        _ => unreachable!("unknown ClusterAgent edge: {edge_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_feature_flag_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "strategies" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let flag = vertex.as_feature_flag().unwrap();
+            let strategies: Vec<Vertex> = raw_list(flag.raw_json.as_deref(), "strategies")
+                .into_iter()
+                .filter_map(api_object::<FeatureFlagStrategy>)
+                .map(|strategy| Vertex::FeatureFlagStrategy(strategy.into()))
+                .collect();
+            Box::new(strategies.into_iter())
+        }),
+        _ => unreachable!("unknown FeatureFlag edge: {edge_name}"),
+    }
+}
+
+/// Error tracking is only in the GraphQL API, which only returns what's asked for.
+#[cfg(feature = "operations")]
+const SENTRY_ERRORS_QUERY: &str = "
+query($fullPath: ID!, $searchTerm: String, $sort: String, $first: Int, $after: String) {
+  project(fullPath: $fullPath) {
+    sentryErrors {
+      errors(searchTerm: $searchTerm, sort: $sort, first: $first, after: $after) {
+        nodes {
+          sentryId shortId title type message culprit status
+          count userCount firstSeen lastSeen externalUrl
+        }
+        pageInfo { hasNextPage endCursor }
+      }
+    }
+  }
+}";
+
+/// Like error tracking, Terraform states are only in the GraphQL API.
+#[cfg(feature = "operations")]
+const TERRAFORM_STATES_QUERY: &str = "
+query($fullPath: ID!, $first: Int, $after: String) {
+  project(fullPath: $fullPath) {
+    terraformStates(first: $first, after: $after) {
+      nodes {
+        name lockedAt createdAt updatedAt
+        lockedByUser { username }
+        latestVersion { serial createdAt }
+      }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}";
+
+/// Per-user time logs aren't in the REST API either, which only has their totals.
+const ISSUE_TIMELOGS_QUERY: &str = "
+query($id: IssueID!, $first: Int, $after: String) {
+  issue(id: $id) {
+    timelogs(first: $first, after: $after) {
+      nodes { timeSpent spentAt summary user { id username name state webUrl } }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}";
+
+const MERGE_REQUEST_TIMELOGS_QUERY: &str = "
+query($id: MergeRequestID!, $first: Int, $after: String) {
+  mergeRequest(id: $id) {
+    timelogs(first: $first, after: $after) {
+      nodes { timeSpent spentAt summary user { id username name state webUrl } }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}";
+
+/// Nor the email participants of issues, Service Desk's included.
+const ISSUE_EMAIL_PARTICIPANTS_QUERY: &str = "
+query($id: IssueID!, $first: Int, $after: String) {
+  issue(id: $id) {
+    emailParticipants(first: $first, after: $after) {
+      nodes { email }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}";
+
+/// Requirements are only in the GraphQL API too.
+const REQUIREMENTS_QUERY: &str = "
+query($fullPath: ID!, $state: RequirementState, $first: Int, $after: String) {
+  project(fullPath: $fullPath) {
+    requirements(state: $state, first: $first, after: $after) {
+      nodes {
+        iid title description state createdAt updatedAt
+        lastTestReportState lastTestReportManuallyCreated
+        author { id username name state webUrl }
+      }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}";
+
+/// The REST API doesn't tell which pipeline triggered another, only the other way around.
+#[cfg(feature = "ci")]
+const UPSTREAM_PIPELINE_QUERY: &str = "
+query($projects: [ID!], $iid: ID!) {
+  projects(ids: $projects) {
+    nodes { pipeline(iid: $iid) { upstream { id project { id } } } }
+  }
+}";
+
+/// The pipeline whose bridge job triggered `pipeline`, if any, looked up through the
+/// GraphQL API and then read from the REST API like other pipelines.
+#[cfg(feature = "ci")]
+fn upstream_pipeline(client: &GitlabClient, pipeline: &Pipeline) -> Option<Pipeline> {
+    let variables = serde_json::json!({
+        "projects": [format!("gid://gitlab/Project/{}", pipeline.project_id)],
+        "iid": pipeline.iid.to_string(),
+    });
+    let response: serde_json::Value =
+        match Graphql::new(UPSTREAM_PIPELINE_QUERY, variables).query(client) {
+            Ok(response) => response,
+            Err(e) => {
+                eprintln!(
+                    "Failed to get the upstream of pipeline {}: {:?}",
+                    pipeline.id, e
+                );
+                return None;
+            }
+        };
+    if let Some(errors) = response.get("errors") {
+        eprintln!(
+            "Failed to get the upstream of pipeline {}: {errors}",
+            pipeline.id
+        );
+        return None;
+    }
+
+    // e.g. `gid://gitlab/Ci::Pipeline/42`
+    let upstream = response["data"].pointer("/projects/nodes/0/pipeline/upstream")?;
+    let gid = |gid: &serde_json::Value| gid.as_str()?.rsplit('/').next()?.parse::<u64>().ok();
+    let (id, project_id) = (gid(&upstream["id"])?, gid(&upstream["project"]["id"])?);
+
+    let endpoint = Resources::new(format!("projects/{project_id}/pipelines/{id}"));
+    match endpoint.query(client) {
+        Ok(upstream) => api_object::<Pipeline>(upstream),
+        Err(e) => {
+            eprintln!(
+                "Failed to get the upstream of pipeline {}: {:?}",
+                pipeline.id, e
+            );
+            None
+        }
+    }
+}
+
+/// The nodes of the GraphQL connection at `connection`, a JSON pointer into the response's
+/// `data`, paging through it with the `$first` and `$after` variables of `query`.
+fn graphql_nodes(
+    client: &GitlabClient,
+    query: &'static str,
+    mut variables: serde_json::Value,
+    connection: &str,
+    pagination: Pagination,
+) -> Vec<serde_json::Value> {
+    let limit = match pagination {
+        Pagination::Limit(limit) => limit,
+        _ => usize::MAX,
+    };
+
+    let mut nodes = Vec::new();
+    while nodes.len() < limit {
+        variables["first"] = (limit - nodes.len()).min(100).into();
+        let response: serde_json::Value = match Graphql::new(query, variables.clone()).query(client)
+        {
+            Ok(response) => response,
+            Err(e) => {
+                eprintln!("Failed to query {connection}: {:?}", e);
+                break;
+            }
+        };
+        if let Some(errors) = response.get("errors") {
+            eprintln!("Failed to query {connection}: {errors}");
+            break;
+        }
+
+        let page = match response["data"].pointer(connection) {
+            Some(page) if !page.is_null() => page,
+            _ => break,
+        };
+        if let Some(page_nodes) = page["nodes"].as_array() {
+            nodes.extend(page_nodes.iter().cloned());
+        }
+        match page["pageInfo"]["endCursor"].as_str() {
+            Some(cursor) if page["pageInfo"]["hasNextPage"] == true => {
+                variables["after"] = cursor.into();
+            }
+            _ => break,
+        }
+    }
+    nodes
+}
+
+/// The project's deployments listed by `endpoint`.
+#[cfg(feature = "operations")]
+fn deployments(
+    client: &GitlabClient,
+    project_id: u64,
+    endpoint: Resources,
+    pagination: Pagination,
+) -> Vec<Deployment> {
+    let deployments: Vec<serde_json::Value> = match paged(endpoint, pagination).query(client) {
+        Ok(deployments) => deployments,
+        Err(e) => {
+            eprintln!("Failed to get deployments of {project_id}: {:?}", e);
+            return Vec::new();
+        }
+    };
+
+    deployments
+        .into_iter()
+        .filter_map(api_object::<Deployment>)
+        .map(|mut deployment| {
+            deployment.project_id = project_id;
+            deployment
+        })
+        .collect()
+}
+
+/// An environment by id. Deployments only embed its name, not its state.
+#[cfg(feature = "operations")]
+fn get_environment(client: &GitlabClient, project_id: u64, id: u64) -> Option<Environment> {
+    let endpoint = Resources::new(format!("projects/{project_id}/environments/{id}"));
+    let environment: Result<serde_json::Value, _> = endpoint.query(client);
+    match environment {
+        Ok(environment) => api_object::<Environment>(environment).map(|mut environment| {
+            environment.project_id = project_id;
+            environment
+        }),
+        Err(e) => {
+            eprintln!("Failed to get environment {id}: {:?}", e);
+            None
+        }
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_ci_config_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "jobs" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let jobs = raw_list(vertex.as_ci_config().unwrap().raw_json.as_deref(), "jobs");
+            Box::new(
+                jobs.into_iter()
+                    .filter_map(api_object::<CiConfigJob>)
+                    .map(|job| Vertex::CiConfigJob(job.into())),
+            )
+        }),
+        "includes" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let config = vertex.as_ci_config().unwrap();
+            let includes = raw_list(config.raw_json.as_deref(), "includes");
+            Box::new(
+                includes
+                    .into_iter()
+                    .filter_map(api_object::<CiInclude>)
+                    .map(|include| Vertex::CiInclude(include.into())),
+            )
+        }),
+        _ => unreachable!("unknown CiConfig edge: {edge_name}"),
+    }
+}
+
+/// The project's CI configuration at `ref_`, expanded by GitLab's CI lint.
+#[cfg(feature = "ci")]
+fn lint_ci_config(client: &GitlabClient, project_id: &str, ref_: &str) -> Option<CiConfig> {
+    let endpoint = Resources::new(format!("projects/{project_id}/ci/lint"))
+        .param("content_ref", ref_)
+        .param("include_jobs", "true");
+    let config: Result<serde_json::Value, _> = endpoint.query(client);
+    match config {
+        Ok(config) => api_object::<CiConfig>(config),
+        Err(e) => {
+            eprintln!("Failed to lint the CI config of {project_id}: {:?}", e);
+            None
+        }
+    }
+}
+
+/// Lists a pipeline's jobs by project and pipeline id, giving each what it needs to read
+/// its log later. Jobs that were retried are left out, unless `include_retried`.
+#[cfg(feature = "ci")]
+fn job_lister(
+    adapter: &GitlabAdapter,
+    pagination: Pagination,
+    include_retried: bool,
+) -> impl Fn(u64, u64) -> Vec<PipelineJob> {
+    let client = adapter.client.clone();
+    let lazy_trace = lazy_trace(adapter);
+    move |project_id, pipeline_id| {
+        let endpoint = PipelineJobs::builder()
+            .project(project_id)
+            .pipeline(pipeline_id)
+            .include_retried(include_retried)
+            .build()
+            .unwrap();
+
+        let jobs: Result<Vec<serde_json::Value>, _> = paged(endpoint, pagination).query(&*client);
+        let jobs = match jobs {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                eprintln!("Failed to get jobs of pipeline {pipeline_id}: {:?}", e);
+                return Vec::new();
+            }
+        };
+
+        jobs.into_iter()
+            .filter_map(api_object::<PipelineJob>)
+            .map(&lazy_trace)
+            .collect()
+    }
+}
+
+/// Lets a listed job read its log when it's first needed.
+#[cfg(feature = "ci")]
+fn lazy_trace(adapter: &GitlabAdapter) -> impl Fn(PipelineJob) -> PipelineJob {
+    let client = adapter.client.clone();
+    let trace_limit = adapter.trace_limit;
+    let redact_secrets = adapter.redact_secrets;
+    move |mut job| {
+        job.trace = Some(Arc::new(LazyTrace {
+            client: client.clone(),
+            limit: trace_limit,
+            redact_secrets,
+            text: Mutex::new(None),
+        }));
+        job
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_runner_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        "jobs" => {
+            let status = extract_string_param!(parameters, "status");
+            let created_after = match datetime_param(parameters, "created_after") {
+                Ok(created_after) => created_after,
+                Err(e) => {
+                    eprintln!("Skipping Runner.jobs: {e}");
+                    return Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                        Box::new(std::iter::empty())
+                    });
+                }
+            };
+
+            let client = adapter.client.clone();
+            let pagination = adapter.pagination(parameters);
+            let lazy_trace = lazy_trace(adapter);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let runner = vertex.as_runner().unwrap();
+                let mut endpoint = Resources::new(format!("runners/{}/jobs", runner.id))
+                    .param("order_by", "id")
+                    .param("sort", "desc");
+                if let Some(status) = &status {
+                    endpoint = endpoint.param("status", status);
+                }
+                let jobs: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*client)
+                {
+                    Ok(jobs) => jobs,
+                    Err(e) => {
+                        eprintln!("Failed to get jobs of runner {}: {:?}", runner.id, e);
+                        return Box::new(std::iter::empty());
+                    }
+                };
+
+                // The API can't filter by date, but the jobs are newest first.
+                let jobs: Vec<Vertex> = jobs
+                    .into_iter()
+                    .filter_map(api_object::<PipelineJob>)
+                    .take_while(|job| match created_after {
+                        Some(created_after) => DateTime::parse_from_rfc3339(&job.created_at)
+                            .map_or(true, |created_at| created_at > created_after),
+                        None => true,
+                    })
+                    .map(|job| Vertex::PipelineJob(lazy_trace(job).into()))
+                    .collect();
+                Box::new(jobs.into_iter())
+            })
+        }
+        _ => unreachable!("unknown Runner edge: {edge_name}"),
+    }
+}
+
+/// Runners listed by `endpoint`, each fetched again for the details listings leave out,
+/// like its tags.
+#[cfg(feature = "ci")]
+pub(super) fn runners(
+    client: &GitlabClient,
+    pagination: Pagination,
+    endpoint: Resources,
+) -> VertexIterator<'static, Vertex> {
+    let runners: Vec<serde_json::Value> = match paged(endpoint, pagination).query(client) {
+        Ok(runners) => runners,
+        Err(e) => {
+            eprintln!("Failed to get runners: {:?}", e);
+            return Box::new(std::iter::empty());
+        }
+    };
+
+    let mut vertices = Vec::new();
+    for runner in runners {
+        let endpoint = Resources::new(format!("runners/{}", runner["id"]));
+        let runner = match endpoint.query(client) {
+            Ok(details) => details,
+            Err(e) => {
+                eprintln!("Failed to get runner {}: {:?}", runner["id"], e);
+                runner
+            }
+        };
+        if let Some(runner) = api_object::<Runner>(runner) {
+            vertices.push(Vertex::Runner(runner.into()));
+        }
+    }
+    Box::new(vertices.into_iter())
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_pipeline_job_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    match edge_name {
+        "retry_of" | "retried_by" => {
+            let retried_by = edge_name == "retried_by";
+            let list_jobs = job_lister(adapter, Pagination::All, true);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let job = vertex.as_pipeline_job().unwrap();
+                // Retries are new jobs with the same name in the same pipeline.
+                let attempts = list_jobs(job.pipeline.project_id, job.pipeline.id)
+                    .into_iter()
+                    .filter(|attempt| attempt.name == job.name);
+                let neighbor = if retried_by {
+                    attempts
+                        .filter(|attempt| attempt.id > job.id)
+                        .min_by_key(|attempt| attempt.id)
+                } else {
+                    attempts
+                        .filter(|attempt| attempt.id < job.id)
+                        .max_by_key(|attempt| attempt.id)
+                };
+                Box::new(
+                    neighbor
+                        .map(|attempt| Vertex::PipelineJob(attempt.into()))
+                        .into_iter(),
+                )
+            })
+        }
+        "needs" | "needed_by" => {
+            let needed_by = edge_name == "needed_by";
+            let client = adapter.client.clone();
+            let list_jobs = job_lister(adapter, Pagination::All, false);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let job = vertex.as_pipeline_job().unwrap();
+                let needs = pipeline_needs(&client, job);
+                let name = match needs.iter().find(|(name, _)| is_job_named(&job.name, name)) {
+                    Some((name, _)) => name.clone(),
+                    None => return Box::new(std::iter::empty()),
+                };
+
+                let neighbors: Vec<&String> = if needed_by {
+                    needs
+                        .iter()
+                        .filter(|(_, needs)| needs.contains(&name))
+                        .map(|(dependent, _)| dependent)
+                        .collect()
+                } else {
+                    needs
+                        .iter()
+                        .filter(|(config_name, _)| *config_name == name)
+                        .flat_map(|(_, needs)| needs)
+                        .collect()
+                };
+                if neighbors.is_empty() {
+                    return Box::new(std::iter::empty());
+                }
+
+                let jobs: Vec<Vertex> = list_jobs(job.pipeline.project_id, job.pipeline.id)
+                    .into_iter()
+                    .filter(|other| neighbors.iter().any(|name| is_job_named(&other.name, name)))
+                    .map(|other| Vertex::PipelineJob(other.into()))
+                    .collect();
+                Box::new(jobs.into_iter())
+            })
+        }
+        "artifacts" => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let job = vertex.as_pipeline_job().unwrap();
+            let artifacts: Vec<Vertex> = raw_list(job.raw_json.as_deref(), "artifacts")
+                .into_iter()
+                .filter_map(api_object::<JobArtifact>)
+                .map(|mut artifact| {
+                    artifact.expire_at = job.artifacts_expire_at.clone();
+                    Vertex::JobArtifact(artifact.into())
+                })
+                .collect();
+            Box::new(artifacts.into_iter())
+        }),
+        "test_cases" => {
+            let path = extract_string_param!(parameters, "path");
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let job = vertex.as_pipeline_job().unwrap();
+                let cases = match &path {
+                    Some(path) => {
+                        match job_artifact(&client, job, path).map(|xml| junit_test_cases(&xml)) {
+                            Some(Ok(cases)) => cases,
+                            Some(Err(e)) => {
+                                eprintln!("Failed to parse {path} of job {}: {e}", job.id);
+                                Vec::new()
+                            }
+                            None => Vec::new(),
+                        }
+                    }
+                    None => report_test_cases(&client, job),
+                };
+                Box::new(cases.into_iter().map(|case| Vertex::TestCase(case.into())))
+            })
+        }
+        "coverage_report" => {
+            let path = extract_string_param!(parameters, "path").unwrap();
+            let client = adapter.client.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let job = vertex.as_pipeline_job().unwrap();
+                let report =
+                    job_artifact(&client, job, &path).and_then(|xml| {
+                        match cobertura_report(&path, &xml) {
+                            Ok(report) => Some(Vertex::CoverageReport(report.into())),
+                            Err(e) => {
+                                eprintln!("Failed to parse {path} of job {}: {e}", job.id);
+                                None
+                            }
+                        }
+                    });
+                Box::new(report.into_iter())
+            })
+        }
+        "trace_excerpt" => {
+            let lines = match extract_int_param!(parameters, "lines") {
+                Some(lines) => lines as usize,
+                None => {
+                    eprintln!("trace_excerpt's `lines` can't be negative");
+                    return Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                        Box::new(std::iter::empty())
+                    });
+                }
+            };
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let job = vertex.as_pipeline_job().unwrap();
+                let trace = match job_trace(job) {
+                    Some(trace) => trace,
+                    None => return Box::new(std::iter::empty()),
+                };
+
+                let all_lines: Vec<&str> = trace.lines().collect();
+                let excerpt: Vec<Vertex> = all_lines[all_lines.len().saturating_sub(lines)..]
+                    .iter()
+                    .map(|line| {
+                        Vertex::TraceLine(
+                            TraceLine {
+                                text: line.to_string(),
+                            }
+                            .into(),
+                        )
+                    })
+                    .collect();
+                Box::new(excerpt.into_iter())
+            })
+        }
+        _ => unreachable!("unknown PipelineJob edge: {edge_name}"),
+    }
+}
+
+/// The file at `path` in a job's artifacts archive, or `None` if there's none.
+#[cfg(feature = "ci")]
+fn job_artifact(client: &GitlabClient, job: &PipelineJob, path: &str) -> Option<String> {
+    let endpoint = Resources::new(format!(
+        "projects/{}/jobs/{}/artifacts/{}",
+        job.pipeline.project_id,
+        job.id,
+        path.trim_start_matches('/')
+    ));
+    match raw(endpoint).query(client) {
+        Ok(content) => Some(String::from_utf8_lossy(&content).to_string()),
+        // Expired or never uploaded.
+        Err(ApiError::Gitlab { msg }) if msg.starts_with("404") => None,
+        Err(e) => {
+            eprintln!("Failed to get {path} from job {}: {:?}", job.id, e);
+            None
+        }
+    }
+}
+
+/// A job's test cases, from its pipeline's test report. The report has a suite per job,
+/// except that parallel jobs like `rspec 1/3` share the suite `rspec`, so all of their
+/// cases are returned for each of them.
+#[cfg(feature = "ci")]
+fn report_test_cases(client: &GitlabClient, job: &PipelineJob) -> Vec<TestCase> {
+    let endpoint = Resources::new(format!(
+        "projects/{}/pipelines/{}/test_report",
+        job.pipeline.project_id, job.pipeline.id
+    ));
+    let report: serde_json::Value = match endpoint.query(client) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!(
+                "Failed to get the test report of pipeline {}: {:?}",
+                job.pipeline.id, e
+            );
+            return Vec::new();
+        }
+    };
+
+    let suites = report["test_suites"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    suites
+        .into_iter()
+        .filter(|suite| {
+            suite["name"]
+                .as_str()
+                .map_or(false, |name| is_job_named(&job.name, name))
+        })
+        .flat_map(|suite| suite["test_cases"].as_array().cloned().unwrap_or_default())
+        .filter_map(api_object::<TestCase>)
+        .collect()
+}
+
+/// The query parameters of a `dora_metrics` edge.
+#[cfg(feature = "operations")]
+fn dora_params(parameters: &EdgeParameters) -> Vec<(&'static str, String)> {
+    ["metric", "interval", "start_date", "end_date"]
+        .into_iter()
+        .filter_map(|name| extract_string_param!(parameters, name).map(|value| (name, value)))
+        .collect()
+}
+
+/// A project's or group's DORA metric, one vertex per period. `scope` is `projects/:id`
+/// or `groups/:id`.
+#[cfg(feature = "operations")]
+fn dora_metrics(
+    client: &GitlabClient,
+    scope: &str,
+    params: &[(&'static str, String)],
+) -> VertexIterator<'static, Vertex> {
+    let endpoint = params.iter().fold(
+        Resources::new(format!("{scope}/dora/metrics")),
+        |endpoint, (name, value)| endpoint.param(*name, value),
+    );
+    let periods: Vec<serde_json::Value> = match endpoint.query(client) {
+        Ok(periods) => periods,
+        Err(e) => {
+            eprintln!("Failed to get DORA metrics of {scope}: {:?}", e);
+            return Box::new(std::iter::empty());
+        }
+    };
+
+    let metric = params
+        .iter()
+        .find(|(name, _)| *name == "metric")
+        .map(|(_, metric)| metric.clone())
+        .unwrap_or_default();
+    let vertices: Vec<Vertex> = periods
+        .into_iter()
+        .filter_map(api_object::<DoraMetric>)
+        .map(|mut period| {
+            period.metric = metric.clone();
+            Vertex::DoraMetric(period.into())
+        })
+        .collect();
+    Box::new(vertices.into_iter())
+}
+
+/// Whether `job` is the job `name` of the CI config, or one of its parallel instances, like
+/// `rspec 1/3` or `rspec: [ruby, 3.2]`.
+#[cfg(feature = "ci")]
+fn is_job_named(job: &str, name: &str) -> bool {
+    job == name || job.starts_with(&format!("{name} ")) || job.starts_with(&format!("{name}: "))
+}
+
+/// The `needs:` of each job in the CI config of the job's pipeline, by name. Jobs without
+/// `needs:` wait for the previous stages, which isn't a need of any particular job.
+#[cfg(feature = "ci")]
+fn pipeline_needs(client: &GitlabClient, job: &PipelineJob) -> Vec<(String, Vec<String>)> {
+    let ref_ = job.pipeline.sha.as_deref().unwrap_or(&job.ref_);
+    let config = match lint_ci_config(client, &job.pipeline.project_id.to_string(), ref_) {
+        Some(config) => config,
+        None => return Vec::new(),
+    };
+    raw_list(config.raw_json.as_deref(), "jobs")
+        .into_iter()
+        .filter_map(api_object::<CiConfigJob>)
+        .map(|config_job| {
+            let needs = config_job.needs.unwrap_or_default();
+            let needs = needs.iter().map(|need| need.name().to_string()).collect();
+            (config_job.name, needs)
+        })
+        .collect()
+}
+
+/// Pipelines listed by `endpoint`, newest first.
+#[cfg(feature = "ci")]
+fn pipelines(
+    client: &Arc<GitlabClient>,
+    pagination: Pagination,
+    endpoint: Resources,
+) -> VertexIterator<'static, Vertex> {
+    let pipelines: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&**client) {
+        Ok(pipelines) => pipelines,
+        Err(e) => {
+            eprintln!("Failed to get pipelines: {:?}", e);
+            return Box::new(std::iter::empty());
+        }
+    };
+
+    let vertices: Vec<Vertex> = pipelines
+        .into_iter()
+        .filter_map(api_object::<Pipeline>)
+        .map(|pipeline| Vertex::Pipeline(lazy_details(pipeline, client).into()))
+        .collect();
+    Box::new(vertices.into_iter())
+}
+
+/// Lets a listed pipeline fetch the details the listing left out when they're read.
+#[cfg(feature = "ci")]
+fn lazy_details(mut pipeline: Pipeline, client: &Arc<GitlabClient>) -> Pipeline {
+    pipeline.details = Some(Arc::new(LazyPipelineDetails {
+        client: client.clone(),
+        timings: Mutex::new(None),
+    }));
+    pipeline
+}
+
+#[cfg(feature = "manifests")]
+pub(super) fn resolve_manifest_file_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+) -> NeighborResolver {
+    match edge_name {
+        "dependencies" => Box::new(GitlabAdapter::manifest_dependencies),
+        _ => resolve_repo_file_edge(adapter, edge_name),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_gitlab_ci_file_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+) -> NeighborResolver {
+    match edge_name {
+        "included_files" => {
+            let client = adapter.client.clone();
+            let parsers = adapter.file_parsers.clone();
+            let redact_secrets = adapter.redact_secrets;
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let file = vertex.as_repo_file().unwrap();
+                let ref_ = match ref_or_default_branch(&file.ref_, &file.repo) {
+                    Some(ref_) => ref_,
+                    None => return Box::new(std::iter::empty()),
+                };
+                let includes = match lint_ci_config(&client, &file.repo.id, &ref_) {
+                    Some(config) => raw_list(config.raw_json.as_deref(), "includes"),
+                    None => return Box::new(std::iter::empty()),
+                };
+
+                // Includes often share a project, which only needs to be looked up once.
+                let mut repos: BTreeMap<String, Option<Arc<GitlabRepo>>> = BTreeMap::new();
+                if let Some(project) = &file.repo.project {
+                    repos.insert(project.path_with_namespace.clone(), Some(file.repo.clone()));
+                }
+
+                let mut files = Vec::new();
+                for include in includes.into_iter().filter_map(api_object::<CiInclude>) {
+                    let (project, ref_, path) = match included_file(&include) {
+                        Some(source) => source,
+                        None => continue,
+                    };
+                    let repo = repos
+                        .entry(project.clone())
+                        .or_insert_with(|| {
+                            match get_project(&client, project.as_str().into()).next() {
+                                Some(Vertex::GitlabRepo(repo)) => Some(Arc::new(repo)),
+                                _ => None,
+                            }
+                        })
+                        .clone();
+                    let repo = match repo {
+                        Some(repo) => repo,
+                        None => continue,
+                    };
+                    let ref_ = match ref_.or_else(|| repo.default_branch.clone()) {
+                        Some(ref_) => ref_,
+                        None => continue,
+                    };
+                    files.extend(GitlabAdapter::get_file(
+                        &client,
+                        &parsers,
+                        redact_secrets,
+                        repo,
+                        &path,
+                        &ref_,
+                    ));
+                }
+                Box::new(files.into_iter())
+            })
+        }
+        _ => resolve_repo_file_edge(adapter, edge_name),
+    }
+}
+
+/// The project, ref and path a CI include was read from. `local` includes are relative to
+/// the project and commit including them, which for nested includes isn't necessarily the
+/// one linted. Remote files and templates aren't in a repository, so they're `None`.
+#[cfg(feature = "ci")]
+fn included_file(include: &CiInclude) -> Option<(String, Option<String>, String)> {
+    let path = include.location.trim_start_matches('/').to_string();
+    match include.kind.as_str() {
+        "local" => Some((
+            include.context_project.clone()?,
+            include.context_sha.clone(),
+            path,
+        )),
+        "file" => Some((
+            include.extra.project.clone()?,
+            include.extra.ref_.clone(),
+            path,
+        )),
+        _ => None,
+    }
+}
+
+/// With the `history` feature, `last_commit` is read from the clone the repository's
+/// history edges use, which saves a request per file, and from the commits API if the
+/// repository can't be cloned.
+pub(super) fn resolve_repo_file_edge(adapter: &GitlabAdapter, edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "last_commit" => {
+            let client = adapter.client.clone();
+            #[cfg(feature = "history")]
+            let history = adapter.history.clone();
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let file = vertex.as_repo_file().unwrap();
+                let repo = &file.repo;
+                #[cfg(feature = "history")]
+                if let Ok(repository) = history.open(repo, client.token()) {
+                    let edge = RepoEdge::LastCommit {
+                        path: file.path.clone(),
+                        ref_: file.ref_.clone(),
+                    };
+                    return edge
+                        .or_ref(repo.default_branch.as_deref())
+                        .resolve(Ok(repository), Some(&repo.id));
+                }
+
+                let mut endpoint =
+                    Resources::new(format!("projects/{}/repository/commits", repo.id))
+                        .param("path", &file.path)
+                        .param("per_page", "1");
+                if let Some(ref_) = ref_or_default_branch(&file.ref_, repo) {
+                    endpoint = endpoint.param("ref_name", ref_);
+                }
+                let commits: Vec<serde_json::Value> = match endpoint.query(&*client) {
+                    Ok(commits) => commits,
+                    Err(e) => {
+                        eprintln!("Failed to get the last commit of {}: {:?}", file.path, e);
+                        return Box::new(std::iter::empty());
+                    }
+                };
+                let commit = commits
+                    .into_iter()
+                    .next()
+                    .map(|raw| commit_vertex(raw, &repo.id));
+                Box::new(commit.into_iter())
+            })
+        }
+        _ => unreachable!("unknown RepoFile edge: {edge_name}"),
+    }
+}
+
+/// Commits from a clone keep being read from it; those from the API are resolved with it.
+#[cfg_attr(not(feature = "ci"), allow(unused_variables))]
+pub(super) fn resolve_commit_edge(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> NeighborResolver {
+    let client = adapter.client.clone();
+    match edge_name {
+        "parents" => Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let commit = vertex.as_commit().unwrap();
+            match &commit.source {
+                CommitSource::Api { project } => {
+                    let parents: Vec<Vertex> = commit
+                        .parents
+                        .iter()
+                        .filter_map(|parent| fetch_commit(&client, project, parent))
+                        .collect();
+                    Box::new(parents.into_iter())
+                }
+                #[cfg(feature = "history")]
+                CommitSource::Git(_) => history::commit_parents(vertex),
+            }
+        }),
+        "diff" => Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+            let commit = vertex.as_commit().unwrap();
+            match &commit.source {
+                CommitSource::Api { project } => commit_diff(&client, project, &commit.hash),
+                #[cfg(feature = "history")]
+                CommitSource::Git(_) => history::commit_diff(vertex),
+            }
+        }),
+        #[cfg(feature = "ci")]
+        "pipelines" => {
+            let pagination = adapter.pagination(parameters);
+            Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let commit = vertex.as_commit().unwrap();
+                let project = match &commit.source {
+                    CommitSource::Api { project } => NameOrId::from(project.as_str()).to_string(),
+                    #[cfg(feature = "history")]
+                    CommitSource::Git(git_dir) => match &git_dir.project_id {
+                        Some(project_id) => project_id.clone(),
+                        None => return Box::new(std::iter::empty()),
+                    },
+                };
+                let endpoint = Resources::new(format!("projects/{project}/pipelines"))
+                    .param("sha", &commit.hash);
+                pipelines(&client, pagination, endpoint)
+            })
+        }
+        _ => unreachable!("unknown Commit edge: {edge_name}"),
+    }
+}
+
+/// The files a commit changed, from the API's diff against its first parent.
+fn commit_diff(client: &GitlabClient, project: &str, sha: &str) -> VertexIterator<'static, Vertex> {
+    let endpoint = Resources::new(format!(
+        "projects/{}/repository/commits/{}/diff",
+        NameOrId::from(project),
+        path_escaped(sha)
+    ));
+    let diffs: Vec<serde_json::Value> = match paged(endpoint, Pagination::All).query(client) {
+        Ok(diffs) => diffs,
+        Err(e) => {
+            eprintln!("Failed to get diff of commit {sha}: {:?}", e);
+            return Box::new(std::iter::empty());
+        }
+    };
+
+    let vertices: Vec<Vertex> = diffs
+        .into_iter()
+        .map(|diff| {
+            let flag = |name: &str| diff[name].as_bool().unwrap_or(false);
+            let mode = |name: &str| diff[name].as_str().unwrap_or_default().get(..3);
+            let status = if flag("new_file") {
+                "added"
+            } else if flag("deleted_file") {
+                "deleted"
+            } else if flag("renamed_file") {
+                "renamed"
+            } else if mode("a_mode") != mode("b_mode") {
+                // e.g. 100644 to 120000, a file replaced by a symlink.
+                "type_changed"
+            } else {
+                "modified"
+            };
+            // Only hunks, without the `---` and `+++` file headers.
+            let (mut additions, mut deletions) = (0, 0);
+            for line in diff["diff"].as_str().unwrap_or_default().lines() {
+                if line.starts_with('+') {
+                    additions += 1;
+                } else if line.starts_with('-') {
+                    deletions += 1;
+                }
+            }
+            Vertex::FileDiff(Arc::new(FileDiff {
+                path: diff["new_path"].as_str().unwrap_or_default().to_string(),
+                old_path: flag("renamed_file")
+                    .then(|| diff["old_path"].as_str().unwrap_or_default().to_string()),
+                status: status.to_string(),
+                additions,
+                deletions,
+            }))
+        })
+        .collect();
+    Box::new(vertices.into_iter())
+}
+
+#[cfg(feature = "history")]
+pub(super) fn resolve_branch_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "head" => Box::new(history::branch_head),
+        _ => unreachable!("unknown Branch edge: {edge_name}"),
+    }
+}
+
+#[cfg(feature = "history")]
+pub(super) fn resolve_blame_hunk_edge(edge_name: &str) -> NeighborResolver {
+    match edge_name {
+        "commit" => Box::new(history::blame_hunk_commit),
+        _ => unreachable!("unknown BlameHunk edge: {edge_name}"),
+    }
+}
+
+impl GitlabAdapter {
+    /// The file at `path`, fetched on its own rather than by listing the tree, or
+    /// nothing if there's no such file.
+    pub fn get_file(
+        client: &GitlabClient,
+        parsers: &FileParsers,
+        redact_secrets: bool,
+        repo: Arc<GitlabRepo>,
+        path: &str,
+        ref_: &str,
+    ) -> VertexIterator<'static, Vertex> {
+        let endpoint = FileRawBuilder::default()
+            .project(repo.id.as_str())
+            .file_path(path)
+            .ref_(ref_)
+            .build()
+            .unwrap();
+
+        match raw(endpoint).query(client) {
+            Ok(content) => Box::new(std::iter::once(Self::file_vertex(
+                RepoFile {
+                    path: path.to_string(),
+                    content: FileContent::read(&content),
+                    blob_id: None,
+                    mode: None,
+                    repo,
+                    ref_: Some(ref_.to_string()),
+                },
+                parsers,
+                redact_secrets,
+            ))),
+            Err(ApiError::Gitlab { msg }) if msg.starts_with("404") => Box::new(std::iter::empty()),
+            Err(e) => {
+                eprintln!("Failed to get file {path}: {:?}", e);
+                Box::new(std::iter::empty())
+            }
+        }
+    }
+
+    /// Only the tree is listed: each file's blob is fetched when its content or size is
+    /// first read, or right away for files that are parsed, see [`Self::file_vertex`].
+    pub fn get_files_for_repo(
+        client: &Arc<GitlabClient>,
+        parsers: &FileParsers,
+        redact_secrets: bool,
+        repo: Arc<GitlabRepo>,
+        ref_: Option<String>,
+        path: Option<String>,
+        recursive: bool,
+        pagination: Pagination,
+    ) -> VertexIterator<'static, Vertex> {
+        let mut tb = TreeBuilder::default();
+        tb.project(repo.id.clone()).recursive(recursive);
+
+        if let Some(p) = path {
+            tb.path(p);
+        };
+
+        if let Some(r) = ref_.clone() {
+            tb.ref_(r);
+        };
+
+        let tbe = tb.build().unwrap();
+
+        let files: Result<Vec<RepoTreeObject>, _> = paged(tbe, pagination).query(&**client);
+
+        match files {
+            Ok(f) => {
+                let mut nodes: Vec<Vertex> = Vec::new();
+
+                for file in f {
+                    match file.type_ {
+                        ObjectType::Tree => continue,
+                        ObjectType::Blob => {
+                            let blob = LazyBlob {
+                                client: client.clone(),
+                                redact_secrets,
+                                text: Mutex::new(None),
+                                size: Mutex::new(None),
+                            };
+                            nodes.push(Self::file_vertex(
+                                RepoFile {
+                                    path: file.path,
+                                    content: FileContent::Listed(Arc::new(blob)),
+                                    blob_id: Some(file.id.value().to_string()),
+                                    mode: Some(file.mode),
+                                    repo: repo.clone(),
+                                    ref_: ref_.clone(),
+                                },
+                                parsers,
+                                redact_secrets,
+                            ));
+                        }
+                    }
+                }
+
+                Box::new(nodes.into_iter())
+            }
+            Err(f) => {
+                eprintln!("Failed to get files for repo: {:?}", f);
+                Box::new(std::iter::empty())
+            }
+        }
+    }
+
+    #[cfg(feature = "manifests")]
+    pub(crate) fn manifest_dependencies(vertex: &Vertex) -> VertexIterator<'static, Vertex> {
+        let manifest = vertex.as_manifest_file().unwrap();
+        let dependencies: Vec<Vertex> = manifest
+            .dependencies
+            .iter()
+            .cloned()
+            .map(Vertex::ManifestDependency)
+            .collect();
+        Box::new(dependencies.into_iter())
+    }
+}
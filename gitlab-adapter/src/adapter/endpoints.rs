@@ -0,0 +1,174 @@
+//! API endpoints the `gitlab` crate doesn't provide, in the crate's own style so they can
+//! be queried and paged like its endpoints.
+
+use std::borrow::Cow;
+
+use gitlab::api::common::NameOrId;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+use http::Method;
+
+/// A list of a project's resources, e.g. `badges` for `GET /projects/:id/badges`.
+pub(super) struct ProjectResources<'a> {
+    project: NameOrId<'a>,
+    resource: &'static str,
+}
+
+impl<'a> ProjectResources<'a> {
+    pub(super) fn new(project: impl Into<NameOrId<'a>>, resource: &'static str) -> Self {
+        Self {
+            project: project.into(),
+            resource,
+        }
+    }
+}
+
+impl<'a> Endpoint for ProjectResources<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/{}", self.project, self.resource).into()
+    }
+}
+
+impl<'a> Pageable for ProjectResources<'a> {}
+
+/// A list of a user's resources, e.g. `projects` for `GET /users/:id/projects`.
+pub(super) struct UserResources<'a> {
+    user: NameOrId<'a>,
+    resource: &'static str,
+}
+
+impl<'a> UserResources<'a> {
+    pub(super) fn new(user: impl Into<NameOrId<'a>>, resource: &'static str) -> Self {
+        Self {
+            user: user.into(),
+            resource,
+        }
+    }
+}
+
+impl<'a> Endpoint for UserResources<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("users/{}/{}", self.user, self.resource).into()
+    }
+}
+
+impl<'a> Pageable for UserResources<'a> {}
+
+/// Any other list, e.g. `todos` for `GET /todos`, with optional query parameters.
+pub(super) struct Resources {
+    path: String,
+    params: Vec<(&'static str, String)>,
+}
+
+impl Resources {
+    pub(super) fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            params: Vec::new(),
+        }
+    }
+
+    pub(super) fn param(mut self, key: &'static str, value: impl ToString) -> Self {
+        self.params.push((key, value.to_string()));
+        self
+    }
+}
+
+impl Endpoint for Resources {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        self.path.clone().into()
+    }
+
+    fn parameters(&self) -> QueryParams {
+        let mut params = QueryParams::default();
+        for (key, value) in &self.params {
+            params.push(*key, value);
+        }
+        params
+    }
+}
+
+impl Pageable for Resources {}
+
+/// A query against the GraphQL API, for data the REST API doesn't have.
+pub(super) struct Graphql {
+    query: &'static str,
+    variables: serde_json::Value,
+}
+
+impl Graphql {
+    pub(super) fn new(query: &'static str, variables: serde_json::Value) -> Self {
+        Self { query, variables }
+    }
+}
+
+impl Endpoint for Graphql {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        // relative to the REST API's `api/v4/`
+        "../graphql".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, gitlab::api::BodyError> {
+        let body = serde_json::json!({ "query": self.query, "variables": self.variables });
+        Ok(Some(("application/json", body.to_string().into_bytes())))
+    }
+}
+
+/// An endpoint of the `gitlab` crate, with query parameters it doesn't support yet.
+pub(super) struct WithParams<E> {
+    endpoint: E,
+    params: Vec<(&'static str, String)>,
+}
+
+impl<E> WithParams<E> {
+    pub(super) fn new(endpoint: E) -> Self {
+        Self {
+            endpoint,
+            params: Vec::new(),
+        }
+    }
+
+    pub(super) fn param(mut self, key: &'static str, value: impl ToString) -> Self {
+        self.params.push((key, value.to_string()));
+        self
+    }
+}
+
+impl<E: Endpoint> Endpoint for WithParams<E> {
+    fn method(&self) -> Method {
+        self.endpoint.method()
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        self.endpoint.endpoint()
+    }
+
+    fn parameters(&self) -> QueryParams {
+        let mut params = self.endpoint.parameters();
+        for (key, value) in &self.params {
+            params.push(*key, value);
+        }
+        params
+    }
+}
+
+impl<E: Pageable> Pageable for WithParams<E> {
+    fn use_keyset_pagination(&self) -> bool {
+        self.endpoint.use_keyset_pagination()
+    }
+}
@@ -0,0 +1,890 @@
+//! The schema's root edges.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use gitlab::api::common::{AccessLevel, NameOrId, SortOrder, VisibilityLevel};
+use gitlab::api::groups::projects::GroupProjects;
+use gitlab::api::projects::repository::commits::Commit as CommitEndpoint;
+use gitlab::api::projects::{Project as ProjectEndpoint, ProjectOrderBy, ProjectsBuilder};
+use gitlab::api::users::CurrentUser;
+use gitlab::api::{paged, Pagination, Query};
+use gitlab::types::Project;
+use trustfall_core::{
+    interpreter::VertexIterator,
+    ir::{EdgeParameters, FieldValue},
+};
+
+#[cfg(feature = "ci")]
+use super::edges::runners;
+use super::edges::todos;
+use super::endpoints::{Resources, UserResources, WithParams};
+use super::{api_object, datetime_param, limit_param, GitlabAdapter};
+use crate::client::GitlabClient;
+use crate::vertex::{
+    Commit, CommitSource, GitlabInstance, GitlabRepo, GitlabUser, InstanceSettings, Issue,
+    MergeRequest, SearchBlob, Vertex,
+};
+
+pub(super) fn resolve_starting_vertices(
+    adapter: &GitlabAdapter,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+) -> VertexIterator<'static, Vertex> {
+    if let Err(e) = limit_param(parameters) {
+        eprintln!("Skipping {edge_name}: {e}");
+        return Box::new(std::iter::empty());
+    }
+
+    match edge_name {
+        "GitlabRepos" => match GitlabProjectsGetParams::try_from(parameters) {
+            Ok(params) => adapter.get_gitlab_repos(params),
+            Err(e) => {
+                eprintln!("Invalid GitlabRepos parameters: {e}");
+                Box::new(std::iter::empty())
+            }
+        },
+        "GitlabProject" => {
+            let full_path = extract_string_param!(parameters, "full_path");
+            let id = extract_int_param!(parameters, "id");
+            let project: NameOrId = match (full_path, id) {
+                (Some(full_path), None) => full_path.into(),
+                (None, Some(id)) => id.into(),
+                _ => {
+                    eprintln!(
+                        "GitlabProject takes exactly one of `full_path` and a non-negative `id`"
+                    );
+                    return Box::new(std::iter::empty());
+                }
+            };
+            adapter.get_gitlab_project(project)
+        }
+        "GitlabGroupProjects" => {
+            let group = extract_string_param!(parameters, "group").unwrap();
+            let include_subgroups = extract_bool_param!(parameters, "include_subgroups");
+            adapter.get_group_projects(
+                group,
+                include_subgroups.unwrap_or(false),
+                adapter.pagination(parameters),
+            )
+        }
+        "GitlabCommit" => {
+            let project = extract_string_param!(parameters, "project").unwrap();
+            let sha = extract_string_param!(parameters, "sha").unwrap();
+            adapter.get_commit(project, sha)
+        }
+        #[cfg(feature = "ci")]
+        "GitlabGroupRunners" => {
+            let group = extract_string_param!(parameters, "group").unwrap();
+            adapter.get_group_runners(group, adapter.pagination(parameters))
+        }
+        #[cfg(feature = "ci")]
+        "GitlabInstanceRunners" => {
+            let status = extract_string_param!(parameters, "status");
+            let tag = extract_string_param!(parameters, "tag");
+            adapter.get_instance_runners(status, tag, adapter.pagination(parameters))
+        }
+        "GitlabCurrentUser" => adapter.get_current_user(),
+        "GitlabTodos" => adapter.get_todos(parameters),
+        "GitlabUsers" => adapter.get_users(parameters),
+        "GitlabInstance" => adapter.get_instance(),
+        "GitlabIssues" => adapter.get_issues(parameters),
+        "GitlabMergeRequests" => adapter.get_merge_requests(parameters),
+        "GitlabSearch" => {
+            let scope = extract_string_param!(parameters, "scope").unwrap();
+            let term = extract_string_param!(parameters, "term").unwrap();
+            let project = extract_string_param!(parameters, "project");
+            let group = extract_string_param!(parameters, "group");
+            adapter.search(
+                &scope,
+                &term,
+                project,
+                group,
+                adapter.pagination(parameters),
+            )
+        }
+        "GitlabUserProjects" => {
+            let username = extract_string_param!(parameters, "username").unwrap();
+            let contributed = extract_bool_param!(parameters, "contributed");
+            adapter.get_user_projects(
+                username,
+                contributed.unwrap_or(false),
+                adapter.pagination(parameters),
+            )
+        }
+        _ => unreachable!("unknown starting edge name: {}", edge_name),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+
+pub struct GitlabProjectsGetParams {
+    pub query_string: Option<String>,
+    pub search_namespaces: Option<bool>,
+    pub language: Option<String>,
+    pub membership: Option<bool>,
+    pub last_activity_after: Option<DateTime<Utc>>,
+    pub last_activity_before: Option<DateTime<Utc>>,
+    pub archived: Option<bool>,
+    /// `public`, `internal` or `private`.
+    pub visibility: Option<String>,
+    pub owned: Option<bool>,
+    pub starred: Option<bool>,
+    pub topic: Option<String>,
+    /// One of the numeric access levels, e.g. 40 for maintainers.
+    pub min_access_level: Option<u64>,
+    /// `last_activity_at`, `created_at`, `name` or `star_count`.
+    pub order_by: Option<String>,
+    /// `asc` or `desc`.
+    pub sort: Option<String>,
+    /// How many projects to fetch, 20 if not given.
+    pub limit: Option<u64>,
+}
+
+impl TryFrom<&EdgeParameters> for GitlabProjectsGetParams {
+    type Error = String;
+
+    fn try_from(p: &EdgeParameters) -> Result<Self, String> {
+        let query_string = extract_string_param!(p, "query");
+        // Singular in the schema, unlike the API parameter.
+        let search_namespaces = extract_bool_param!(p, "search_namespace");
+
+        let language = extract_string_param!(p, "language");
+        let membership = extract_bool_param!(p, "membership");
+
+        let last_activity_before = datetime_param(p, "last_activity_before")?;
+        let last_activity_after = datetime_param(p, "last_activity_after")?;
+
+        let archived = extract_bool_param!(p, "archived");
+        let visibility = extract_string_param!(p, "visibility");
+        let owned = extract_bool_param!(p, "owned");
+        let starred = extract_bool_param!(p, "starred");
+        let topic = extract_string_param!(p, "topic");
+        let min_access_level = extract_int_param!(p, "min_access_level");
+
+        let order_by = extract_string_param!(p, "order_by");
+        let sort = extract_string_param!(p, "sort");
+
+        let limit = limit_param(p).ok().flatten().map(|limit| limit as u64);
+
+        Ok(Self {
+            query_string,
+            search_namespaces,
+            language,
+            membership,
+            last_activity_after,
+            last_activity_before,
+            archived,
+            visibility,
+            owned,
+            starred,
+            topic,
+            min_access_level,
+            order_by,
+            sort,
+            limit,
+        })
+    }
+}
+
+impl GitlabAdapter {
+    /// Applies every given parameter to a `ProjectsBuilder`. The builder's setters take
+    /// `&mut self`, so they're called on `pb` itself rather than chained. Fails on
+    /// visibilities, access levels and orderings GitLab doesn't have.
+    pub fn build_projects_builder(
+        params: GitlabProjectsGetParams,
+    ) -> Result<ProjectsBuilder<'static>, String> {
+        let mut pb = ProjectsBuilder::default();
+
+        if let Some(query_string) = params.query_string {
+            pb.search(query_string);
+        }
+
+        if let Some(search_namespaces) = params.search_namespaces {
+            pb.search_namespaces(search_namespaces);
+        }
+
+        if let Some(lang) = params.language {
+            pb.with_programming_language(lang);
+        }
+
+        if let Some(membership) = params.membership {
+            pb.membership(membership);
+        }
+
+        if let Some(last_activity_after) = params.last_activity_after {
+            pb.last_activity_after(last_activity_after);
+        }
+
+        if let Some(last_activity_before) = params.last_activity_before {
+            pb.last_activity_before(last_activity_before);
+        }
+
+        if let Some(archived) = params.archived {
+            pb.archived(archived);
+        }
+
+        if let Some(visibility) = params.visibility {
+            pb.visibility(match visibility.as_str() {
+                "public" => VisibilityLevel::Public,
+                "internal" => VisibilityLevel::Internal,
+                "private" => VisibilityLevel::Private,
+                _ => return Err(format!("unknown visibility: {visibility}")),
+            });
+        }
+
+        if let Some(owned) = params.owned {
+            pb.owned(owned);
+        }
+
+        if let Some(starred) = params.starred {
+            pb.starred(starred);
+        }
+
+        if let Some(topic) = params.topic {
+            pb.topic(topic);
+        }
+
+        if let Some(min_access_level) = params.min_access_level {
+            pb.min_access_level(match min_access_level {
+                5 => AccessLevel::Minimal,
+                10 => AccessLevel::Guest,
+                20 => AccessLevel::Reporter,
+                30 => AccessLevel::Developer,
+                40 => AccessLevel::Maintainer,
+                50 => AccessLevel::Owner,
+                _ => return Err(format!("unknown access level: {min_access_level}")),
+            });
+        }
+
+        if let Some(order_by) = params.order_by {
+            let order_by = match order_by.as_str() {
+                "last_activity_at" => Some(ProjectOrderBy::LastActivityAt),
+                "created_at" => Some(ProjectOrderBy::CreatedAt),
+                "name" => Some(ProjectOrderBy::Name),
+                // Not in `ProjectOrderBy`, so `get_gitlab_repos` adds it to the endpoint.
+                "star_count" => None,
+                _ => return Err(format!("unknown order_by: {order_by}")),
+            };
+            if let Some(order_by) = order_by {
+                pb.order_by(order_by);
+            }
+        }
+
+        if let Some(sort) = params.sort {
+            pb.sort(match sort.as_str() {
+                "asc" => SortOrder::Ascending,
+                "desc" => SortOrder::Descending,
+                _ => return Err(format!("unknown sort: {sort}")),
+            });
+        }
+
+        Ok(pb)
+    }
+
+    pub fn get_gitlab_repos(
+        &self,
+        params: GitlabProjectsGetParams,
+    ) -> VertexIterator<'static, Vertex> {
+        eprintln!("Getting gitlab repos w/ params: {:?}", &params);
+        let needs_user = [params.membership, params.owned, params.starred].contains(&Some(true))
+            || params.min_access_level.is_some();
+        if needs_user && self.requires_auth("filtering by the user's projects") {
+            return Box::new(std::iter::empty());
+        }
+        let by_stars = params.order_by.as_deref() == Some("star_count");
+        let limit = params.limit.map_or(self.page_limit, |limit| limit as usize);
+        let pb = match Self::build_projects_builder(params) {
+            Ok(pb) => pb,
+            Err(e) => {
+                eprintln!("Invalid GitlabRepos parameters: {e}");
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let mut projects = WithParams::new(pb.build().unwrap());
+        if by_stars {
+            projects = projects.param("order_by", "star_count");
+        }
+
+        let pjs: Vec<serde_json::Value> =
+            match paged(projects, Pagination::Limit(limit)).query(&*self.client) {
+                Ok(pjs) => pjs,
+                Err(e) => {
+                    eprintln!("Failed to get projects: {:?}", e);
+                    return Box::new(std::iter::empty());
+                }
+            };
+
+        let vertices: Vec<Vertex> = pjs.into_iter().filter_map(repo_vertex).collect();
+        Box::new(vertices.into_iter())
+    }
+
+    pub fn get_group_projects(
+        &self,
+        group: String,
+        include_subgroups: bool,
+        pagination: Pagination,
+    ) -> VertexIterator<'static, Vertex> {
+        eprintln!("Getting projects of group {group}, include_subgroups: {include_subgroups}");
+        let endpoint = GroupProjects::builder()
+            .group(group)
+            .include_subgroups(include_subgroups)
+            .build()
+            .unwrap();
+
+        let pjs: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*self.client) {
+            Ok(pjs) => pjs,
+            Err(e) => {
+                eprintln!("Failed to get group projects: {:?}", e);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let vertices: Vec<Vertex> = pjs.into_iter().filter_map(repo_vertex).collect();
+        Box::new(vertices.into_iter())
+    }
+
+    /// Projects in the user's namespace, and with `contributed`, also the projects
+    /// they contributed to.
+    pub fn get_user_projects(
+        &self,
+        username: String,
+        contributed: bool,
+        pagination: Pagination,
+    ) -> VertexIterator<'static, Vertex> {
+        eprintln!("Getting projects of user {username}, contributed: {contributed}");
+        let mut resources = vec!["projects"];
+        if contributed {
+            resources.push("contributed_projects");
+        }
+
+        let mut seen = HashSet::new();
+        let mut vertices = Vec::new();
+        for resource in resources {
+            let endpoint = UserResources::new(username.as_str(), resource);
+            let pjs: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*self.client)
+            {
+                Ok(pjs) => pjs,
+                Err(e) => {
+                    eprintln!("Failed to get {resource} of user {username}: {:?}", e);
+                    continue;
+                }
+            };
+
+            // Users contribute to their own projects too.
+            vertices.extend(
+                pjs.into_iter()
+                    .filter(|raw| seen.insert(raw["id"].to_string()))
+                    .filter_map(repo_vertex),
+            );
+        }
+        Box::new(vertices.into_iter())
+    }
+
+    pub fn get_current_user(&self) -> VertexIterator<'static, Vertex> {
+        if self.requires_auth("GitlabCurrentUser") {
+            return Box::new(std::iter::empty());
+        }
+
+        let endpoint = CurrentUser::builder().build().unwrap();
+        let raw: Result<serde_json::Value, _> = endpoint.query(&*self.client);
+        let raw = match raw {
+            Ok(raw) => raw,
+            Err(e) => {
+                eprintln!("Failed to get the current user: {:?}", e);
+                return Box::new(std::iter::empty());
+            }
+        };
+        match api_object::<GitlabUser>(raw) {
+            Some(user) => Box::new(std::iter::once(Vertex::CurrentUser(user.into()))),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// The authenticated user's todos, like their `todos` edge.
+    pub fn get_todos(&self, parameters: &EdgeParameters) -> VertexIterator<'static, Vertex> {
+        if self.requires_auth("GitlabTodos") {
+            return Box::new(std::iter::empty());
+        }
+
+        let state = extract_string_param!(parameters, "state");
+        let action = extract_string_param!(parameters, "action");
+        todos(
+            &self.client,
+            state.as_deref(),
+            action.as_deref(),
+            self.pagination(parameters),
+        )
+    }
+
+    /// The instance's users, as listed for administrators.
+    pub fn get_users(&self, parameters: &EdgeParameters) -> VertexIterator<'static, Vertex> {
+        if self.requires_auth("GitlabUsers") {
+            return Box::new(std::iter::empty());
+        }
+
+        let state = extract_string_param!(parameters, "state");
+        let mut endpoint = Resources::new("users");
+        // The API only filters on these two states; the others are filtered below.
+        if let Some(state @ ("active" | "blocked")) = state.as_deref() {
+            endpoint = endpoint.param(state, true);
+        }
+        if extract_bool_param!(parameters, "admins_only") == Some(true) {
+            endpoint = endpoint.param("admins", true);
+        }
+        match datetime_param(parameters, "created_after") {
+            Ok(Some(created_after)) => {
+                endpoint = endpoint.param("created_after", created_after.to_rfc3339())
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("Skipping GitlabUsers: {e}");
+                return Box::new(std::iter::empty());
+            }
+        }
+        if let Some(disabled) = extract_bool_param!(parameters, "two_factor_disabled") {
+            endpoint = endpoint.param("two_factor", if disabled { "disabled" } else { "enabled" });
+        }
+
+        let users: Vec<serde_json::Value> =
+            match paged(endpoint, self.pagination(parameters)).query(&*self.client) {
+                Ok(users) => users,
+                Err(e) => {
+                    eprintln!("Failed to get users: {:?}", e);
+                    return Box::new(std::iter::empty());
+                }
+            };
+
+        let vertices: Vec<Vertex> = users
+            .into_iter()
+            .filter_map(api_object::<GitlabUser>)
+            .filter(|user| state.as_ref().map_or(true, |state| &user.state == state))
+            .map(|user| Vertex::GitlabUser(user.into()))
+            .collect();
+        Box::new(vertices.into_iter())
+    }
+
+    pub fn get_instance(&self) -> VertexIterator<'static, Vertex> {
+        if self.requires_auth("GitlabInstance") {
+            return Box::new(std::iter::empty());
+        }
+
+        let raw: serde_json::Value = match Resources::new("metadata").query(&*self.client) {
+            Ok(raw) => raw,
+            Err(e) => {
+                eprintln!("Failed to get the instance metadata: {:?}", e);
+                return Box::new(std::iter::empty());
+            }
+        };
+        let mut instance = match api_object::<GitlabInstance>(raw) {
+            Some(instance) => instance,
+            None => return Box::new(std::iter::empty()),
+        };
+        // Only administrators can read the settings; everyone else gets them as nulls.
+        let settings: Result<serde_json::Value, _> =
+            Resources::new("application/settings").query(&*self.client);
+        instance.settings = settings
+            .ok()
+            .and_then(|settings| serde_json::from_value::<InstanceSettings>(settings).ok());
+        Box::new(std::iter::once(Vertex::GitlabInstance(instance.into())))
+    }
+
+    pub fn get_gitlab_project(&self, project: NameOrId<'_>) -> VertexIterator<'static, Vertex> {
+        eprintln!("Getting gitlab project {project}");
+        get_project(&self.client, project)
+    }
+
+    /// The commit, from the commits API, so no clone is needed.
+    pub fn get_commit(&self, project: String, sha: String) -> VertexIterator<'static, Vertex> {
+        eprintln!("Getting commit {sha} of {project}");
+        Box::new(fetch_commit(&self.client, &project, &sha).into_iter())
+    }
+
+    /// Issues across every project the user can see, not only their own.
+    pub fn get_issues(&self, parameters: &EdgeParameters) -> VertexIterator<'static, Vertex> {
+        let mut endpoint = Resources::new("issues").param("scope", "all");
+        if let Some(state) = extract_string_param!(parameters, "state") {
+            endpoint = endpoint.param("state", state);
+        }
+        if let Some(FieldValue::List(labels)) = parameters.get("labels") {
+            let labels: Vec<&str> = labels.iter().filter_map(FieldValue::as_str).collect();
+            endpoint = endpoint.param("labels", labels.join(","));
+        }
+        if let Some(assignee) = extract_string_param!(parameters, "assignee") {
+            endpoint = endpoint.param("assignee_username", assignee);
+        }
+        match datetime_param(parameters, "created_after") {
+            Ok(Some(created_after)) => {
+                endpoint = endpoint.param("created_after", created_after.to_rfc3339())
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("Skipping GitlabIssues: {e}");
+                return Box::new(std::iter::empty());
+            }
+        }
+
+        let issues: Vec<serde_json::Value> =
+            match paged(endpoint, self.pagination(parameters)).query(&*self.client) {
+                Ok(issues) => issues,
+                Err(e) => {
+                    eprintln!("Failed to get issues: {:?}", e);
+                    return Box::new(std::iter::empty());
+                }
+            };
+
+        let vertices: Vec<Vertex> = issues
+            .into_iter()
+            .filter_map(api_object::<Issue>)
+            .map(|issue| Vertex::Issue(issue.into()))
+            .collect();
+        Box::new(vertices.into_iter())
+    }
+
+    /// Merge requests across every project the user can see, not only their own.
+    pub fn get_merge_requests(
+        &self,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, Vertex> {
+        let merged_after = match datetime_param(parameters, "merged_after") {
+            Ok(merged_after) => merged_after,
+            Err(e) => {
+                eprintln!("Skipping GitlabMergeRequests: {e}");
+                return Box::new(std::iter::empty());
+            }
+        };
+        let state = extract_string_param!(parameters, "state")
+            .or_else(|| merged_after.map(|_| "merged".to_string()));
+
+        let mut endpoint = Resources::new("merge_requests").param("scope", "all");
+        if let Some(state) = state {
+            endpoint = endpoint.param("state", state);
+        }
+        if let Some(author) = extract_string_param!(parameters, "author") {
+            endpoint = endpoint.param("author_username", author);
+        }
+        if let Some(target_branch) = extract_string_param!(parameters, "target_branch") {
+            endpoint = endpoint.param("target_branch", target_branch);
+        }
+        // The API can't filter by merge time, but merging updates a merge request,
+        // so this narrows the listing down before filtering on `merged_at` below.
+        if let Some(merged_after) = merged_after {
+            endpoint = endpoint.param("updated_after", merged_after.to_rfc3339());
+        }
+
+        let merge_requests: Vec<serde_json::Value> =
+            match paged(endpoint, self.pagination(parameters)).query(&*self.client) {
+                Ok(merge_requests) => merge_requests,
+                Err(e) => {
+                    eprintln!("Failed to get merge requests: {:?}", e);
+                    return Box::new(std::iter::empty());
+                }
+            };
+
+        let vertices: Vec<Vertex> = merge_requests
+            .into_iter()
+            .filter_map(api_object::<MergeRequest>)
+            .filter(|merge_request| match merged_after {
+                Some(merged_after) => merge_request
+                    .merged_at
+                    .as_deref()
+                    .and_then(|merged_at| DateTime::parse_from_rfc3339(merged_at).ok())
+                    .map_or(false, |merged_at| {
+                        merged_at.with_timezone(&Utc) >= merged_after
+                    }),
+                None => true,
+            })
+            .map(|merge_request| Vertex::MergeRequest(merge_request.into()))
+            .collect();
+        Box::new(vertices.into_iter())
+    }
+
+    /// `scope` is one of `projects`, `issues`, `merge_requests` and `blobs`.
+    pub fn search(
+        &self,
+        scope: &str,
+        term: &str,
+        project: Option<String>,
+        group: Option<String>,
+        pagination: Pagination,
+    ) -> VertexIterator<'static, Vertex> {
+        eprintln!("Searching {scope} for {term:?}");
+        let path = match (project, group) {
+            (Some(project), _) => format!("projects/{}/search", NameOrId::from(project)),
+            (None, Some(group)) => format!("groups/{}/search", NameOrId::from(group)),
+            (None, None) => "search".to_string(),
+        };
+        let endpoint = Resources::new(path)
+            .param("scope", scope)
+            .param("search", term);
+
+        let results: Vec<serde_json::Value> = match paged(endpoint, pagination).query(&*self.client)
+        {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!("Failed to search {scope}: {:?}", e);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let vertices: Vec<Vertex> = match scope {
+            "projects" => results.into_iter().filter_map(repo_vertex).collect(),
+            "issues" => results
+                .into_iter()
+                .filter_map(api_object::<Issue>)
+                .map(|issue| Vertex::Issue(issue.into()))
+                .collect(),
+            "merge_requests" => results
+                .into_iter()
+                .filter_map(api_object::<MergeRequest>)
+                .map(|merge_request| Vertex::MergeRequest(merge_request.into()))
+                .collect(),
+            "blobs" => results
+                .into_iter()
+                .filter_map(api_object::<SearchBlob>)
+                .map(|blob| Vertex::SearchBlob(blob.into()))
+                .collect(),
+            _ => {
+                eprintln!("Unsupported search scope: {scope}");
+                Vec::new()
+            }
+        };
+        Box::new(vertices.into_iter())
+    }
+
+    /// The runners of a group, including those of its ancestors, which its projects can use.
+    #[cfg(feature = "ci")]
+    pub fn get_group_runners(
+        &self,
+        group: String,
+        pagination: Pagination,
+    ) -> VertexIterator<'static, Vertex> {
+        eprintln!("Getting runners of group {group}");
+        let group = NameOrId::from(group);
+        runners(
+            &self.client,
+            pagination,
+            Resources::new(format!("groups/{group}/runners")),
+        )
+    }
+
+    /// The instance's shared runners; needs an administrator's token.
+    #[cfg(feature = "ci")]
+    pub fn get_instance_runners(
+        &self,
+        status: Option<String>,
+        tag: Option<String>,
+        pagination: Pagination,
+    ) -> VertexIterator<'static, Vertex> {
+        eprintln!("Getting instance runners");
+        let mut endpoint = Resources::new("runners/all").param("type", "instance_type");
+        if let Some(status) = status {
+            endpoint = endpoint.param("status", status);
+        }
+        if let Some(tag) = tag {
+            endpoint = endpoint.param("tag_list", tag);
+        }
+        runners(&self.client, pagination, endpoint)
+    }
+}
+
+/// The project, or nothing if it doesn't exist or isn't visible.
+pub(super) fn get_project(
+    client: &GitlabClient,
+    project: NameOrId<'_>,
+) -> VertexIterator<'static, Vertex> {
+    let endpoint = ProjectEndpoint::builder().project(project).build().unwrap();
+    let raw: Result<serde_json::Value, _> = endpoint.query(client);
+    match raw {
+        Ok(raw) => Box::new(repo_vertex(raw).into_iter()),
+        Err(e) => {
+            eprintln!("Failed to get project: {:?}", e);
+            Box::new(std::iter::empty())
+        }
+    }
+}
+
+/// The commit, or nothing if it or the project doesn't exist or isn't visible.
+pub(super) fn fetch_commit(client: &GitlabClient, project: &str, sha: &str) -> Option<Vertex> {
+    let endpoint = CommitEndpoint::builder()
+        .project(project)
+        .commit(sha)
+        .build()
+        .unwrap();
+    match endpoint.query(client) {
+        Ok(raw) => Some(commit_vertex(raw, project)),
+        Err(e) => {
+            eprintln!("Failed to get commit: {:?}", e);
+            None
+        }
+    }
+}
+
+/// A commit as the commits API returns it, from the project with this id or full path.
+pub(super) fn commit_vertex(raw: serde_json::Value, project: &str) -> Vertex {
+    let field = |name: &str| raw[name].as_str().unwrap_or_default().to_string();
+    Vertex::Commit(Arc::new(Commit {
+        hash: field("id"),
+        summary: field("title"),
+        message: field("message"),
+        author_name: field("author_name"),
+        author_email: field("author_email"),
+        authored_at: field("authored_date"),
+        committer_name: field("committer_name"),
+        committer_email: field("committer_email"),
+        committed_at: field("committed_date"),
+        parents: raw["parent_ids"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|parent| parent.as_str().map(str::to_string))
+            .collect(),
+        source: CommitSource::Api {
+            project: project.to_string(),
+        },
+    }))
+}
+
+/// Projects are read as JSON, so fields the schema doesn't model are still reachable
+/// via `raw_json`.
+pub(super) fn repo_vertex(raw: serde_json::Value) -> Option<Vertex> {
+    let pj: Project = match serde_json::from_value(raw.clone()) {
+        Ok(pj) => pj,
+        Err(e) => {
+            eprintln!("Failed to read project: {e}");
+            return None;
+        }
+    };
+    Some(Vertex::GitlabRepo(GitlabRepo {
+        id: pj.id.to_string(),
+        url: pj.http_url_to_repo.clone(),
+        name: pj.name.clone(),
+        description: pj.description.clone().unwrap_or(String::new()),
+        repo_files: Vec::new(),
+        default_branch: pj.default_branch.clone(),
+        raw_json: Some(raw.to_string()),
+        project: Some(Arc::new(pj)),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use gitlab::api::Endpoint;
+    use url::Url;
+
+    use super::*;
+
+    /// The query parameters the built endpoint sends, in order.
+    fn query_pairs(params: GitlabProjectsGetParams) -> Vec<(String, String)> {
+        let endpoint = GitlabAdapter::build_projects_builder(params)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut url = Url::parse("https://gitlab.example.com/api/v4/projects").unwrap();
+        endpoint.parameters().add_to_url(&mut url);
+        url.query_pairs()
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn no_params_sends_nothing() {
+        assert_eq!(query_pairs(GitlabProjectsGetParams::default()), pairs(&[]));
+    }
+
+    #[test]
+    fn search_namespaces_uses_the_api_key() {
+        let params = GitlabProjectsGetParams {
+            query_string: Some("adapter".to_string()),
+            search_namespaces: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            query_pairs(params),
+            pairs(&[("search", "adapter"), ("search_namespaces", "true")]),
+        );
+    }
+
+    #[test]
+    fn every_param_is_applied() {
+        let params = GitlabProjectsGetParams {
+            query_string: Some("adapter".to_string()),
+            search_namespaces: Some(false),
+            language: Some("Rust".to_string()),
+            membership: Some(true),
+            last_activity_after: Some(Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap()),
+            last_activity_before: Some(Utc.with_ymd_and_hms(2024, 2, 29, 12, 30, 0).unwrap()),
+            archived: Some(false),
+            visibility: Some("internal".to_string()),
+            owned: Some(true),
+            starred: Some(false),
+            topic: Some("trustfall".to_string()),
+            min_access_level: Some(40),
+            order_by: Some("name".to_string()),
+            sort: Some("asc".to_string()),
+            limit: Some(5),
+        };
+        assert_eq!(
+            query_pairs(params),
+            pairs(&[
+                ("search", "adapter"),
+                ("archived", "false"),
+                ("visibility", "internal"),
+                ("search_namespaces", "false"),
+                ("owned", "true"),
+                ("membership", "true"),
+                ("starred", "false"),
+                ("with_programming_language", "Rust"),
+                ("min_access_level", "40"),
+                ("last_activity_after", "2024-01-31T00:00:00Z"),
+                ("last_activity_before", "2024-02-29T12:30:00Z"),
+                ("topic", "trustfall"),
+                ("order_by", "name"),
+                ("sort", "asc"),
+            ]),
+        );
+    }
+
+    #[test]
+    fn star_count_is_left_to_the_caller() {
+        let params = GitlabProjectsGetParams {
+            order_by: Some("star_count".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query_pairs(params), pairs(&[]));
+    }
+
+    #[test]
+    fn unknown_values_are_rejected() {
+        for params in [
+            GitlabProjectsGetParams {
+                visibility: Some("secret".to_string()),
+                ..Default::default()
+            },
+            GitlabProjectsGetParams {
+                min_access_level: Some(35),
+                ..Default::default()
+            },
+            GitlabProjectsGetParams {
+                order_by: Some("stars".to_string()),
+                ..Default::default()
+            },
+            GitlabProjectsGetParams {
+                sort: Some("up".to_string()),
+                ..Default::default()
+            },
+        ] {
+            assert!(GitlabAdapter::build_projects_builder(params).is_err());
+        }
+    }
+}
@@ -0,0 +1,462 @@
+//! The GitLab adapter, laid out like `trustfall_stubgen`'s output, so supporting a new
+//! schema type means filling in a stub rather than growing one long match:
+//!
+//! - `entrypoints`: the root edges, e.g. `GitlabRepos`
+//! - `properties`: a `resolve_<type>_property` function per vertex type
+//! - `edges`: a `resolve_<type>_edge` function per vertex type
+//! - `endpoints`: API endpoints the `gitlab` crate doesn't provide
+//! - `adapter_impl`: dispatch from type names to the above, coercions, and the
+//!   `BasicAdapter` impl
+//!
+//! After adding a type to `schema.graphql` and a variant to [`Vertex`], run
+//! `trustfall_stubgen` on the schema and move the new type's stubs into the matching
+//! module, then add its name to the dispatch in `adapter_impl`.
+
+use crate::client::GitlabClient;
+use crate::config::{ClientConfig, ConfigError};
+use crate::file_parsers::FileParsers;
+#[cfg(feature = "history")]
+use crate::history::HistoryCache;
+use crate::redaction::redact;
+#[cfg(feature = "manifests")]
+use crate::vertex::ManifestFile;
+use crate::vertex::{ApiObject, FileContent, RepoFile, Vertex};
+#[cfg(feature = "ci")]
+use crate::vertex::{Pipeline, PipelineJob, PipelineTimings};
+#[cfg(feature = "manifests")]
+use manifest_parsers::ManifestKind;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+#[cfg(feature = "ci")]
+use gitlab::api::projects::jobs::JobTrace;
+use gitlab::api::Pagination;
+use gitlab::api::{raw, Query};
+use trustfall_core::{
+    interpreter::VertexIterator,
+    ir::{EdgeParameters, FieldValue},
+};
+
+#[derive(Debug, Clone)]
+pub struct GitlabAdapter {
+    page_limit: usize,
+    client: Arc<GitlabClient>,
+    file_parsers: FileParsers,
+    redact_secrets: bool,
+    #[cfg(feature = "ci")]
+    trace_limit: usize,
+    #[cfg(feature = "history")]
+    history: Arc<HistoryCache>,
+}
+impl Default for GitlabAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+macro_rules! extract_string_param {
+    ($obj:expr, $param:expr) => {
+        $obj.get($param)
+            .map(|v| match v {
+                FieldValue::String(s) => Some(s.clone()),
+                FieldValue::Null => None,
+                _ => unreachable!(),
+            })
+            .unwrap_or(None)
+    };
+}
+
+macro_rules! extract_bool_param {
+    ($obj:expr, $param:expr) => {
+        $obj.get($param)
+            .map(|v| match v {
+                FieldValue::Boolean(s) => Some(s.clone()),
+                FieldValue::Null => None,
+                _ => unreachable!(),
+            })
+            .unwrap_or(None)
+    };
+}
+
+/// Negative values are taken as not given, rather than wrapping around to huge ones.
+macro_rules! extract_int_param {
+    ($obj:expr, $param:expr) => {
+        $obj.get($param)
+            .map(|v| match v {
+                FieldValue::Int64(i) => u64::try_from(*i).ok(),
+                FieldValue::Uint64(u) => Some(*u),
+                FieldValue::Null => None,
+                _ => unreachable!(),
+            })
+            .unwrap_or(None)
+    };
+}
+
+/// An edge's `limit` parameter, if given. Negative limits are an error: cast to the
+/// unsigned page sizes the API takes, they'd wrap around to fetching everything. Both
+/// adapters fail edges with such a limit before resolving them, so resolvers can treat
+/// the error as no limit.
+pub(crate) fn limit_param(parameters: &EdgeParameters) -> Result<Option<usize>, String> {
+    match parameters.get("limit") {
+        Some(FieldValue::Int64(limit)) => usize::try_from(*limit)
+            .map(Some)
+            .map_err(|_| format!("limit can't be negative, got {limit}")),
+        Some(FieldValue::Uint64(limit)) => Ok(Some(*limit as usize)),
+        _ => Ok(None),
+    }
+}
+
+/// A timestamp parameter, if given: rfc3339, or a plain date like "2024-01-31", taken as
+/// midnight UTC. Anything else is an error, for the edge to fail on.
+fn datetime_param(
+    parameters: &EdgeParameters,
+    name: &str,
+) -> Result<Option<DateTime<Utc>>, String> {
+    match parameters.get(name) {
+        Some(FieldValue::DateTimeUtc(datetime)) => Ok(Some(*datetime)),
+        Some(FieldValue::String(value)) => DateTime::parse_from_rfc3339(value)
+            .map(|datetime| datetime.with_timezone(&Utc))
+            .or_else(|_| {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map(|date| Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()))
+            })
+            .map(Some)
+            .map_err(|_| {
+                format!("{name} must be rfc3339 or a date like 2024-01-31, got {value:?}")
+            }),
+        _ => Ok(None),
+    }
+}
+
+mod adapter_impl;
+mod edges;
+mod endpoints;
+mod entrypoints;
+mod properties;
+
+pub use entrypoints::GitlabProjectsGetParams;
+
+impl GitlabAdapter {
+    pub fn new() -> Self {
+        Self::with_client(GitlabClient::FromEnv)
+    }
+
+    /// The schema this adapter serves, see also [`crate::SCHEMA_VERSION`].
+    pub fn schema() -> &'static trustfall::Schema {
+        crate::schema()
+    }
+
+    /// Without a token, only public data can be read. Edges that need more than that
+    /// produce no vertices instead of failing the whole query.
+    fn requires_auth(&self, what: &str) -> bool {
+        if self.client.is_authenticated() {
+            return false;
+        }
+        eprintln!("Skipping {what}, which requires authentication");
+        true
+    }
+
+    /// How many results a paginated edge fetches: its `limit` parameter, or 20 by default.
+    fn pagination(&self, parameters: &EdgeParameters) -> Pagination {
+        Pagination::Limit(
+            limit_param(parameters)
+                .ok()
+                .flatten()
+                .unwrap_or(self.page_limit),
+        )
+    }
+
+    /// Builds the client from `config`, e.g. one loaded with [`ClientConfig::load`].
+    pub fn from_config(config: &ClientConfig) -> Result<Self, ConfigError> {
+        Ok(Self::with_client(config.build()?))
+    }
+
+    /// Issues all API calls through the given client, e.g. one wrapping a custom
+    /// [`HttpTransport`](crate::client::HttpTransport).
+    pub fn with_client(client: GitlabClient) -> Self {
+        Self {
+            page_limit: 20,
+            client: Arc::new(client),
+            file_parsers: FileParsers::default(),
+            redact_secrets: true,
+            #[cfg(feature = "ci")]
+            trace_limit: 1024 * 1024,
+            #[cfg(feature = "history")]
+            history: Arc::new(HistoryCache::default()),
+        }
+    }
+
+    /// Serves files matched by `parsers` as `ParsedFile`s. Queries must then be run against
+    /// [`FileParsers::schema`], which includes the parsers' fields.
+    pub fn with_file_parsers(mut self, parsers: FileParsers) -> Self {
+        self.file_parsers = parsers;
+        self
+    }
+
+    /// Credentials in file contents, like AWS keys and access tokens, are masked unless
+    /// this is turned off, see [`crate::redaction`].
+    pub fn with_secret_redaction(mut self, enabled: bool) -> Self {
+        self.redact_secrets = enabled;
+        self
+    }
+
+    /// How much of a job's log its `trace` keeps, from the end where errors are;
+    /// 1 MiB by default.
+    #[cfg(feature = "ci")]
+    pub fn with_trace_limit(mut self, bytes: usize) -> Self {
+        self.trace_limit = bytes;
+        self
+    }
+
+    /// The schema this adapter serves, including any registered parsers' fields.
+    pub fn schema_text(&self) -> String {
+        self.file_parsers.schema_text()
+    }
+
+    /// Where repositories are cloned to serve `commits`, `branches` and `blame`.
+    /// Defaults to a directory in the system's temporary directory.
+    #[cfg(feature = "history")]
+    pub fn with_history_cache(mut self, cache: HistoryCache) -> Self {
+        self.history = Arc::new(cache);
+        self
+    }
+}
+
+impl GitlabAdapter {
+    /// Files matched by a registered parser become `ParsedFile`s, the repository's
+    /// `.gitlab-ci.yml` a `GitlabCiFile`, and files recognized as dependency manifests the
+    /// matching `RepoFile` subtype. Anything else, including files that fail to parse,
+    /// stays a plain `RepoFile`.
+    ///
+    /// Secrets are redacted before parsing, so they can't leak through parsed properties.
+    /// Listed files are only fetched here if their path says they may be one of those
+    /// types; the rest stay plain `RepoFile`s until their content is read.
+    pub(crate) fn file_vertex(
+        mut file: RepoFile,
+        parsers: &FileParsers,
+        redact_secrets: bool,
+    ) -> Vertex {
+        if matches!(file.content, FileContent::Listed(_)) {
+            if !Self::is_typed_by_content(&file.path, parsers) {
+                return file.into();
+            }
+            // Fetched content is already redacted.
+            match (file_content(&file), file_size(&file)) {
+                (Some(text), Some(size)) => file.content = FileContent::Read { text, size },
+                _ => return file.into(),
+            }
+        } else if redact_secrets {
+            if let FileContent::Read { text, .. } = &mut file.content {
+                let read = text.clone();
+                if let Cow::Owned(redacted) = redact(&read) {
+                    *text = redacted.into();
+                }
+            }
+        }
+
+        match parsers.parse(&file) {
+            Some(parsed) => parsed.into(),
+            // Only the default location: the lint API its edges use doesn't say which
+            // file a project configured instead.
+            #[cfg(feature = "ci")]
+            None if file.path == ".gitlab-ci.yml" => Vertex::GitlabCiFile(file.into()),
+            None => Self::manifest_vertex(file),
+        }
+    }
+
+    /// Whether `file_vertex` may need the file's content to pick its type.
+    fn is_typed_by_content(path: &str, parsers: &FileParsers) -> bool {
+        #[cfg(feature = "manifests")]
+        let manifest = ManifestKind::from_path(path).is_some();
+        #[cfg(not(feature = "manifests"))]
+        let manifest = false;
+
+        parsers.matches(path) || manifest || (cfg!(feature = "ci") && path == ".gitlab-ci.yml")
+    }
+
+    #[cfg(feature = "manifests")]
+    fn manifest_vertex(file: RepoFile) -> Vertex {
+        let kind = match ManifestKind::from_path(&file.path) {
+            Some(kind) => kind,
+            None => return file.into(),
+        };
+        let content = match file.content.text() {
+            Some(content) => content,
+            None => return file.into(),
+        };
+
+        match kind.parse(content) {
+            Ok(dependencies) => ManifestFile {
+                file,
+                kind,
+                dependencies: dependencies.into_iter().map(Arc::new).collect(),
+            }
+            .into(),
+            Err(e) => {
+                eprintln!(
+                    "Failed to parse {} as a {:?} manifest: {}",
+                    file.path, kind, e
+                );
+                file.into()
+            }
+        }
+    }
+
+    #[cfg(not(feature = "manifests"))]
+    fn manifest_vertex(file: RepoFile) -> Vertex {
+        file.into()
+    }
+}
+
+/// The end of a job's log, see [`GitlabAdapter::with_trace_limit`], fetched the first
+/// time it's read; `None` if it can't be.
+#[cfg(feature = "ci")]
+pub(crate) fn job_trace(job: &PipelineJob) -> Option<Arc<str>> {
+    let trace = job.trace.as_ref()?;
+    let mut text = trace.text.lock().unwrap();
+    if text.is_none() {
+        let endpoint = JobTrace::builder()
+            .project(job.pipeline.project_id)
+            .job(job.id)
+            .build()
+            .unwrap();
+        *text = Some(match raw(endpoint).query(&*trace.client) {
+            Ok(log) => {
+                let tail = log_tail(&log, trace.limit);
+                if trace.redact_secrets {
+                    Some(redact(&tail).into())
+                } else {
+                    Some(tail.into())
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to get the trace of job {}: {:?}", job.id, e);
+                None
+            }
+        });
+    }
+    text.clone().flatten()
+}
+
+/// The file's content, fetched the first time it's read if the file was listed; `None`
+/// if it can't be.
+pub(crate) fn file_content(file: &RepoFile) -> Option<Arc<str>> {
+    let blob = match &file.content {
+        FileContent::Read { text, .. } => return Some(text.clone()),
+        FileContent::Listed(blob) => blob,
+    };
+    let mut text = blob.text.lock().unwrap();
+    if text.is_none() {
+        let endpoint = endpoints::Resources::new(format!(
+            "projects/{}/repository/blobs/{}/raw",
+            file.repo.id,
+            file.blob_id.as_deref().unwrap_or_default()
+        ));
+        *text = Some(match raw(endpoint).query(&*blob.client) {
+            Ok(content) => {
+                *blob.size.lock().unwrap() = Some(Some(content.len()));
+                let content = String::from_utf8_lossy(&content);
+                if blob.redact_secrets {
+                    Some(redact(&content).into())
+                } else {
+                    Some(content.into())
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to get the content of {}: {:?}", file.path, e);
+                None
+            }
+        });
+    }
+    text.clone().flatten()
+}
+
+/// The file's size in bytes, before any redaction. For a listed file whose content
+/// wasn't read, it's fetched from the blob's metadata the first time it's read; `None`
+/// if it can't be.
+pub(crate) fn file_size(file: &RepoFile) -> Option<usize> {
+    let blob = match &file.content {
+        FileContent::Read { size, .. } => return Some(*size),
+        FileContent::Listed(blob) => blob,
+    };
+    let mut size = blob.size.lock().unwrap();
+    if size.is_none() {
+        let endpoint = endpoints::Resources::new(format!(
+            "projects/{}/repository/blobs/{}",
+            file.repo.id,
+            file.blob_id.as_deref().unwrap_or_default()
+        ));
+        let metadata: Result<serde_json::Value, _> = endpoint.query(&*blob.client);
+        *size = Some(match metadata {
+            Ok(metadata) => metadata["size"].as_u64().map(|size| size as usize),
+            Err(e) => {
+                eprintln!("Failed to get the size of {}: {:?}", file.path, e);
+                None
+            }
+        });
+    }
+    size.flatten()
+}
+
+/// The pipeline's timings, fetched the first time they're read if it was listed; empty
+/// if they can't be.
+#[cfg(feature = "ci")]
+pub(crate) fn pipeline_timings(pipeline: &Pipeline) -> PipelineTimings {
+    let details = match &pipeline.details {
+        Some(details) => details,
+        None => return pipeline.timings.clone(),
+    };
+    let mut timings = details.timings.lock().unwrap();
+    if timings.is_none() {
+        let endpoint = endpoints::Resources::new(format!(
+            "projects/{}/pipelines/{}",
+            pipeline.project_id, pipeline.id
+        ));
+        *timings = Some(match endpoint.query(&*details.client) {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                eprintln!("Failed to get pipeline {}: {:?}", pipeline.id, e);
+                PipelineTimings::default()
+            }
+        });
+    }
+    timings.clone().unwrap_or_default()
+}
+
+/// The last `limit` bytes of `log`, from the first whole line among them.
+#[cfg(feature = "ci")]
+fn log_tail(log: &[u8], limit: usize) -> String {
+    let start = log.len().saturating_sub(limit);
+    let mut tail = &log[start..];
+    if start > 0 {
+        if let Some(newline) = tail.iter().position(|&byte| byte == b'\n') {
+            tail = &tail[newline + 1..];
+        }
+    }
+    String::from_utf8_lossy(tail).to_string()
+}
+
+/// Reads an API object into `T`, keeping the object as its `raw_json`. Objects that
+/// don't match `T` are reported and skipped.
+pub(crate) fn api_object<T: ApiObject>(raw: serde_json::Value) -> Option<T> {
+    match serde_json::from_value::<T>(raw.clone()) {
+        Ok(mut object) => {
+            object.set_raw_json(raw.to_string());
+            Some(object)
+        }
+        Err(e) => {
+            eprintln!("Failed to read {}: {e}", std::any::type_name::<T>());
+            None
+        }
+    }
+}
+
+/// Resolves a property on a single vertex.
+pub type PropertyResolver = Box<dyn Fn(&Vertex) -> FieldValue>;
+
+/// Resolves an edge from a single vertex.
+pub type NeighborResolver = Box<dyn Fn(&Vertex) -> VertexIterator<'static, Vertex>>;
+
+/// Decides whether a single vertex can be coerced to a subtype.
+pub type CoercionResolver = Box<dyn Fn(&Vertex) -> bool>;
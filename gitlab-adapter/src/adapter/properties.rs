@@ -0,0 +1,1338 @@
+//! Properties, one function per vertex type dispatching on the property name.
+
+use gitlab::types::{AccessLevel, VisibilityLevel};
+use trustfall_core::ir::FieldValue;
+
+use super::{file_content, file_size, PropertyResolver};
+#[cfg(feature = "ci")]
+use super::{job_trace, pipeline_timings};
+use crate::vertex::{GitlabRepo, Vertex};
+
+macro_rules! impl_property {
+    ($conversion:ident, $attr:ident) => {
+        Box::new(|vertex: &Vertex| -> FieldValue {
+            vertex.$conversion().unwrap().$attr.clone().into()
+        })
+    };
+
+    ($conversion:ident, $var:ident, $b:block) => {
+        Box::new(|vertex: &Vertex| -> FieldValue {
+            let $var = vertex.$conversion().unwrap();
+            $b.into()
+        })
+    };
+}
+
+pub(super) fn resolve_gitlab_repo_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_gitlab_repo, id),
+        "url" => impl_property!(as_gitlab_repo, url),
+        "name" => impl_property!(as_gitlab_repo, name),
+        "description" => impl_property!(as_gitlab_repo, description),
+        "default_branch" => impl_property!(as_gitlab_repo, default_branch),
+        "full_path" => impl_property!(as_gitlab_repo, repo, {
+            repo.project
+                .as_ref()
+                .map(|project| project.path_with_namespace.clone())
+        }),
+        "ssh_url_to_repo" => impl_property!(as_gitlab_repo, repo, {
+            repo.project
+                .as_ref()
+                .map(|project| project.ssh_url_to_repo.clone())
+        }),
+        "web_url" => impl_property!(as_gitlab_repo, repo, {
+            repo.project.as_ref().map(|project| project.web_url.clone())
+        }),
+        "readme_url" => impl_property!(as_gitlab_repo, repo, {
+            repo.project
+                .as_ref()
+                .and_then(|project| project.readme_url.clone())
+        }),
+        "visibility" => impl_property!(as_gitlab_repo, repo, {
+            repo.project
+                .as_ref()
+                .map(|project| match project.visibility {
+                    VisibilityLevel::Public => "public",
+                    VisibilityLevel::Internal => "internal",
+                    VisibilityLevel::Private => "private",
+                })
+        }),
+        "topics" => impl_property!(as_gitlab_repo, repo, { repo_topics(repo) }),
+        "archived" => impl_property!(as_gitlab_repo, repo, {
+            repo.project.as_ref().map(|project| project.archived)
+        }),
+        "created_at" => impl_property!(as_gitlab_repo, repo, {
+            repo.project
+                .as_ref()
+                .map(|project| project.created_at.to_rfc3339())
+        }),
+        "last_activity_at" => impl_property!(as_gitlab_repo, repo, {
+            repo.project
+                .as_ref()
+                .map(|project| project.last_activity_at.to_rfc3339())
+        }),
+        "star_count" => impl_property!(as_gitlab_repo, repo, {
+            repo.project
+                .as_ref()
+                .map(|project| project.star_count as i64)
+        }),
+        "forks_count" => impl_property!(as_gitlab_repo, repo, {
+            repo.project
+                .as_ref()
+                .map(|project| project.forks_count as i64)
+        }),
+        "service_desk_enabled" => impl_property!(as_gitlab_repo, repo, {
+            repo_field(repo, "service_desk_enabled").and_then(|enabled| enabled.as_bool())
+        }),
+        "service_desk_address" => impl_property!(as_gitlab_repo, repo, {
+            repo_field(repo, "service_desk_address")
+                .and_then(|address| address.as_str().map(str::to_string))
+        }),
+        "open_issues_count" => impl_property!(as_gitlab_repo, repo, {
+            repo.project
+                .as_ref()
+                .and_then(|project| project.open_issues_count)
+                .map(|count| count as i64)
+        }),
+        "access_level" => impl_property!(as_gitlab_repo, repo, {
+            access_level(repo).map(|level| level as i64)
+        }),
+        "access_role" => impl_property!(as_gitlab_repo, repo, {
+            access_level(repo).map(|level| AccessLevel::from(level).as_str().to_string())
+        }),
+        "raw_json" => impl_property!(as_gitlab_repo, raw_json),
+        _ => unreachable!("unknown GitlabRepo property: {property_name}"),
+    }
+}
+
+fn access_level(repo: &GitlabRepo) -> Option<u64> {
+    let permissions = repo.project.as_ref()?.permissions.as_ref()?;
+    [&permissions.project_access, &permissions.group_access]
+        .into_iter()
+        .flatten()
+        .map(|access| access.access_level)
+        .max()
+}
+
+/// A field of the project object that `gitlab::types::Project` doesn't read.
+fn repo_field(repo: &GitlabRepo, name: &str) -> Option<serde_json::Value> {
+    repo.raw_json
+        .as_deref()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .map(|mut raw| raw[name].take())
+}
+
+fn repo_topics(repo: &GitlabRepo) -> Option<Vec<String>> {
+    let project = repo.project.as_ref()?;
+    // `topics` replaced `tag_list`, which is the only one `gitlab::types::Project` reads.
+    let topics = repo
+        .raw_json
+        .as_deref()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|raw| serde_json::from_value::<Vec<String>>(raw["topics"].clone()).ok());
+    Some(topics.unwrap_or_else(|| project.tag_list.clone()))
+}
+
+pub(super) fn resolve_namespace_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_namespace, id),
+        "name" => impl_property!(as_namespace, name),
+        "path" => impl_property!(as_namespace, path),
+        "full_path" => impl_property!(as_namespace, full_path),
+        "kind" => impl_property!(as_namespace, kind),
+        "web_url" => impl_property!(as_namespace, web_url),
+        "raw_json" => impl_property!(as_namespace, raw_json),
+        _ => unreachable!("unknown Namespace property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_badge_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_badge, badge, { badge.id as i64 }),
+        "name" => impl_property!(as_badge, name),
+        "link_url" => impl_property!(as_badge, link_url),
+        "image_url" => impl_property!(as_badge, image_url),
+        "kind" => impl_property!(as_badge, kind),
+        "raw_json" => impl_property!(as_badge, raw_json),
+        _ => unreachable!("unknown Badge property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_pages_domain_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "domain" => impl_property!(as_pages_domain, domain),
+        "url" => impl_property!(as_pages_domain, url),
+        "verified" => impl_property!(as_pages_domain, verified),
+        "enabled_until" => impl_property!(as_pages_domain, enabled_until),
+        "auto_ssl_enabled" => impl_property!(as_pages_domain, auto_ssl_enabled),
+        "certificate_expired" => impl_property!(as_pages_domain, domain, {
+            domain
+                .certificate
+                .as_ref()
+                .map(|certificate| certificate.expired)
+        }),
+        "certificate_expires_at" => impl_property!(as_pages_domain, domain, {
+            domain
+                .certificate
+                .as_ref()
+                .and_then(|certificate| certificate.expiration.clone())
+        }),
+        "raw_json" => impl_property!(as_pages_domain, raw_json),
+        _ => unreachable!("unknown PagesDomain property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_pages_deployment_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "created_at" => impl_property!(as_pages_deployment, created_at),
+        "url" => impl_property!(as_pages_deployment, url),
+        "path_prefix" => impl_property!(as_pages_deployment, path_prefix),
+        "root_directory" => impl_property!(as_pages_deployment, root_directory),
+        "raw_json" => impl_property!(as_pages_deployment, raw_json),
+        _ => unreachable!("unknown PagesDeployment property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_integration_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_integration, integration, { integration.id as i64 }),
+        "title" => impl_property!(as_integration, title),
+        "slug" => impl_property!(as_integration, slug),
+        "active" => impl_property!(as_integration, active),
+        "created_at" => impl_property!(as_integration, created_at),
+        "updated_at" => impl_property!(as_integration, updated_at),
+        "events" => impl_property!(as_integration, events),
+        "properties" => impl_property!(as_integration, integration, {
+            integration
+                .properties
+                .as_ref()
+                .map(|properties| properties.to_string())
+        }),
+        "raw_json" => impl_property!(as_integration, raw_json),
+        _ => unreachable!("unknown Integration property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "security")]
+pub(super) fn resolve_access_token_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_access_token, token, { token.id as i64 }),
+        "name" => impl_property!(as_access_token, name),
+        "scopes" => impl_property!(as_access_token, token, { token.scopes.clone() }),
+        "active" => impl_property!(as_access_token, active),
+        "revoked" => impl_property!(as_access_token, revoked),
+        "created_at" => impl_property!(as_access_token, created_at),
+        "last_used_at" => impl_property!(as_access_token, last_used_at),
+        "expires_at" => impl_property!(as_access_token, expires_at),
+        "user_id" => impl_property!(as_access_token, token, { token.user_id as i64 }),
+        "access_level" => impl_property!(as_access_token, token, {
+            token.access_level.map(|level| level as i64)
+        }),
+        "raw_json" => impl_property!(as_access_token, raw_json),
+        _ => unreachable!("unknown AccessToken property: {property_name}"),
+    }
+}
+
+/// Also serves `CurrentUser`.
+pub(super) fn resolve_gitlab_user_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_gitlab_user, user, { user.id as i64 }),
+        "username" => impl_property!(as_gitlab_user, username),
+        "name" => impl_property!(as_gitlab_user, name),
+        "state" => impl_property!(as_gitlab_user, state),
+        "web_url" => impl_property!(as_gitlab_user, web_url),
+        "created_at" => impl_property!(as_gitlab_user, created_at),
+        "last_activity_on" => impl_property!(as_gitlab_user, last_activity_on),
+        "is_admin" => impl_property!(as_gitlab_user, is_admin),
+        "two_factor_enabled" => impl_property!(as_gitlab_user, two_factor_enabled),
+        "raw_json" => impl_property!(as_gitlab_user, raw_json),
+        _ => unreachable!("unknown GitlabUser property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_todo_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_todo, todo, { todo.id as i64 }),
+        "action_name" => impl_property!(as_todo, action_name),
+        "target_type" => impl_property!(as_todo, target_type),
+        "target_title" => impl_property!(as_todo, target_title),
+        "target_url" => impl_property!(as_todo, target_url),
+        "body" => impl_property!(as_todo, body),
+        "state" => impl_property!(as_todo, state),
+        "created_at" => impl_property!(as_todo, created_at),
+        "project_full_path" => impl_property!(as_todo, project_full_path),
+        "raw_json" => impl_property!(as_todo, raw_json),
+        _ => unreachable!("unknown Todo property: {property_name}"),
+    }
+}
+
+/// Every search result type has `raw_json`, so that's the interface's only property.
+pub(super) fn resolve_search_result_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "raw_json" => Box::new(|vertex: &Vertex| -> FieldValue {
+            match vertex {
+                Vertex::GitlabRepo(repo) => repo.raw_json.clone().into(),
+                Vertex::Issue(issue) => issue.raw_json.clone().into(),
+                Vertex::MergeRequest(merge_request) => merge_request.raw_json.clone().into(),
+                Vertex::SearchBlob(blob) => blob.raw_json.clone().into(),
+                _ => unreachable!("not a SearchResult: {vertex:?}"),
+            }
+        }),
+        _ => unreachable!("unknown SearchResult property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_issue_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_issue, issue, { issue.id as i64 }),
+        "iid" => impl_property!(as_issue, issue, { issue.iid as i64 }),
+        "project_id" => impl_property!(as_issue, issue, { issue.project_id as i64 }),
+        "title" => impl_property!(as_issue, title),
+        "description" => impl_property!(as_issue, description),
+        "state" => impl_property!(as_issue, state),
+        "labels" => impl_property!(as_issue, labels),
+        "web_url" => impl_property!(as_issue, web_url),
+        "created_at" => impl_property!(as_issue, created_at),
+        "updated_at" => impl_property!(as_issue, updated_at),
+        "closed_at" => impl_property!(as_issue, closed_at),
+        "time_estimate" => impl_property!(as_issue, issue, { issue.time_stats.time_estimate }),
+        "total_time_spent" => {
+            impl_property!(as_issue, issue, { issue.time_stats.total_time_spent })
+        }
+        "upvotes" => impl_property!(as_issue, upvotes),
+        "downvotes" => impl_property!(as_issue, downvotes),
+        "weight" => impl_property!(as_issue, weight),
+        "health_status" => impl_property!(as_issue, health_status),
+        "service_desk_reply_to" => impl_property!(as_issue, service_desk_reply_to),
+        "raw_json" => impl_property!(as_issue, raw_json),
+        _ => unreachable!("unknown Issue property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_merge_request_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_merge_request, merge_request, { merge_request.id as i64 }),
+        "iid" => impl_property!(as_merge_request, merge_request, {
+            merge_request.iid as i64
+        }),
+        "project_id" => impl_property!(as_merge_request, merge_request, {
+            merge_request.project_id as i64
+        }),
+        "title" => impl_property!(as_merge_request, title),
+        "description" => impl_property!(as_merge_request, description),
+        "state" => impl_property!(as_merge_request, state),
+        "source_branch" => impl_property!(as_merge_request, source_branch),
+        "target_branch" => impl_property!(as_merge_request, target_branch),
+        "draft" => impl_property!(as_merge_request, draft),
+        "labels" => impl_property!(as_merge_request, labels),
+        "web_url" => impl_property!(as_merge_request, web_url),
+        "created_at" => impl_property!(as_merge_request, created_at),
+        "updated_at" => impl_property!(as_merge_request, updated_at),
+        "merged_at" => impl_property!(as_merge_request, merged_at),
+        "closed_at" => impl_property!(as_merge_request, closed_at),
+        "time_estimate" => impl_property!(as_merge_request, merge_request, {
+            merge_request.time_stats.time_estimate
+        }),
+        "total_time_spent" => impl_property!(as_merge_request, merge_request, {
+            merge_request.time_stats.total_time_spent
+        }),
+        "upvotes" => impl_property!(as_merge_request, upvotes),
+        "downvotes" => impl_property!(as_merge_request, downvotes),
+        "merge_status" => impl_property!(as_merge_request, merge_status),
+        "detailed_merge_status" => impl_property!(as_merge_request, detailed_merge_status),
+        "has_conflicts" => impl_property!(as_merge_request, has_conflicts),
+        "blocking_discussions_resolved" => {
+            impl_property!(as_merge_request, blocking_discussions_resolved)
+        }
+        "squash" => impl_property!(as_merge_request, squash),
+        "squash_on_merge" => impl_property!(as_merge_request, squash_on_merge),
+        "merge_when_pipeline_succeeds" => {
+            impl_property!(as_merge_request, merge_when_pipeline_succeeds)
+        }
+        "raw_json" => impl_property!(as_merge_request, raw_json),
+        _ => unreachable!("unknown MergeRequest property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_timelog_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "time_spent" => impl_property!(as_timelog, time_spent),
+        "spent_at" => impl_property!(as_timelog, spent_at),
+        "summary" => impl_property!(as_timelog, summary),
+        "raw_json" => impl_property!(as_timelog, raw_json),
+        _ => unreachable!("unknown Timelog property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_email_participant_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "email" => impl_property!(as_email_participant, email),
+        "raw_json" => impl_property!(as_email_participant, raw_json),
+        _ => unreachable!("unknown EmailParticipant property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_label_event_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_label_event, event, { event.id as i64 }),
+        "action" => impl_property!(as_label_event, action),
+        "created_at" => impl_property!(as_label_event, created_at),
+        "resource_type" => impl_property!(as_label_event, resource_type),
+        "raw_json" => impl_property!(as_label_event, raw_json),
+        _ => unreachable!("unknown LabelEvent property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_state_event_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_state_event, event, { event.id as i64 }),
+        "state" => impl_property!(as_state_event, state),
+        "created_at" => impl_property!(as_state_event, created_at),
+        "resource_type" => impl_property!(as_state_event, resource_type),
+        "raw_json" => impl_property!(as_state_event, raw_json),
+        _ => unreachable!("unknown StateEvent property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_requirement_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "iid" => impl_property!(as_requirement, requirement, {
+            requirement.iid.parse::<i64>().ok()
+        }),
+        "title" => impl_property!(as_requirement, title),
+        "description" => impl_property!(as_requirement, description),
+        "state" => impl_property!(as_requirement, requirement, {
+            requirement.state.to_lowercase()
+        }),
+        "last_test_report_state" => impl_property!(as_requirement, requirement, {
+            requirement
+                .last_test_report_state
+                .as_ref()
+                .map(|state| state.to_lowercase())
+        }),
+        "last_test_report_manually_created" => {
+            impl_property!(as_requirement, last_test_report_manually_created)
+        }
+        "created_at" => impl_property!(as_requirement, created_at),
+        "updated_at" => impl_property!(as_requirement, updated_at),
+        "raw_json" => impl_property!(as_requirement, raw_json),
+        _ => unreachable!("unknown Requirement property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_note_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_note, note, { note.id as i64 }),
+        "body" => impl_property!(as_note, body),
+        "system" => impl_property!(as_note, system),
+        "internal" => impl_property!(as_note, internal),
+        "resolvable" => impl_property!(as_note, resolvable),
+        "resolved" => impl_property!(as_note, resolved),
+        "created_at" => impl_property!(as_note, created_at),
+        "updated_at" => impl_property!(as_note, updated_at),
+        "raw_json" => impl_property!(as_note, raw_json),
+        _ => unreachable!("unknown Note property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_award_emoji_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_award_emoji, award_emoji, { award_emoji.id as i64 }),
+        "name" => impl_property!(as_award_emoji, name),
+        "awarded_at" => impl_property!(as_award_emoji, created_at),
+        "updated_at" => impl_property!(as_award_emoji, updated_at),
+        "awardable_type" => impl_property!(as_award_emoji, awardable_type),
+        "raw_json" => impl_property!(as_award_emoji, raw_json),
+        _ => unreachable!("unknown AwardEmoji property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_event_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_event, event, { event.id as i64 }),
+        "action_name" => impl_property!(as_event, action_name),
+        "target_type" => impl_property!(as_event, target_type),
+        "target_title" => impl_property!(as_event, target_title),
+        "created_at" => impl_property!(as_event, created_at),
+        "push_action" => impl_property!(as_event, event, {
+            event.push_data.as_ref().map(|push| push.action.clone())
+        }),
+        "push_ref" => impl_property!(as_event, event, {
+            event.push_data.as_ref().and_then(|push| push.ref_.clone())
+        }),
+        "push_commit_count" => impl_property!(as_event, event, {
+            event
+                .push_data
+                .as_ref()
+                .map(|push| push.commit_count as i64)
+        }),
+        "push_commit_title" => impl_property!(as_event, event, {
+            event
+                .push_data
+                .as_ref()
+                .and_then(|push| push.commit_title.clone())
+        }),
+        "raw_json" => impl_property!(as_event, raw_json),
+        _ => unreachable!("unknown Event property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_contribution_stats_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "since" => impl_property!(as_contribution_stats, since),
+        "push_count" => impl_property!(as_contribution_stats, stats, { stats.push_count as i64 }),
+        "commit_count" => {
+            impl_property!(as_contribution_stats, stats, { stats.commit_count as i64 })
+        }
+        "merge_requests_opened" => impl_property!(as_contribution_stats, stats, {
+            stats.merge_requests_opened as i64
+        }),
+        "merge_requests_merged" => impl_property!(as_contribution_stats, stats, {
+            stats.merge_requests_merged as i64
+        }),
+        "review_count" => {
+            impl_property!(as_contribution_stats, stats, { stats.review_count as i64 })
+        }
+        "comment_count" => {
+            impl_property!(as_contribution_stats, stats, { stats.comment_count as i64 })
+        }
+        _ => unreachable!("unknown ContributionStats property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_epic_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_epic, epic, { epic.id as i64 }),
+        "iid" => impl_property!(as_epic, epic, { epic.iid as i64 }),
+        "group_id" => impl_property!(as_epic, epic, { epic.group_id as i64 }),
+        "title" => impl_property!(as_epic, title),
+        "description" => impl_property!(as_epic, description),
+        "state" => impl_property!(as_epic, state),
+        "web_url" => impl_property!(as_epic, web_url),
+        "labels" => impl_property!(as_epic, epic, { epic.labels.clone() }),
+        "start_date" => impl_property!(as_epic, start_date),
+        "due_date" => impl_property!(as_epic, due_date),
+        "created_at" => impl_property!(as_epic, created_at),
+        "updated_at" => impl_property!(as_epic, updated_at),
+        "closed_at" => impl_property!(as_epic, closed_at),
+        "raw_json" => impl_property!(as_epic, raw_json),
+        _ => unreachable!("unknown Epic property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_group_member_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_group_member, member, { member.id as i64 }),
+        "username" => impl_property!(as_group_member, username),
+        "name" => impl_property!(as_group_member, name),
+        "state" => impl_property!(as_group_member, state),
+        "access_level" => impl_property!(as_group_member, member, { member.access_level as i64 }),
+        "access_role" => impl_property!(as_group_member, member, {
+            AccessLevel::from(member.access_level).as_str().to_string()
+        }),
+        "created_at" => impl_property!(as_group_member, created_at),
+        "expires_at" => impl_property!(as_group_member, expires_at),
+        "saml_provider" => impl_property!(as_group_member, member, {
+            member
+                .group_saml_identity
+                .as_ref()
+                .map(|identity| identity.provider.clone())
+        }),
+        "saml_extern_uid" => impl_property!(as_group_member, member, {
+            member
+                .group_saml_identity
+                .as_ref()
+                .map(|identity| identity.extern_uid.clone())
+        }),
+        "raw_json" => impl_property!(as_group_member, raw_json),
+        _ => unreachable!("unknown GroupMember property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_billable_member_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_billable_member, member, { member.id as i64 }),
+        "username" => impl_property!(as_billable_member, username),
+        "name" => impl_property!(as_billable_member, name),
+        "state" => impl_property!(as_billable_member, state),
+        "membership_type" => impl_property!(as_billable_member, membership_type),
+        "last_activity_on" => impl_property!(as_billable_member, last_activity_on),
+        "last_login_at" => impl_property!(as_billable_member, last_login_at),
+        "created_at" => impl_property!(as_billable_member, created_at),
+        "removable" => impl_property!(as_billable_member, removable),
+        "raw_json" => impl_property!(as_billable_member, raw_json),
+        _ => unreachable!("unknown BillableMember property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_access_request_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_access_request, request, { request.id as i64 }),
+        "username" => impl_property!(as_access_request, username),
+        "name" => impl_property!(as_access_request, name),
+        "state" => impl_property!(as_access_request, state),
+        "requested_at" => impl_property!(as_access_request, requested_at),
+        "raw_json" => impl_property!(as_access_request, raw_json),
+        _ => unreachable!("unknown AccessRequest property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_board_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_board, board, { board.id as i64 }),
+        "name" => impl_property!(as_board, name),
+        "weight" => impl_property!(as_board, weight),
+        "hide_backlog_list" => impl_property!(as_board, hide_backlog_list),
+        "hide_closed_list" => impl_property!(as_board, hide_closed_list),
+        "raw_json" => impl_property!(as_board, raw_json),
+        _ => unreachable!("unknown Board property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_board_list_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_board_list, list, { list.id as i64 }),
+        "position" => impl_property!(as_board_list, position),
+        "max_issue_count" => impl_property!(as_board_list, max_issue_count),
+        "max_issue_weight" => impl_property!(as_board_list, max_issue_weight),
+        "limit_metric" => impl_property!(as_board_list, limit_metric),
+        "raw_json" => impl_property!(as_board_list, raw_json),
+        _ => unreachable!("unknown BoardList property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_iteration_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_iteration, iteration, { iteration.id as i64 }),
+        "iid" => impl_property!(as_iteration, iteration, { iteration.iid as i64 }),
+        "sequence" => impl_property!(as_iteration, iteration, {
+            iteration.sequence.map(|sequence| sequence as i64)
+        }),
+        "group_id" => impl_property!(as_iteration, iteration, {
+            iteration.group_id.map(|id| id as i64)
+        }),
+        "title" => impl_property!(as_iteration, title),
+        "description" => impl_property!(as_iteration, description),
+        "state" => impl_property!(as_iteration, iteration, { iteration.state_name() }),
+        "start_date" => impl_property!(as_iteration, start_date),
+        "due_date" => impl_property!(as_iteration, due_date),
+        "web_url" => impl_property!(as_iteration, web_url),
+        "created_at" => impl_property!(as_iteration, created_at),
+        "updated_at" => impl_property!(as_iteration, updated_at),
+        "raw_json" => impl_property!(as_iteration, raw_json),
+        _ => unreachable!("unknown Iteration property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_label_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_label, label, { label.id as i64 }),
+        "name" => impl_property!(as_label, name),
+        "color" => impl_property!(as_label, color),
+        "text_color" => impl_property!(as_label, text_color),
+        "description" => impl_property!(as_label, description),
+        "priority" => impl_property!(as_label, priority),
+        "is_project_label" => impl_property!(as_label, is_project_label),
+        "raw_json" => impl_property!(as_label, raw_json),
+        _ => unreachable!("unknown Label property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_milestone_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_milestone, milestone, { milestone.id as i64 }),
+        "iid" => impl_property!(as_milestone, milestone, { milestone.iid as i64 }),
+        "project_id" => impl_property!(as_milestone, milestone, {
+            milestone.project_id.map(|id| id as i64)
+        }),
+        "group_id" => impl_property!(as_milestone, milestone, {
+            milestone.group_id.map(|id| id as i64)
+        }),
+        "title" => impl_property!(as_milestone, title),
+        "description" => impl_property!(as_milestone, description),
+        "state" => impl_property!(as_milestone, state),
+        "start_date" => impl_property!(as_milestone, start_date),
+        "due_date" => impl_property!(as_milestone, due_date),
+        "expired" => impl_property!(as_milestone, expired),
+        "web_url" => impl_property!(as_milestone, web_url),
+        "created_at" => impl_property!(as_milestone, created_at),
+        "updated_at" => impl_property!(as_milestone, updated_at),
+        "raw_json" => impl_property!(as_milestone, raw_json),
+        _ => unreachable!("unknown Milestone property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_milestone_stats_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "open_issues" => impl_property!(as_milestone_stats, stats, { stats.open_issues as i64 }),
+        "closed_issues" => {
+            impl_property!(as_milestone_stats, stats, { stats.closed_issues as i64 })
+        }
+        "total_weight" => impl_property!(as_milestone_stats, total_weight),
+        "closed_weight" => impl_property!(as_milestone_stats, closed_weight),
+        "completion_percentage" => impl_property!(as_milestone_stats, stats, {
+            let total = stats.open_issues + stats.closed_issues;
+            (total > 0).then(|| stats.closed_issues as f64 * 100.0 / total as f64)
+        }),
+        "weight_completion_percentage" => impl_property!(as_milestone_stats, stats, {
+            (stats.total_weight > 0)
+                .then(|| stats.closed_weight as f64 * 100.0 / stats.total_weight as f64)
+        }),
+        _ => unreachable!("unknown MilestoneStats property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_search_blob_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "path" => impl_property!(as_search_blob, path),
+        "ref" => impl_property!(as_search_blob, ref_),
+        "startline" => impl_property!(as_search_blob, startline),
+        "data" => impl_property!(as_search_blob, data),
+        "project_id" => impl_property!(as_search_blob, blob, { blob.project_id as i64 }),
+        "raw_json" => impl_property!(as_search_blob, raw_json),
+        _ => unreachable!("unknown SearchBlob property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_gitlab_instance_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "version" => impl_property!(as_gitlab_instance, version),
+        "revision" => impl_property!(as_gitlab_instance, revision),
+        "enterprise" => impl_property!(as_gitlab_instance, enterprise),
+        "kas_enabled" => impl_property!(as_gitlab_instance, instance, { instance.kas.enabled }),
+        "kas_version" => impl_property!(as_gitlab_instance, instance, {
+            instance.kas.version.clone()
+        }),
+        "signup_enabled" => impl_property!(as_gitlab_instance, instance, {
+            instance
+                .settings
+                .as_ref()
+                .and_then(|settings| settings.signup_enabled)
+        }),
+        "require_two_factor_authentication" => impl_property!(as_gitlab_instance, instance, {
+            instance
+                .settings
+                .as_ref()
+                .and_then(|settings| settings.require_two_factor_authentication)
+        }),
+        "password_authentication_enabled" => impl_property!(as_gitlab_instance, instance, {
+            instance
+                .settings
+                .as_ref()
+                .and_then(|settings| settings.password_authentication_enabled_for_web)
+        }),
+        "default_project_visibility" => impl_property!(as_gitlab_instance, instance, {
+            instance
+                .settings
+                .as_ref()
+                .and_then(|settings| settings.default_project_visibility.clone())
+        }),
+        "restricted_visibility_levels" => impl_property!(as_gitlab_instance, instance, {
+            instance
+                .settings
+                .as_ref()
+                .map(|settings| settings.restricted_visibility_levels.clone())
+        }),
+        "raw_json" => impl_property!(as_gitlab_instance, raw_json),
+        _ => unreachable!("unknown GitlabInstance property: {property_name}"),
+    }
+}
+
+/// Also serves the manifest subtypes, which share `RepoFile`'s properties.
+pub(super) fn resolve_repo_file_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "path" => impl_property!(as_repo_file, path),
+        "content" => impl_property!(as_repo_file, file, {
+            file_content(file).map(|content| content.to_string())
+        }),
+        "blob_id" => impl_property!(as_repo_file, blob_id),
+        "mode" => impl_property!(as_repo_file, mode),
+        "size" => impl_property!(as_repo_file, file, {
+            file_size(file).map(|size| size as i64)
+        }),
+        _ => unreachable!("unknown RepoFile property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_directory_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "path" => impl_property!(as_directory, path),
+        "name" => impl_property!(as_directory, directory, {
+            directory
+                .path
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        }),
+        _ => unreachable!("unknown Directory property: {property_name}"),
+    }
+}
+
+/// Fields added by parsers are looked up by name, and are `null` for other parsers' files.
+pub(super) fn resolve_parsed_file_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "path" | "content" | "blob_id" | "mode" | "size" => {
+            resolve_repo_file_property(property_name)
+        }
+        "format" => impl_property!(as_parsed_file, format),
+        _ => {
+            let property_name = property_name.to_string();
+            Box::new(move |vertex: &Vertex| -> FieldValue {
+                let parsed = vertex.as_parsed_file().unwrap();
+                parsed
+                    .properties
+                    .get(&property_name)
+                    .cloned()
+                    .unwrap_or(FieldValue::Null)
+            })
+        }
+    }
+}
+
+#[cfg(feature = "manifests")]
+pub(super) fn resolve_manifest_dependency_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "name" => impl_property!(as_manifest_dependency, name),
+        "requirement" => impl_property!(as_manifest_dependency, requirement),
+        "kind" => impl_property!(as_manifest_dependency, dependency, {
+            dependency.kind.as_str()
+        }),
+        _ => unreachable!("unknown ManifestDependency property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_pipeline_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_pipeline, pipeline, { pipeline.id as i64 }),
+        "iid" => impl_property!(as_pipeline, pipeline, { pipeline.iid as i64 }),
+        "project_id" => impl_property!(as_pipeline, pipeline, { pipeline.project_id as i64 }),
+        "status" => impl_property!(as_pipeline, status),
+        "source" => impl_property!(as_pipeline, source),
+        "ref" => impl_property!(as_pipeline, ref_),
+        "sha" => impl_property!(as_pipeline, sha),
+        "web_url" => impl_property!(as_pipeline, web_url),
+        "created_at" => impl_property!(as_pipeline, created_at),
+        "updated_at" => impl_property!(as_pipeline, updated_at),
+        "started_at" => impl_property!(as_pipeline, pipeline, {
+            pipeline_timings(pipeline).started_at
+        }),
+        "finished_at" => impl_property!(as_pipeline, pipeline, {
+            pipeline_timings(pipeline).finished_at
+        }),
+        "queued_duration" => impl_property!(as_pipeline, pipeline, {
+            pipeline_timings(pipeline)
+                .queued_duration
+                .map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "wait_time" => impl_property!(as_pipeline, pipeline, {
+            let started_at = pipeline_timings(pipeline).started_at;
+            seconds_between(&pipeline.created_at, started_at.as_deref())
+                .map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "raw_json" => impl_property!(as_pipeline, raw_json),
+        _ => unreachable!("unknown Pipeline property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_pipeline_job_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_pipeline_job, job, { job.id as i64 }),
+        "name" => impl_property!(as_pipeline_job, name),
+        "stage" => impl_property!(as_pipeline_job, stage),
+        "status" => impl_property!(as_pipeline_job, status),
+        "ref" => impl_property!(as_pipeline_job, ref_),
+        "allow_failure" => impl_property!(as_pipeline_job, allow_failure),
+        "failure_reason" => impl_property!(as_pipeline_job, failure_reason),
+        "web_url" => impl_property!(as_pipeline_job, web_url),
+        "created_at" => impl_property!(as_pipeline_job, created_at),
+        "started_at" => impl_property!(as_pipeline_job, started_at),
+        "finished_at" => impl_property!(as_pipeline_job, finished_at),
+        "duration" => impl_property!(as_pipeline_job, job, {
+            job.duration.map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "queued_duration" => impl_property!(as_pipeline_job, job, {
+            job.queued_duration
+                .map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "wait_time" => impl_property!(as_pipeline_job, job, {
+            seconds_between(&job.created_at, job.started_at.as_deref())
+                .map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "coverage" => impl_property!(as_pipeline_job, job, {
+            job.coverage.map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "tags" => impl_property!(as_pipeline_job, tag_list),
+        "artifacts_expire_at" => impl_property!(as_pipeline_job, artifacts_expire_at),
+        "retried" => impl_property!(as_pipeline_job, retried),
+        "pipeline_id" => impl_property!(as_pipeline_job, job, { job.pipeline.id as i64 }),
+        "trace" => impl_property!(as_pipeline_job, job, {
+            job_trace(job).map(|trace| trace.to_string())
+        }),
+        "raw_json" => impl_property!(as_pipeline_job, raw_json),
+        _ => unreachable!("unknown PipelineJob property: {property_name}"),
+    }
+}
+
+/// Seconds from `from` until `to`, both rfc3339; `None` until `to` is set.
+#[cfg(feature = "ci")]
+fn seconds_between(from: &str, to: Option<&str>) -> Option<f64> {
+    let from = chrono::DateTime::parse_from_rfc3339(from).ok()?;
+    let to = chrono::DateTime::parse_from_rfc3339(to?).ok()?;
+    Some((to - from).num_milliseconds() as f64 / 1000.0)
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_pipeline_coverage_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "percentage" => impl_property!(as_pipeline_coverage, coverage, {
+            coverage
+                .percentage
+                .map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        _ => unreachable!("unknown PipelineCoverage property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_pipeline_variable_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "key" => impl_property!(as_pipeline_variable, key),
+        "variable_type" => impl_property!(as_pipeline_variable, variable_type),
+        "value" => impl_property!(as_pipeline_variable, value),
+        "raw_json" => impl_property!(as_pipeline_variable, raw_json),
+        _ => unreachable!("unknown PipelineVariable property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_pipeline_schedule_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_pipeline_schedule, schedule, { schedule.id as i64 }),
+        "description" => impl_property!(as_pipeline_schedule, description),
+        "ref" => impl_property!(as_pipeline_schedule, ref_),
+        "cron" => impl_property!(as_pipeline_schedule, cron),
+        "cron_timezone" => impl_property!(as_pipeline_schedule, cron_timezone),
+        "next_run_at" => impl_property!(as_pipeline_schedule, next_run_at),
+        "active" => impl_property!(as_pipeline_schedule, active),
+        "created_at" => impl_property!(as_pipeline_schedule, created_at),
+        "updated_at" => impl_property!(as_pipeline_schedule, updated_at),
+        "last_pipeline_status" => impl_property!(as_pipeline_schedule, schedule, {
+            schedule
+                .last_pipeline
+                .as_ref()
+                .map(|pipeline| pipeline.status.clone())
+        }),
+        "owner_state" => impl_property!(as_pipeline_schedule, schedule, {
+            schedule.owner.as_ref().map(|owner| owner.state.clone())
+        }),
+        "raw_json" => impl_property!(as_pipeline_schedule, raw_json),
+        _ => unreachable!("unknown PipelineSchedule property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_job_artifact_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "file_type" => impl_property!(as_job_artifact, file_type),
+        "filename" => impl_property!(as_job_artifact, filename),
+        "file_format" => impl_property!(as_job_artifact, file_format),
+        "size" => impl_property!(as_job_artifact, artifact, { artifact.size as i64 }),
+        "expire_at" => impl_property!(as_job_artifact, expire_at),
+        // The trace is kept with the job, not as an expiring artifact.
+        "kept" => impl_property!(as_job_artifact, artifact, {
+            artifact.expire_at.is_none() && artifact.file_type != "trace"
+        }),
+        "raw_json" => impl_property!(as_job_artifact, raw_json),
+        _ => unreachable!("unknown JobArtifact property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_dora_metric_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "metric" => impl_property!(as_dora_metric, metric),
+        "date" => impl_property!(as_dora_metric, date),
+        "value" => impl_property!(as_dora_metric, metric, {
+            metric.value.map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "raw_json" => impl_property!(as_dora_metric, raw_json),
+        _ => unreachable!("unknown DoraMetric property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_merge_train_car_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_merge_train_car, car, { car.id as i64 }),
+        "status" => impl_property!(as_merge_train_car, status),
+        "target_branch" => impl_property!(as_merge_train_car, target_branch),
+        "position" => impl_property!(as_merge_train_car, car, {
+            car.position.map(|position| position as i64)
+        }),
+        "created_at" => impl_property!(as_merge_train_car, created_at),
+        "updated_at" => impl_property!(as_merge_train_car, updated_at),
+        "merged_at" => impl_property!(as_merge_train_car, merged_at),
+        "duration" => impl_property!(as_merge_train_car, car, {
+            car.duration.map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "raw_json" => impl_property!(as_merge_train_car, raw_json),
+        _ => unreachable!("unknown MergeTrainCar property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_environment_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_environment, environment, { environment.id as i64 }),
+        "project_id" => impl_property!(as_environment, environment, {
+            environment.project_id as i64
+        }),
+        "name" => impl_property!(as_environment, name),
+        "slug" => impl_property!(as_environment, slug),
+        "external_url" => impl_property!(as_environment, external_url),
+        "state" => impl_property!(as_environment, state),
+        "tier" => impl_property!(as_environment, tier),
+        "created_at" => impl_property!(as_environment, created_at),
+        "updated_at" => impl_property!(as_environment, updated_at),
+        "auto_stop_at" => impl_property!(as_environment, auto_stop_at),
+        "raw_json" => impl_property!(as_environment, raw_json),
+        _ => unreachable!("unknown Environment property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_deployment_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_deployment, deployment, { deployment.id as i64 }),
+        "iid" => impl_property!(as_deployment, deployment, { deployment.iid as i64 }),
+        "project_id" => impl_property!(as_deployment, deployment, { deployment.project_id as i64 }),
+        "ref" => impl_property!(as_deployment, ref_),
+        "sha" => impl_property!(as_deployment, sha),
+        "status" => impl_property!(as_deployment, status),
+        "created_at" => impl_property!(as_deployment, created_at),
+        "updated_at" => impl_property!(as_deployment, updated_at),
+        "environment_name" => impl_property!(as_deployment, deployment, {
+            deployment.environment.name.clone()
+        }),
+        "raw_json" => impl_property!(as_deployment, raw_json),
+        _ => unreachable!("unknown Deployment property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_protected_environment_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "name" => impl_property!(as_protected_environment, name),
+        "required_approval_count" => impl_property!(as_protected_environment, environment, {
+            environment
+                .required_approval_count
+                .map(|count| count as i64)
+        }),
+        "raw_json" => impl_property!(as_protected_environment, raw_json),
+        _ => unreachable!("unknown ProtectedEnvironment property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_environment_access_rule_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "access_level" => impl_property!(as_environment_access_rule, rule, {
+            rule.access_level.map(|level| level as i64)
+        }),
+        "access_level_description" => {
+            impl_property!(as_environment_access_rule, access_level_description)
+        }
+        "user_id" => impl_property!(as_environment_access_rule, rule, {
+            rule.user_id.map(|id| id as i64)
+        }),
+        "group_id" => impl_property!(as_environment_access_rule, rule, {
+            rule.group_id.map(|id| id as i64)
+        }),
+        "required_approvals" => impl_property!(as_environment_access_rule, rule, {
+            rule.required_approvals.map(|count| count as i64)
+        }),
+        "raw_json" => impl_property!(as_environment_access_rule, raw_json),
+        _ => unreachable!("unknown EnvironmentAccessRule property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_feature_flag_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "name" => impl_property!(as_feature_flag, name),
+        "description" => impl_property!(as_feature_flag, description),
+        "active" => impl_property!(as_feature_flag, active),
+        "version" => impl_property!(as_feature_flag, version),
+        "created_at" => impl_property!(as_feature_flag, created_at),
+        "updated_at" => impl_property!(as_feature_flag, updated_at),
+        "environment_scopes" => {
+            impl_property!(as_feature_flag, flag, { flag.environment_scopes() })
+        }
+        "raw_json" => impl_property!(as_feature_flag, raw_json),
+        _ => unreachable!("unknown FeatureFlag property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_feature_flag_strategy_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "name" => impl_property!(as_feature_flag_strategy, name),
+        "parameters" => impl_property!(as_feature_flag_strategy, strategy, {
+            strategy.parameters.to_string()
+        }),
+        "environment_scopes" => impl_property!(as_feature_flag_strategy, strategy, {
+            strategy.environment_scopes()
+        }),
+        "raw_json" => impl_property!(as_feature_flag_strategy, raw_json),
+        _ => unreachable!("unknown FeatureFlagStrategy property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_error_tracking_issue_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_error_tracking_issue, sentry_id),
+        "short_id" => impl_property!(as_error_tracking_issue, short_id),
+        "title" => impl_property!(as_error_tracking_issue, title),
+        "type" => impl_property!(as_error_tracking_issue, kind),
+        "message" => impl_property!(as_error_tracking_issue, message),
+        "culprit" => impl_property!(as_error_tracking_issue, culprit),
+        "status" => impl_property!(as_error_tracking_issue, status),
+        "count" => impl_property!(as_error_tracking_issue, issue, { issue.count as i64 }),
+        "user_count" => impl_property!(as_error_tracking_issue, issue, { issue.user_count as i64 }),
+        "first_seen" => impl_property!(as_error_tracking_issue, first_seen),
+        "last_seen" => impl_property!(as_error_tracking_issue, last_seen),
+        "external_url" => impl_property!(as_error_tracking_issue, external_url),
+        "raw_json" => impl_property!(as_error_tracking_issue, raw_json),
+        _ => unreachable!("unknown ErrorTrackingIssue property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_terraform_state_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "name" => impl_property!(as_terraform_state, name),
+        "locked" => impl_property!(as_terraform_state, state, { state.locked_at.is_some() }),
+        "locked_at" => impl_property!(as_terraform_state, locked_at),
+        "locked_by" => impl_property!(as_terraform_state, state, {
+            state
+                .locked_by_user
+                .as_ref()
+                .map(|user| user.username.clone())
+        }),
+        "latest_version_serial" => impl_property!(as_terraform_state, state, {
+            state
+                .latest_version
+                .as_ref()
+                .and_then(|version| version.serial)
+                .map(|serial| serial as i64)
+        }),
+        "latest_version_created_at" => impl_property!(as_terraform_state, state, {
+            state
+                .latest_version
+                .as_ref()
+                .map(|version| version.created_at.clone())
+        }),
+        "created_at" => impl_property!(as_terraform_state, created_at),
+        "updated_at" => impl_property!(as_terraform_state, updated_at),
+        "raw_json" => impl_property!(as_terraform_state, raw_json),
+        _ => unreachable!("unknown TerraformState property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_cluster_agent_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_cluster_agent, agent, { agent.id as i64 }),
+        "project_id" => impl_property!(as_cluster_agent, agent, { agent.project_id as i64 }),
+        "name" => impl_property!(as_cluster_agent, name),
+        "created_at" => impl_property!(as_cluster_agent, created_at),
+        "created_by_user_id" => impl_property!(as_cluster_agent, agent, {
+            agent.created_by_user_id.map(|id| id as i64)
+        }),
+        "raw_json" => impl_property!(as_cluster_agent, raw_json),
+        _ => unreachable!("unknown ClusterAgent property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "operations")]
+pub(super) fn resolve_cluster_agent_token_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_cluster_agent_token, token, { token.id as i64 }),
+        "name" => impl_property!(as_cluster_agent_token, name),
+        "description" => impl_property!(as_cluster_agent_token, description),
+        "status" => impl_property!(as_cluster_agent_token, status),
+        "created_at" => impl_property!(as_cluster_agent_token, created_at),
+        "created_by_user_id" => impl_property!(as_cluster_agent_token, token, {
+            token.created_by_user_id.map(|id| id as i64)
+        }),
+        "last_used_at" => impl_property!(as_cluster_agent_token, last_used_at),
+        "raw_json" => impl_property!(as_cluster_agent_token, raw_json),
+        _ => unreachable!("unknown ClusterAgentToken property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_runner_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "id" => impl_property!(as_runner, runner, { runner.id as i64 }),
+        "description" => impl_property!(as_runner, description),
+        "runner_type" => impl_property!(as_runner, runner_type),
+        "is_shared" => impl_property!(as_runner, is_shared),
+        "status" => impl_property!(as_runner, status),
+        "paused" => impl_property!(as_runner, paused),
+        "tags" => impl_property!(as_runner, tag_list),
+        "run_untagged" => impl_property!(as_runner, run_untagged),
+        "locked" => impl_property!(as_runner, locked),
+        "version" => impl_property!(as_runner, version),
+        "contacted_at" => impl_property!(as_runner, contacted_at),
+        "raw_json" => impl_property!(as_runner, raw_json),
+        _ => unreachable!("unknown Runner property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_trace_line_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "text" => impl_property!(as_trace_line, text),
+        _ => unreachable!("unknown TraceLine property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_test_case_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "name" => impl_property!(as_test_case, name),
+        "classname" => impl_property!(as_test_case, classname),
+        "status" => impl_property!(as_test_case, status),
+        "execution_time" => impl_property!(as_test_case, test_case, {
+            FieldValue::Float64(test_case.execution_time)
+        }),
+        "system_output" => impl_property!(as_test_case, system_output),
+        "stack_trace" => impl_property!(as_test_case, stack_trace),
+        "file" => impl_property!(as_test_case, file),
+        "raw_json" => impl_property!(as_test_case, raw_json),
+        _ => unreachable!("unknown TestCase property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_coverage_report_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "path" => impl_property!(as_coverage_report, path),
+        "line_rate" => impl_property!(as_coverage_report, report, {
+            report
+                .line_rate
+                .map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "branch_rate" => impl_property!(as_coverage_report, report, {
+            report
+                .branch_rate
+                .map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "lines_covered" => impl_property!(as_coverage_report, lines_covered),
+        "lines_valid" => impl_property!(as_coverage_report, lines_valid),
+        "branches_covered" => impl_property!(as_coverage_report, branches_covered),
+        "branches_valid" => impl_property!(as_coverage_report, branches_valid),
+        _ => unreachable!("unknown CoverageReport property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_ci_config_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "valid" => impl_property!(as_ci_config, valid),
+        "errors" => impl_property!(as_ci_config, errors),
+        "warnings" => impl_property!(as_ci_config, warnings),
+        "merged_yaml" => impl_property!(as_ci_config, merged_yaml),
+        "raw_json" => impl_property!(as_ci_config, raw_json),
+        _ => unreachable!("unknown CiConfig property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_ci_config_job_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "name" => impl_property!(as_ci_config_job, name),
+        "stage" => impl_property!(as_ci_config_job, stage),
+        "before_script" => impl_property!(as_ci_config_job, before_script),
+        "script" => impl_property!(as_ci_config_job, script),
+        "after_script" => impl_property!(as_ci_config_job, after_script),
+        "tags" => impl_property!(as_ci_config_job, tag_list),
+        "when" => impl_property!(as_ci_config_job, when),
+        "allow_failure" => impl_property!(as_ci_config_job, allow_failure),
+        "needs" => impl_property!(as_ci_config_job, job, {
+            job.needs.as_ref().map(|needs| {
+                needs
+                    .iter()
+                    .map(|need| need.name().to_string())
+                    .collect::<Vec<_>>()
+            })
+        }),
+        "raw_json" => impl_property!(as_ci_config_job, raw_json),
+        _ => unreachable!("unknown CiConfigJob property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "ci")]
+pub(super) fn resolve_ci_include_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "type" => impl_property!(as_ci_include, kind),
+        "location" => impl_property!(as_ci_include, location),
+        "blob" => impl_property!(as_ci_include, blob),
+        "raw" => impl_property!(as_ci_include, raw),
+        "project" => impl_property!(as_ci_include, include, { include.extra.project.clone() }),
+        "ref" => impl_property!(as_ci_include, include, { include.extra.ref_.clone() }),
+        "context_project" => impl_property!(as_ci_include, context_project),
+        "context_sha" => impl_property!(as_ci_include, context_sha),
+        "raw_json" => impl_property!(as_ci_include, raw_json),
+        _ => unreachable!("unknown CiInclude property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_commit_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "hash" => impl_property!(as_commit, hash),
+        "summary" => impl_property!(as_commit, summary),
+        "message" => impl_property!(as_commit, message),
+        "author_name" => impl_property!(as_commit, author_name),
+        "author_email" => impl_property!(as_commit, author_email),
+        "authored_at" => impl_property!(as_commit, authored_at),
+        "committer_name" => impl_property!(as_commit, committer_name),
+        "committer_email" => impl_property!(as_commit, committer_email),
+        "committed_at" => impl_property!(as_commit, committed_at),
+        _ => unreachable!("unknown Commit property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "history")]
+pub(super) fn resolve_branch_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "name" => impl_property!(as_branch, name),
+        _ => unreachable!("unknown Branch property: {property_name}"),
+    }
+}
+
+#[cfg(feature = "history")]
+pub(super) fn resolve_blame_hunk_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "path" => impl_property!(as_blame_hunk, path),
+        "start_line" => impl_property!(as_blame_hunk, start_line),
+        "line_count" => impl_property!(as_blame_hunk, line_count),
+        _ => unreachable!("unknown BlameHunk property: {property_name}"),
+    }
+}
+
+pub(super) fn resolve_file_diff_property(property_name: &str) -> PropertyResolver {
+    match property_name {
+        "path" => impl_property!(as_file_diff, path),
+        "old_path" => impl_property!(as_file_diff, old_path),
+        "status" => impl_property!(as_file_diff, status),
+        "additions" => impl_property!(as_file_diff, additions),
+        "deletions" => impl_property!(as_file_diff, deletions),
+        _ => unreachable!("unknown FileDiff property: {property_name}"),
+    }
+}
@@ -0,0 +1,319 @@
+//! Caching of GitLab API responses, for long-running processes like the CLI's serve mode.
+//!
+//! Responses are kept for a fixed TTL, and can be dropped early when GitLab reports
+//! a change through a webhook or system hook, see [`WebhookEvent`].
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use http::{HeaderMap, StatusCode};
+use serde_json::Value;
+
+/// Successful `GET` responses, keyed by request URL.
+#[derive(Debug)]
+pub struct ResponseCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CachedResponse>>,
+    /// When expired entries were last dropped, see [`ResponseCache::insert`].
+    swept_at: Mutex<Instant>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    api_calls: AtomicU64,
+    rate_limit: Mutex<Option<RateLimit>>,
+}
+
+/// GitLab's `RateLimit-Limit` and `RateLimit-Remaining` headers, from the latest response
+/// that carried them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+}
+
+/// Counters since the cache was created, for monitoring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Requests that reached GitLab, cacheable or not.
+    pub api_calls: u64,
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl CacheStats {
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    stored_at: Instant,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl ResponseCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            swept_at: Mutex::new(Instant::now()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            api_calls: AtomicU64::new(0),
+            rate_limit: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn get(&self, url: &str) -> Option<http::Response<Bytes>> {
+        let mut entries = self.entries.lock().unwrap();
+        let fresh = entries
+            .get(url)
+            .map(|cached| cached.stored_at.elapsed() <= self.ttl);
+        if fresh != Some(true) {
+            if fresh.is_some() {
+                entries.remove(url);
+            }
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        self.hits.fetch_add(1, Ordering::Relaxed);
+
+        let cached = &entries[url];
+
+        let mut response = http::Response::new(cached.body.clone());
+        *response.status_mut() = cached.status;
+        *response.headers_mut() = cached.headers.clone();
+        Some(response)
+    }
+
+    pub(crate) fn insert(&self, url: String, response: &http::Response<Bytes>) {
+        let mut entries = self.entries.lock().unwrap();
+
+        // `get` only drops the expired entries it's asked for, so a long-running process
+        // touching ever-new URLs would otherwise grow without bound. Sweeping at most once
+        // per TTL keeps inserts cheap, and entries at most twice the TTL old.
+        let mut swept_at = self.swept_at.lock().unwrap();
+        if swept_at.elapsed() >= self.ttl {
+            entries.retain(|_, cached| cached.stored_at.elapsed() <= self.ttl);
+            *swept_at = Instant::now();
+        }
+
+        entries.insert(
+            url,
+            CachedResponse {
+                stored_at: Instant::now(),
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: response.body().clone(),
+            },
+        );
+    }
+
+    /// Records a response that came from GitLab rather than the cache.
+    pub(crate) fn record_api_call<T>(&self, response: &http::Response<T>) {
+        self.api_calls.fetch_add(1, Ordering::Relaxed);
+
+        let header = |name: &str| -> Option<u64> {
+            response.headers().get(name)?.to_str().ok()?.parse().ok()
+        };
+        if let (Some(limit), Some(remaining)) =
+            (header("RateLimit-Limit"), header("RateLimit-Remaining"))
+        {
+            *self.rate_limit.lock().unwrap() = Some(RateLimit { limit, remaining });
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            api_calls: self.api_calls.load(Ordering::Relaxed),
+            rate_limit: *self.rate_limit.lock().unwrap(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Drops the responses an event may have made stale, returning how many were dropped,
+    /// see [`WebhookEvent::makes_stale`].
+    pub fn invalidate(&self, event: &WebhookEvent) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+        let before = entries.len();
+        entries.retain(|url, _| !event.makes_stale(url));
+        before - entries.len()
+    }
+}
+
+/// Listings across projects, which any project's events can change.
+const CROSS_PROJECT_LISTINGS: [&str; 4] = ["issues", "merge_requests", "search", "events"];
+
+/// The part of a URL between the host and the query string.
+fn url_path(url: &str) -> &str {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = without_scheme
+        .find('/')
+        .map_or("", |start| &without_scheme[start..]);
+    path.split('?').next().unwrap_or_default()
+}
+
+/// The parts of a GitLab webhook or system hook payload that decide what to invalidate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookEvent {
+    /// `object_kind` for project webhooks, `event_name` for system hooks,
+    /// e.g. `push`, `merge_request` or `pipeline`.
+    pub kind: String,
+    pub project_id: Option<u64>,
+    /// The project's `path_with_namespace`.
+    pub project_path: Option<String>,
+}
+
+impl WebhookEvent {
+    pub fn from_json(payload: &[u8]) -> Result<Self, serde_json::Error> {
+        let payload: Value = serde_json::from_slice(payload)?;
+
+        let kind = payload
+            .get("object_kind")
+            .or_else(|| payload.get("event_name"))
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string();
+
+        // Project webhooks nest the project, system hooks mostly flatten it.
+        let project = payload.get("project");
+        let project_id = project
+            .and_then(|project| project.get("id"))
+            .or_else(|| payload.get("project_id"))
+            .and_then(Value::as_u64);
+        let project_path = project
+            .and_then(|project| project.get("path_with_namespace"))
+            .or_else(|| payload.get("path_with_namespace"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        Ok(Self {
+            kind,
+            project_id,
+            project_path,
+        })
+    }
+
+    /// Whether the response cached for `url` may be stale after this event: anything
+    /// under the event's project, the groups it's in, and listings across projects, like
+    /// `/issues`, `/search` and every `.../projects`, since those embed fields like
+    /// `last_activity_at` that most events change. Groups named by id are dropped
+    /// whatever the project, since events don't say which ids its groups have.
+    pub fn makes_stale(&self, url: &str) -> bool {
+        let path = url_path(url);
+        let resource = path
+            .split_once("/api/v4/")
+            .map_or(path.trim_start_matches('/'), |(_, resource)| resource);
+        let mut segments = resource.split('/');
+        let scope = segments.next().unwrap_or_default();
+        let scope_id = segments.next();
+
+        let project = self.project_path.as_deref().unwrap_or_default();
+        let encode = |path: &str| path.replace('/', "%2F");
+        match (scope, scope_id) {
+            ("projects", None | Some("")) => true,
+            ("projects", Some(id)) => {
+                self.project_id.map_or(false, |own| id == own.to_string())
+                    || (!project.is_empty() && id == encode(project))
+                    || resource.ends_with("/projects")
+            }
+            ("groups", Some(id)) => {
+                let in_group = project
+                    .match_indices('/')
+                    .any(|(end, _)| id == encode(&project[..end]));
+                in_group || id.parse::<u64>().is_ok() || resource.ends_with("/projects")
+            }
+            _ => CROSS_PROJECT_LISTINGS.contains(&scope) || resource.ends_with("/projects"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> WebhookEvent {
+        WebhookEvent {
+            kind: "push".to_string(),
+            project_id: Some(42),
+            project_path: Some("platform/tools/deployer".to_string()),
+        }
+    }
+
+    fn stale(url: &str) -> bool {
+        event().makes_stale(&format!("https://gitlab.example.com/api/v4/{url}"))
+    }
+
+    #[test]
+    fn the_events_project_is_stale() {
+        assert!(stale("projects/42"));
+        assert!(stale("projects/42/repository/tree?recursive=true"));
+        assert!(stale("projects/platform%2Ftools%2Fdeployer/pipelines"));
+        assert!(!stale("projects/421"));
+        assert!(!stale("projects/7/issues"));
+        assert!(!stale("projects/platform%2Ftools%2Fdeployer-ui"));
+    }
+
+    #[test]
+    fn listings_across_projects_are_stale() {
+        assert!(stale("projects?membership=true"));
+        assert!(stale("issues?scope=all"));
+        assert!(stale("merge_requests"));
+        assert!(stale("search?scope=blobs&search=TODO"));
+        assert!(stale("users/3/projects"));
+        assert!(!stale("users/3"));
+        assert!(!stale("application/settings"));
+    }
+
+    #[test]
+    fn the_projects_groups_are_stale() {
+        assert!(stale("groups/platform/issues"));
+        assert!(stale("groups/platform%2Ftools/merge_requests"));
+        assert!(stale("groups/12/projects"));
+        assert!(!stale("groups/marketing/issues"));
+        assert!(!stale("groups/platform%2Ftools%2Fdeployer/issues"));
+    }
+
+    #[test]
+    fn invalidate_drops_only_stale_responses() {
+        let cache = ResponseCache::new(Duration::from_secs(60));
+        let response = http::Response::new(Bytes::new());
+        for url in [
+            "projects/42/issues",
+            "projects/7/issues",
+            "issues",
+            "users/3",
+        ] {
+            cache.insert(
+                format!("https://gitlab.example.com/api/v4/{url}"),
+                &response,
+            );
+        }
+        assert_eq!(cache.invalidate(&event()), 2);
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -0,0 +1,253 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use gitlab::api::{ApiError, Client, RestClient};
+use gitlab::{Gitlab, RestError};
+use url::Url;
+
+use crate::cache::ResponseCache;
+use crate::config::{ClientConfig, ConfigError, Credentials, TokenKind};
+
+lazy_static! {
+    // instantiate a global gitlab client
+    static ref GITLAB_CLIENT: GitlabClient = ClientConfig::load()
+        .and_then(|config| config.build())
+        .unwrap_or_else(|e| panic!("Failed to initialize the Gitlab Client: {e}"));
+}
+
+/// Sends fully-formed HTTP requests to GitLab.
+///
+/// Implement this to run the adapter over a different HTTP stack than the one
+/// built into the `gitlab` crate, e.g. one with a proxy or custom TLS roots. Transports
+/// must be thread-safe, like the rest of the adapter.
+pub trait HttpTransport: Send + Sync {
+    fn send(
+        &self,
+        request: http::Request<Vec<u8>>,
+    ) -> Result<http::Response<Vec<u8>>, TransportError>;
+}
+
+#[derive(Debug)]
+pub struct TransportError(pub Box<dyn Error + Send + Sync>);
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transport error: {}", self.0)
+    }
+}
+
+impl Error for TransportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.0.as_ref())
+    }
+}
+
+/// The client the adapter issues every GitLab API call through.
+pub enum GitlabClient {
+    /// The `gitlab` crate's own client, configured with [`ClientConfig::load`]
+    /// the first time it's used.
+    FromEnv,
+    /// The `gitlab` crate's own client, built from an explicit config.
+    Native(NativeClient),
+    /// Requests are sent through a user-provided [`HttpTransport`].
+    Transport(TransportClient),
+    /// Another client, with its `GET` responses cached, see [`GitlabClient::cached`].
+    Cached(CachedClient),
+}
+
+impl fmt::Debug for GitlabClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FromEnv => write!(f, "GitlabClient::FromEnv"),
+            Self::Native(_) => write!(f, "GitlabClient::Native"),
+            Self::Transport(client) => write!(f, "GitlabClient::Transport({})", client.rest_url),
+            Self::Cached(client) => write!(f, "GitlabClient::Cached({:?})", client.inner),
+        }
+    }
+}
+
+pub struct NativeClient {
+    client: Gitlab,
+    config: ClientConfig,
+}
+
+impl NativeClient {
+    pub fn new(config: ClientConfig) -> Result<Self, ConfigError> {
+        Ok(Self {
+            client: config.build_gitlab()?,
+            config,
+        })
+    }
+}
+
+pub struct TransportClient {
+    rest_url: Url,
+    credentials: Option<Credentials>,
+    transport: Box<dyn HttpTransport>,
+}
+
+impl TransportClient {
+    /// `host` is the GitLab instance's hostname, e.g. `gitlab.com`, and `token` a
+    /// private token. Without a token, only public data is accessible.
+    pub fn new(
+        host: &str,
+        token: Option<String>,
+        transport: impl HttpTransport + 'static,
+    ) -> Result<Self, url::ParseError> {
+        Ok(Self {
+            rest_url: Url::parse(&format!("https://{host}/api/v4/"))?,
+            credentials: token.map(|token| Credentials {
+                kind: TokenKind::Private,
+                token,
+            }),
+            transport: Box::new(transport),
+        })
+    }
+
+    /// Authenticates with other kinds of tokens, e.g. OAuth tokens.
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+}
+
+pub struct CachedClient {
+    inner: Box<GitlabClient>,
+    cache: Arc<ResponseCache>,
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    Rest(RestError),
+    Transport(TransportError),
+    Http(http::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rest(e) => write!(f, "{e}"),
+            Self::Transport(e) => write!(f, "{e}"),
+            Self::Http(e) => write!(f, "`http` error: {e}"),
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Rest(e) => Some(e),
+            Self::Transport(e) => Some(e),
+            Self::Http(e) => Some(e),
+        }
+    }
+}
+
+impl GitlabClient {
+    /// Serves repeated `GET` requests from `cache`, which can be shared with whatever
+    /// invalidates it, e.g. a webhook handler.
+    pub fn cached(self, cache: Arc<ResponseCache>) -> Self {
+        Self::Cached(CachedClient {
+            inner: Box::new(self),
+            cache,
+        })
+    }
+
+    /// Unauthenticated clients can only read public data.
+    pub fn is_authenticated(&self) -> bool {
+        match self {
+            Self::FromEnv => GITLAB_CLIENT.is_authenticated(),
+            Self::Native(client) => client.config.auth.is_some(),
+            Self::Transport(client) => client.credentials.is_some(),
+            Self::Cached(client) => client.inner.is_authenticated(),
+        }
+    }
+
+    /// The token requests are authenticated with, for use outside the REST API,
+    /// e.g. to clone repositories over HTTPS.
+    #[cfg(feature = "history")]
+    pub(crate) fn token(&self) -> Option<String> {
+        match self {
+            Self::FromEnv => GITLAB_CLIENT.token(),
+            Self::Native(client) => client.config.auth.as_ref()?.source.read().ok(),
+            Self::Transport(client) => client
+                .credentials
+                .as_ref()
+                .map(|credentials| credentials.token.clone()),
+            Self::Cached(client) => client.inner.token(),
+        }
+    }
+}
+
+impl RestClient for GitlabClient {
+    type Error = ClientError;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        match self {
+            Self::FromEnv => GITLAB_CLIENT.rest_endpoint(endpoint),
+            Self::Native(native) => native
+                .client
+                .rest_endpoint(endpoint)
+                .map_err(|e| e.map_client(ClientError::Rest)),
+            Self::Transport(client) => Ok(client.rest_url.join(endpoint)?),
+            Self::Cached(client) => client.inner.rest_endpoint(endpoint),
+        }
+    }
+}
+
+impl Client for GitlabClient {
+    fn rest(
+        &self,
+        mut request: http::request::Builder,
+        body: Vec<u8>,
+    ) -> Result<http::Response<Bytes>, ApiError<Self::Error>> {
+        match self {
+            Self::FromEnv => GITLAB_CLIENT.rest(request, body),
+            Self::Native(native) => native
+                .client
+                .rest(request, body)
+                .map_err(|e| e.map_client(ClientError::Rest)),
+            Self::Transport(client) => {
+                if let Some(credentials) = &client.credentials {
+                    let (name, value) = credentials.header();
+                    request = request.header(name, value);
+                }
+                let request = request
+                    .body(body)
+                    .map_err(|e| ApiError::client(ClientError::Http(e)))?;
+
+                let response = client
+                    .transport
+                    .send(request)
+                    .map_err(|e| ApiError::client(ClientError::Transport(e)))?;
+
+                Ok(response.map(Bytes::from))
+            }
+            Self::Cached(client) => {
+                let is_get = request
+                    .method_ref()
+                    .map_or(true, |method| method == http::Method::GET);
+                let url = match request.uri_ref() {
+                    Some(uri) if is_get => uri.to_string(),
+                    _ => {
+                        let response = client.inner.rest(request, body)?;
+                        client.cache.record_api_call(&response);
+                        return Ok(response);
+                    }
+                };
+
+                if let Some(response) = client.cache.get(&url) {
+                    return Ok(response);
+                }
+                let response = client.inner.rest(request, body)?;
+                client.cache.record_api_call(&response);
+                if response.status().is_success() {
+                    client.cache.insert(url, &response);
+                }
+                Ok(response)
+            }
+        }
+    }
+}
@@ -0,0 +1,845 @@
+//! Composes this adapter with other Trustfall adapters, so a single query can span
+//! GitLab and, say, a crates.io or advisory database adapter.
+//!
+//! Each adapter's root edges are exposed under a prefix in the merged schema, and
+//! cross edges connect vertices of one adapter to vertices of another:
+//!
+//! ```ignore
+//! let (adapter, schema) = ComposedAdapter::builder()
+//!     .adapter("gitlab_", GitlabAdapter::new())
+//!     .adapter("crates_", CratesAdapter::new())
+//!     .cross_edge(
+//!         "ManifestDependency",
+//!         "crate: [Crate!]!",
+//!         |dependency, adapters| {
+//!             let name = adapters.property(dependency, "ManifestDependency", "name");
+//!             let parameters = btreemap! { Arc::from("name") => name };
+//!             adapters.starting_vertices("crates_Crate", &parameters)
+//!         },
+//!     )
+//!     .build()?;
+//! ```
+//!
+//! Composed adapters resolve vertices one at a time, through [`ComposableAdapter`].
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use trustfall::provider::{
+    resolve_coercion_with, resolve_neighbors_with, resolve_property_with, BasicAdapter,
+};
+use trustfall::Schema;
+use trustfall_core::interpreter::Typename;
+use trustfall_core::{
+    interpreter::{ContextIterator, ContextOutcomeIterator, VertexIterator},
+    ir::{EdgeParameters, FieldValue},
+};
+
+use crate::vertex::Vertex;
+use crate::GitlabAdapter;
+
+const ROOT_TYPE: &str = "RootSchemaQuery";
+
+/// An adapter that can resolve vertices one at a time, which is what composition requires.
+///
+/// For adapters built with the `resolve_*_with` helpers, implementing this is usually
+/// a matter of calling the same per-vertex closures directly.
+pub trait ComposableAdapter: 'static {
+    type Vertex: Typename + Clone + Debug + Send + Sync + 'static;
+
+    /// The adapter's schema, in GraphQL SDL.
+    fn schema_text(&self) -> String;
+
+    fn starting_vertices(
+        &self,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, Self::Vertex>;
+
+    fn property(&self, vertex: &Self::Vertex, type_name: &str, property_name: &str) -> FieldValue;
+
+    fn neighbors(
+        &self,
+        vertex: &Self::Vertex,
+        type_name: &str,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, Self::Vertex>;
+
+    fn can_coerce(&self, vertex: &Self::Vertex, type_name: &str, coerce_to_type: &str) -> bool;
+}
+
+impl ComposableAdapter for GitlabAdapter {
+    type Vertex = Vertex;
+
+    fn schema_text(&self) -> String {
+        GitlabAdapter::schema_text(self)
+    }
+
+    fn starting_vertices(
+        &self,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, Self::Vertex> {
+        self.resolve_starting_vertices(edge_name, parameters)
+    }
+
+    fn property(&self, vertex: &Vertex, type_name: &str, property_name: &str) -> FieldValue {
+        Self::property_resolver(type_name, property_name)(vertex)
+    }
+
+    fn neighbors(
+        &self,
+        vertex: &Vertex,
+        type_name: &str,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, Self::Vertex> {
+        self.neighbor_resolver(type_name, edge_name, parameters)(vertex)
+    }
+
+    fn can_coerce(&self, vertex: &Vertex, type_name: &str, coerce_to_type: &str) -> bool {
+        Self::coercion_resolver(type_name, coerce_to_type)(vertex)
+    }
+}
+
+trait AnyVertex: Any + Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + Debug + Send + Sync> AnyVertex for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A vertex from any of the composed adapters.
+#[derive(Debug, Clone)]
+pub struct ComposedVertex {
+    adapter: usize,
+    typename: &'static str,
+    inner: Arc<dyn AnyVertex>,
+}
+
+impl ComposedVertex {
+    fn new<V: Typename + Debug + Send + Sync + 'static>(adapter: usize, vertex: V) -> Self {
+        Self {
+            adapter,
+            typename: vertex.typename(),
+            inner: Arc::new(vertex),
+        }
+    }
+
+    /// The underlying vertex, if it came from an adapter with this vertex type.
+    pub fn downcast_ref<V: 'static>(&self) -> Option<&V> {
+        self.inner.as_any().downcast_ref()
+    }
+}
+
+impl Typename for ComposedVertex {
+    fn typename(&self) -> &'static str {
+        self.typename
+    }
+}
+
+/// Type-erased [`ComposableAdapter`], so adapters with different vertex types
+/// can live side by side.
+trait ErasedAdapter {
+    fn starting_vertices(
+        &self,
+        index: usize,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, ComposedVertex>;
+
+    fn property(&self, vertex: &ComposedVertex, type_name: &str, property_name: &str)
+        -> FieldValue;
+
+    fn neighbors(
+        &self,
+        vertex: &ComposedVertex,
+        type_name: &str,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, ComposedVertex>;
+
+    fn can_coerce(&self, vertex: &ComposedVertex, type_name: &str, coerce_to_type: &str) -> bool;
+}
+
+impl<A: ComposableAdapter> ErasedAdapter for A {
+    fn starting_vertices(
+        &self,
+        index: usize,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, ComposedVertex> {
+        Box::new(
+            ComposableAdapter::starting_vertices(self, edge_name, parameters)
+                .map(move |vertex| ComposedVertex::new(index, vertex)),
+        )
+    }
+
+    fn property(
+        &self,
+        vertex: &ComposedVertex,
+        type_name: &str,
+        property_name: &str,
+    ) -> FieldValue {
+        ComposableAdapter::property(self, downcast::<A>(vertex), type_name, property_name)
+    }
+
+    fn neighbors(
+        &self,
+        vertex: &ComposedVertex,
+        type_name: &str,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, ComposedVertex> {
+        let index = vertex.adapter;
+        Box::new(
+            ComposableAdapter::neighbors(
+                self,
+                downcast::<A>(vertex),
+                type_name,
+                edge_name,
+                parameters,
+            )
+            .map(move |neighbor| ComposedVertex::new(index, neighbor)),
+        )
+    }
+
+    fn can_coerce(&self, vertex: &ComposedVertex, type_name: &str, coerce_to_type: &str) -> bool {
+        ComposableAdapter::can_coerce(self, downcast::<A>(vertex), type_name, coerce_to_type)
+    }
+}
+
+fn downcast<A: ComposableAdapter>(vertex: &ComposedVertex) -> &A::Vertex {
+    vertex
+        .downcast_ref()
+        .expect("vertex was resolved by a different adapter than the one that produced it")
+}
+
+/// The composed adapters, as seen by cross edge resolvers.
+pub struct Adapters {
+    adapters: Vec<Box<dyn ErasedAdapter>>,
+    /// Prefixed root edge name -> (adapter index, the adapter's own root edge name)
+    root_edges: BTreeMap<String, (usize, String)>,
+}
+
+impl Adapters {
+    /// Resolves a root edge, by its prefixed name in the composed schema.
+    pub fn starting_vertices(
+        &self,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, ComposedVertex> {
+        let (index, original_name) = self
+            .root_edges
+            .get(edge_name)
+            .unwrap_or_else(|| unreachable!("unknown starting edge name: {}", edge_name));
+        self.adapters[*index].starting_vertices(*index, original_name, parameters)
+    }
+
+    pub fn property(
+        &self,
+        vertex: &ComposedVertex,
+        type_name: &str,
+        property_name: &str,
+    ) -> FieldValue {
+        if property_name == "__typename" {
+            return vertex.typename().into();
+        }
+        self.adapters[vertex.adapter].property(vertex, type_name, property_name)
+    }
+}
+
+pub type CrossEdgeResolver =
+    Rc<dyn Fn(&ComposedVertex, &Adapters) -> VertexIterator<'static, ComposedVertex>>;
+
+/// Serves queries against the merged schema of several adapters.
+pub struct ComposedAdapter {
+    adapters: Rc<Adapters>,
+    cross_edges: BTreeMap<(String, String), CrossEdgeResolver>,
+}
+
+impl Debug for ComposedAdapter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComposedAdapter")
+            .field("root_edges", &self.adapters.root_edges)
+            .field("cross_edges", &self.cross_edges.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ComposedAdapter {
+    pub fn builder() -> ComposedAdapterBuilder {
+        ComposedAdapterBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct ComposedAdapterBuilder {
+    parts: Vec<(String, String, Box<dyn ErasedAdapter>)>,
+    cross_edges: Vec<(String, String, CrossEdgeResolver)>,
+}
+
+impl ComposedAdapterBuilder {
+    /// Adds an adapter, whose root edges will be named `{prefix}{edge}` in the merged schema.
+    pub fn adapter(mut self, prefix: &str, adapter: impl ComposableAdapter) -> Self {
+        self.parts
+            .push((prefix.to_string(), adapter.schema_text(), Box::new(adapter)));
+        self
+    }
+
+    /// Adds an edge to `type_name`, which may point to a type of any composed adapter.
+    ///
+    /// `field_definition` is the edge's SDL, e.g. `advisories(severity: String): [Advisory!]!`.
+    pub fn cross_edge(
+        mut self,
+        type_name: &str,
+        field_definition: &str,
+        resolver: impl Fn(&ComposedVertex, &Adapters) -> VertexIterator<'static, ComposedVertex>
+            + 'static,
+    ) -> Self {
+        self.cross_edges.push((
+            type_name.to_string(),
+            field_definition.trim().to_string(),
+            Rc::new(resolver),
+        ));
+        self
+    }
+
+    pub fn build(self) -> Result<(ComposedAdapter, Schema), CompositionError> {
+        let mut adapters = Vec::new();
+        let mut root_edges = BTreeMap::new();
+        let mut root_fields = String::new();
+        let mut directives: BTreeMap<String, String> = BTreeMap::new();
+        let mut types: Vec<(String, String)> = Vec::new();
+
+        for (index, (prefix, schema_text, adapter)) in self.parts.into_iter().enumerate() {
+            let definitions = split_definitions(&schema_text);
+            let root_type = definitions
+                .iter()
+                .find_map(|d| match d.kind {
+                    DefinitionKind::Schema => root_query_type(&d.text),
+                    _ => None,
+                })
+                .unwrap_or_else(|| ROOT_TYPE.to_string());
+
+            for definition in definitions {
+                match definition.kind {
+                    DefinitionKind::Schema => {}
+                    DefinitionKind::Directive => {
+                        directives.entry(definition.name).or_insert(definition.text);
+                    }
+                    DefinitionKind::Type if definition.name == root_type => {
+                        let body = definition_body(&definition.text);
+                        for field in field_names(body) {
+                            root_edges.insert(format!("{prefix}{field}"), (index, field));
+                        }
+                        root_fields.push_str(&prefix_fields(body, &prefix));
+                        root_fields.push('\n');
+                    }
+                    DefinitionKind::Type => {
+                        // Identical definitions, like the shared `Repository` interface,
+                        // are merged. Anything else would make the schema ambiguous.
+                        match types.iter().find(|(name, _)| *name == definition.name) {
+                            Some((_, text)) if *text == definition.text => {}
+                            Some(_) => {
+                                return Err(CompositionError::DuplicateType(definition.name))
+                            }
+                            None => types.push((definition.name, definition.text)),
+                        }
+                    }
+                }
+            }
+
+            adapters.push(adapter);
+        }
+
+        let mut cross_edges = BTreeMap::new();
+        for (type_name, field_definition, resolver) in self.cross_edges {
+            let (_, text) = types
+                .iter_mut()
+                .find(|(name, _)| *name == type_name)
+                .ok_or_else(|| CompositionError::UnknownType(type_name.clone()))?;
+            let closing_brace = text.rfind('}').expect("type definition without a body");
+            text.insert_str(closing_brace, &format!("  {field_definition}\n"));
+
+            let edge_name = field_names(&field_definition)
+                .into_iter()
+                .next()
+                .ok_or_else(|| CompositionError::InvalidCrossEdge(field_definition.clone()))?;
+            cross_edges.insert((type_name, edge_name), resolver);
+        }
+
+        let mut schema_text = format!("schema {{\n  query: {ROOT_TYPE}\n}}\n\n");
+        for directive in directives.values() {
+            schema_text.push_str(directive);
+            schema_text.push('\n');
+        }
+        schema_text.push_str(&format!("\ntype {ROOT_TYPE} {{\n{root_fields}}}\n"));
+        for (_, text) in &types {
+            schema_text.push('\n');
+            schema_text.push_str(text);
+            schema_text.push('\n');
+        }
+
+        let schema = Schema::parse(&schema_text)
+            .map_err(|e| CompositionError::InvalidSchema(format!("{e:?}")))?;
+
+        let adapter = ComposedAdapter {
+            adapters: Rc::new(Adapters {
+                adapters,
+                root_edges,
+            }),
+            cross_edges,
+        };
+        Ok((adapter, schema))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompositionError {
+    /// Two adapters define different types with the same name.
+    DuplicateType(String),
+    /// A cross edge was added to a type that no adapter defines.
+    UnknownType(String),
+    InvalidCrossEdge(String),
+    InvalidSchema(String),
+}
+
+impl fmt::Display for CompositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateType(name) => write!(f, "type {name} is defined by several adapters"),
+            Self::UnknownType(name) => write!(f, "no adapter defines type {name}"),
+            Self::InvalidCrossEdge(field) => write!(f, "invalid cross edge definition: {field}"),
+            Self::InvalidSchema(e) => write!(f, "composed schema is invalid: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for CompositionError {}
+
+impl BasicAdapter<'static> for ComposedAdapter {
+    type Vertex = ComposedVertex;
+
+    fn resolve_starting_vertices(
+        &self,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, Self::Vertex> {
+        self.adapters.starting_vertices(edge_name, parameters)
+    }
+
+    fn resolve_property(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &str,
+        property_name: &str,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, FieldValue> {
+        let adapters = self.adapters.clone();
+        let type_name = type_name.to_string();
+        let property_name = property_name.to_string();
+        resolve_property_with(contexts, move |vertex| {
+            adapters.property(vertex, &type_name, &property_name)
+        })
+    }
+
+    fn resolve_neighbors(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &str,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, VertexIterator<'static, Self::Vertex>> {
+        let adapters = self.adapters.clone();
+
+        let key = (type_name.to_string(), edge_name.to_string());
+        if let Some(resolver) = self.cross_edges.get(&key).cloned() {
+            return resolve_neighbors_with(contexts, move |vertex| resolver(vertex, &adapters));
+        }
+
+        let (type_name, edge_name) = key;
+        let parameters = parameters.clone();
+        resolve_neighbors_with(contexts, move |vertex| {
+            adapters.adapters[vertex.adapter].neighbors(vertex, &type_name, &edge_name, &parameters)
+        })
+    }
+
+    fn resolve_coercion(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &str,
+        coerce_to_type: &str,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, bool> {
+        let adapters = self.adapters.clone();
+        let type_name = type_name.to_string();
+        let coerce_to_type = coerce_to_type.to_string();
+        resolve_coercion_with(contexts, move |vertex| {
+            adapters.adapters[vertex.adapter].can_coerce(vertex, &type_name, &coerce_to_type)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DefinitionKind {
+    Schema,
+    Directive,
+    /// Types, interfaces, enums, scalars, unions and input types.
+    Type,
+}
+
+#[derive(Debug, Clone)]
+struct Definition {
+    kind: DefinitionKind,
+    name: String,
+    text: String,
+}
+
+const DEFINITION_KEYWORDS: [&str; 8] = [
+    "schema",
+    "directive",
+    "type",
+    "interface",
+    "enum",
+    "scalar",
+    "union",
+    "input",
+];
+
+/// Splits SDL into its top-level definitions, keeping each definition's leading comments.
+fn split_definitions(sdl: &str) -> Vec<Definition> {
+    let mut chunks: Vec<String> = Vec::new();
+    let mut pending_comments = String::new();
+    let mut depth = 0i32;
+
+    for line in sdl.lines() {
+        let trimmed = line.trim();
+        let keyword = trimmed.split_whitespace().next().unwrap_or("");
+
+        if depth == 0 && DEFINITION_KEYWORDS.contains(&keyword) {
+            chunks.push(std::mem::take(&mut pending_comments));
+        }
+
+        if depth == 0 && (trimmed.starts_with('#') || trimmed.is_empty()) {
+            pending_comments.push_str(line);
+            pending_comments.push('\n');
+            continue;
+        }
+
+        if let Some(current) = chunks.last_mut() {
+            current.push_str(line);
+            current.push('\n');
+        }
+        depth += brace_delta(line);
+    }
+
+    chunks
+        .into_iter()
+        .filter_map(|text| {
+            let header = text
+                .lines()
+                .find(|line| !line.trim().is_empty() && !line.trim().starts_with('#'))?;
+            let mut words = header.split_whitespace();
+            let keyword = words.next()?;
+            let name = words
+                .next()
+                .unwrap_or("")
+                .trim_start_matches('@')
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .next()
+                .unwrap_or("")
+                .to_string();
+
+            let kind = match keyword {
+                "schema" => DefinitionKind::Schema,
+                "directive" => DefinitionKind::Directive,
+                _ => DefinitionKind::Type,
+            };
+            Some(Definition {
+                kind,
+                name,
+                text: text.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Net change in `{}`/`()` nesting on a line, ignoring comments and strings.
+fn brace_delta(line: &str) -> i32 {
+    let mut delta = 0;
+    let mut in_string = false;
+    for c in line.chars() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => break,
+            '{' | '(' if !in_string => delta += 1,
+            '}' | ')' if !in_string => delta -= 1,
+            _ => {}
+        }
+    }
+    delta
+}
+
+fn root_query_type(schema_definition: &str) -> Option<String> {
+    let after = schema_definition.split("query").nth(1)?;
+    let name = after.trim_start().strip_prefix(':')?.trim_start();
+    let end = name
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(name.len());
+    Some(name[..end].to_string())
+}
+
+fn definition_body(definition: &str) -> &str {
+    let start = definition.find('{').map(|i| i + 1).unwrap_or(0);
+    let end = definition.rfind('}').unwrap_or(definition.len());
+    &definition[start..end]
+}
+
+/// Byte offsets and names of the fields defined directly in a type's body.
+///
+/// A field name is an identifier outside any argument list that is followed by `(` or `:`,
+/// which tells it apart from type names and directives.
+fn field_positions(body: &str) -> Vec<(usize, String)> {
+    let mut positions = Vec::new();
+    let mut depth = 0;
+    let mut in_comment = false;
+    let mut previous_significant = ' ';
+    let mut chars = body.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if in_comment {
+            in_comment = c != '\n';
+            continue;
+        }
+        match c {
+            '#' => in_comment = true,
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if depth == 0 && (c.is_alphabetic() || c == '_') => {
+                let mut end = i + c.len_utf8();
+                while let Some(&(j, next)) = chars.peek() {
+                    if next.is_alphanumeric() || next == '_' {
+                        end = j + next.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let followed_by = body[end..].trim_start().chars().next();
+                if previous_significant != '@'
+                    && previous_significant != ':'
+                    && previous_significant != '['
+                    && matches!(followed_by, Some('(') | Some(':'))
+                {
+                    positions.push((i, body[i..end].to_string()));
+                }
+                previous_significant = 'a';
+                continue;
+            }
+            _ => {}
+        }
+        if !c.is_whitespace() {
+            previous_significant = c;
+        }
+    }
+
+    positions
+}
+
+fn field_names(body: &str) -> Vec<String> {
+    field_positions(body)
+        .into_iter()
+        .map(|(_, name)| name)
+        .collect()
+}
+
+fn prefix_fields(body: &str, prefix: &str) -> String {
+    let mut prefixed = body.to_string();
+    for (offset, _) in field_positions(body).into_iter().rev() {
+        prefixed.insert_str(offset, prefix);
+    }
+    prefixed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Enough of an adapter to compose schemas.
+    struct SchemaOnly(&'static str);
+
+    impl ComposableAdapter for SchemaOnly {
+        type Vertex = Vertex;
+
+        fn schema_text(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn starting_vertices(
+            &self,
+            _edge_name: &str,
+            _parameters: &EdgeParameters,
+        ) -> VertexIterator<'static, Vertex> {
+            Box::new(std::iter::empty())
+        }
+
+        fn property(&self, _vertex: &Vertex, _type_name: &str, _property_name: &str) -> FieldValue {
+            FieldValue::Null
+        }
+
+        fn neighbors(
+            &self,
+            _vertex: &Vertex,
+            _type_name: &str,
+            _edge_name: &str,
+            _parameters: &EdgeParameters,
+        ) -> VertexIterator<'static, Vertex> {
+            Box::new(std::iter::empty())
+        }
+
+        fn can_coerce(&self, _vertex: &Vertex, _type_name: &str, _coerce_to_type: &str) -> bool {
+            false
+        }
+    }
+
+    const THINGS: &str = "
+schema {
+  query: RootA
+}
+
+type RootA {
+  things(limit: Int): [Thing!]!
+}
+
+interface Named {
+  name: String!
+}
+
+type Thing implements Named {
+  name: String!
+}
+";
+
+    #[test]
+    fn braces_in_comments_do_not_split_definitions() {
+        let definitions = split_definitions(
+            "# a { in a leading comment
+type A {
+  # a closing } in a field comment
+  x: Int # and ) here
+}
+
+type B {
+  y: String
+}
+",
+        );
+        let names: Vec<&str> = definitions.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["A", "B"]);
+        assert!(definitions[0]
+            .text
+            .starts_with("# a { in a leading comment\ntype A {"));
+        assert!(definitions[0].text.ends_with("x: Int # and ) here\n}"));
+    }
+
+    #[test]
+    fn implements_lists_are_not_part_of_the_name_or_fields() {
+        let definitions = split_definitions(
+            "type Repo implements Repository & Named {
+  name: String!
+  files(path: String = \"a: b\"): [File!]! @deprecated
+}
+",
+        );
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].kind, DefinitionKind::Type);
+        assert_eq!(definitions[0].name, "Repo");
+        assert_eq!(
+            field_names(definition_body(&definitions[0].text)),
+            ["name", "files"]
+        );
+    }
+
+    #[test]
+    fn schema_and_directive_definitions() {
+        let definitions = split_definitions(
+            "schema {
+  query: Root
+}
+
+directive @output(name: String) on FIELD
+",
+        );
+        assert_eq!(definitions[0].kind, DefinitionKind::Schema);
+        assert_eq!(
+            root_query_type(&definitions[0].text),
+            Some("Root".to_string())
+        );
+        assert_eq!(definitions[1].kind, DefinitionKind::Directive);
+        assert_eq!(definitions[1].name, "output");
+    }
+
+    #[test]
+    fn root_fields_are_prefixed() {
+        assert_eq!(
+            prefix_fields(
+                "\n  # one: per line\n  repos(limit: Int): [Repo!]!\n",
+                "gitlab_"
+            ),
+            "\n  # one: per line\n  gitlab_repos(limit: Int): [Repo!]!\n"
+        );
+    }
+
+    #[test]
+    fn identical_types_are_merged() {
+        let (adapter, _) = ComposedAdapter::builder()
+            .adapter("a_", SchemaOnly(THINGS))
+            .adapter(
+                "b_",
+                SchemaOnly(
+                    "
+type RootSchemaQuery {
+  others: [Named!]!
+}
+
+interface Named {
+  name: String!
+}
+",
+                ),
+            )
+            .build()
+            .unwrap();
+        let root_edges: Vec<&String> = adapter.adapters.root_edges.keys().collect();
+        assert_eq!(root_edges, ["a_things", "b_others"]);
+    }
+
+    #[test]
+    fn different_types_with_one_name_conflict() {
+        let error = ComposedAdapter::builder()
+            .adapter("a_", SchemaOnly(THINGS))
+            .adapter(
+                "b_",
+                SchemaOnly(
+                    "
+type RootSchemaQuery {
+  others: [Named!]!
+}
+
+interface Named {
+  name: String
+}
+",
+                ),
+            )
+            .build()
+            .unwrap_err();
+        assert_eq!(error, CompositionError::DuplicateType("Named".to_string()));
+    }
+}
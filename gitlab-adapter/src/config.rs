@@ -0,0 +1,470 @@
+//! Client configuration: which GitLab instance to query, and how to authenticate.
+//!
+//! [`ClientConfig::load`] reads an optional TOML config file, then applies environment
+//! variable overrides. The file is read from `$GITLAB_CONFIG`, or from
+//! `~/.config/trustfall-gitlab/config.toml` if that exists:
+//!
+//! ```toml
+//! host = "gitlab.example.com"
+//!
+//! [auth]
+//! # "private" (personal, project or group access tokens, the default), "oauth" or "job"
+//! kind = "oauth"
+//! # one of `token`, `token-env`, `token-file`, or `keyring-service` and `keyring-user`
+//! token-file = "~/.config/trustfall-gitlab/token"
+//!
+//! [proxy]
+//! url = "http://proxy.example.com:3128"
+//! no-proxy = "localhost,.internal.example.com"
+//!
+//! [tls]
+//! ca-bundle = "/etc/ssl/certs/corporate-ca.pem"
+//! ```
+//!
+//! Environment variables take precedence over the file:
+//!
+//! - `GITLAB_HOST`, falling back to `CI_SERVER_HOST` inside GitLab CI
+//! - `GITLAB_API_TOKEN` or `GITLAB_API_TOKEN_FILE`, for private tokens
+//! - `GITLAB_OAUTH_TOKEN`, for OAuth tokens
+//! - `CI_JOB_TOKEN`, used only when no other token is configured
+//! - `GITLAB_CA_BUNDLE`, for `tls.ca-bundle`
+//!
+//! Without any token, the client is unauthenticated, which is enough to query public
+//! projects on public instances like gitlab.com.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use gitlab::{Gitlab, GitlabBuilder, GitlabError};
+use serde::Deserialize;
+
+use crate::client::{GitlabClient, NativeClient, TransportClient};
+use crate::reqwest_transport::ReqwestTransport;
+
+/// How a token authenticates requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenKind {
+    /// Personal, project or group access tokens.
+    Private,
+    #[serde(rename = "oauth")]
+    OAuth,
+    /// A CI job's `CI_JOB_TOKEN`, which can only access a few endpoints.
+    Job,
+}
+
+impl Default for TokenKind {
+    fn default() -> Self {
+        Self::Private
+    }
+}
+
+/// Where to read a token from. Tokens are read when the client is built, not when
+/// the config is loaded, so they don't linger in memory longer than needed.
+#[derive(Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    Value(String),
+    /// The name of an environment variable holding the token.
+    Env(String),
+    /// A file holding the token, ignoring surrounding whitespace.
+    File(PathBuf),
+    /// An entry in the platform's keyring, with the `keyring` feature.
+    #[cfg(feature = "keyring")]
+    Keyring {
+        service: String,
+        user: String,
+    },
+}
+
+impl fmt::Debug for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Value(_) => write!(f, "Value(..)"),
+            Self::Env(name) => f.debug_tuple("Env").field(name).finish(),
+            Self::File(path) => f.debug_tuple("File").field(path).finish(),
+            #[cfg(feature = "keyring")]
+            Self::Keyring { service, user } => f
+                .debug_struct("Keyring")
+                .field("service", service)
+                .field("user", user)
+                .finish(),
+        }
+    }
+}
+
+impl TokenSource {
+    pub fn read(&self) -> Result<String, ConfigError> {
+        match self {
+            Self::Value(token) => Ok(token.clone()),
+            Self::Env(name) => {
+                std::env::var(name).map_err(|_| ConfigError::MissingEnv(name.clone()))
+            }
+            Self::File(path) => fs::read_to_string(path)
+                .map(|token| token.trim().to_string())
+                .map_err(|e| ConfigError::Io(path.clone(), e)),
+            #[cfg(feature = "keyring")]
+            Self::Keyring { service, user } => keyring::Entry::new(service, user)
+                .and_then(|entry| entry.get_password())
+                .map_err(|e| ConfigError::Keyring(e.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Auth {
+    pub kind: TokenKind,
+    pub source: TokenSource,
+}
+
+impl Auth {
+    pub fn new(kind: TokenKind, source: TokenSource) -> Self {
+        Self { kind, source }
+    }
+
+    pub fn credentials(&self) -> Result<Credentials, ConfigError> {
+        Ok(Credentials {
+            kind: self.kind,
+            token: self.source.read()?,
+        })
+    }
+}
+
+/// A token that has been read from its [`TokenSource`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub kind: TokenKind,
+    pub token: String,
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Credentials {
+    /// The header GitLab expects this kind of token in, and its value.
+    pub fn header(&self) -> (&'static str, String) {
+        match self.kind {
+            TokenKind::Private => ("PRIVATE-TOKEN", self.token.clone()),
+            TokenKind::OAuth => ("Authorization", format!("Bearer {}", self.token)),
+            TokenKind::Job => ("JOB-TOKEN", self.token.clone()),
+        }
+    }
+}
+
+/// An explicit proxy for all requests. `HTTPS_PROXY` and friends are honored without one.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ProxyConfig {
+    pub url: String,
+    /// Comma-separated hosts and domains to connect to directly, like `NO_PROXY`.
+    pub no_proxy: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TlsConfig {
+    /// PEM file of additional root certificates to trust, e.g. a corporate CA.
+    pub ca_bundle: Option<PathBuf>,
+    /// Accepts any certificate. Only meant for testing against self-signed instances.
+    #[serde(default)]
+    pub insecure: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    /// The GitLab instance's hostname, e.g. `gitlab.com`.
+    pub host: String,
+    pub auth: Option<Auth>,
+    pub proxy: Option<ProxyConfig>,
+    pub tls: TlsConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    host: Option<String>,
+    auth: Option<AuthSection>,
+    proxy: Option<ProxyConfig>,
+    #[serde(default)]
+    tls: TlsConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct AuthSection {
+    #[serde(default)]
+    kind: TokenKind,
+    token: Option<String>,
+    token_env: Option<String>,
+    token_file: Option<PathBuf>,
+    keyring_service: Option<String>,
+    keyring_user: Option<String>,
+}
+
+impl AuthSection {
+    fn into_auth(self) -> Result<Auth, ConfigError> {
+        let kind = self.kind;
+        let source = match self {
+            Self {
+                token: Some(token), ..
+            } => TokenSource::Value(token),
+            Self {
+                token_env: Some(name),
+                ..
+            } => TokenSource::Env(name),
+            Self {
+                token_file: Some(path),
+                ..
+            } => TokenSource::File(expand_home(&path)),
+            #[cfg(feature = "keyring")]
+            Self {
+                keyring_service: Some(service),
+                keyring_user: Some(user),
+                ..
+            } => TokenSource::Keyring { service, user },
+            #[cfg(not(feature = "keyring"))]
+            Self {
+                keyring_service: Some(_),
+                ..
+            }
+            | Self {
+                keyring_user: Some(_),
+                ..
+            } => {
+                return Err(ConfigError::Invalid(
+                    "keyring tokens require the `keyring` feature".to_string(),
+                ))
+            }
+            _ => {
+                return Err(ConfigError::Invalid(
+                    "[auth] needs one of `token`, `token-env`, `token-file`, \
+                    or `keyring-service` and `keyring-user`"
+                        .to_string(),
+                ))
+            }
+        };
+
+        Ok(Auth::new(kind, source))
+    }
+}
+
+impl ClientConfig {
+    pub fn new(host: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            auth: None,
+            proxy: None,
+            tls: TlsConfig::default(),
+        }
+    }
+
+    pub fn auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn ca_bundle(mut self, path: impl Into<PathBuf>) -> Self {
+        self.tls.ca_bundle = Some(path.into());
+        self
+    }
+
+    /// Accepts any certificate. Only meant for testing against self-signed instances.
+    pub fn danger_accept_invalid_certs(mut self) -> Self {
+        self.tls.insecure = true;
+        self
+    }
+
+    /// Loads the config file, if any, and applies environment variable overrides.
+    pub fn load() -> Result<Self, ConfigError> {
+        let file = match config_path() {
+            Some(path) => Self::read_file(&path)?,
+            None => ConfigFile::default(),
+        };
+
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        let host = env("GITLAB_HOST")
+            .or(file.host)
+            .or_else(|| env("CI_SERVER_HOST"))
+            .ok_or(ConfigError::MissingHost)?;
+
+        let auth = if env("GITLAB_API_TOKEN").is_some() {
+            Some(Auth::new(
+                TokenKind::Private,
+                TokenSource::Env("GITLAB_API_TOKEN".to_string()),
+            ))
+        } else if let Some(path) = env("GITLAB_API_TOKEN_FILE") {
+            Some(Auth::new(
+                TokenKind::Private,
+                TokenSource::File(path.into()),
+            ))
+        } else if env("GITLAB_OAUTH_TOKEN").is_some() {
+            Some(Auth::new(
+                TokenKind::OAuth,
+                TokenSource::Env("GITLAB_OAUTH_TOKEN".to_string()),
+            ))
+        } else if let Some(section) = file.auth {
+            Some(section.into_auth()?)
+        } else if env("CI_JOB_TOKEN").is_some() {
+            Some(Auth::new(
+                TokenKind::Job,
+                TokenSource::Env("CI_JOB_TOKEN".to_string()),
+            ))
+        } else {
+            None
+        };
+
+        let mut tls = file.tls;
+        if let Some(path) = env("GITLAB_CA_BUNDLE") {
+            tls.ca_bundle = Some(path.into());
+        }
+        tls.ca_bundle = tls.ca_bundle.map(|path| expand_home(&path));
+
+        Ok(Self {
+            host,
+            auth,
+            proxy: file.proxy,
+            tls,
+        })
+    }
+
+    fn read_file(path: &Path) -> Result<ConfigFile, ConfigError> {
+        let content =
+            fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+        toml::from_str(&content).map_err(|e| ConfigError::Invalid(e.to_string()))
+    }
+
+    /// Builds the client, reading the token from its source. Without `auth`, the client
+    /// is unauthenticated and can only read public data.
+    ///
+    /// The `gitlab` crate's own client is used where possible. It can't be given a proxy,
+    /// extra root certificates or a job token, so with those, requests go through a
+    /// [`ReqwestTransport`](crate::reqwest_transport::ReqwestTransport) instead.
+    pub fn build(&self) -> Result<GitlabClient, ConfigError> {
+        let job_token = matches!(&self.auth, Some(auth) if auth.kind == TokenKind::Job);
+        if self.proxy.is_some() || self.tls.ca_bundle.is_some() || job_token {
+            return self.build_transport();
+        }
+
+        Ok(GitlabClient::Native(NativeClient::new(self.clone())?))
+    }
+
+    fn build_transport(&self) -> Result<GitlabClient, ConfigError> {
+        let transport = ReqwestTransport::from_config(self)?;
+        let mut client = TransportClient::new(&self.host, None, transport)
+            .map_err(|e| ConfigError::Invalid(format!("invalid host {}: {e}", self.host)))?;
+        if let Some(auth) = &self.auth {
+            client = client.credentials(auth.credentials()?);
+        }
+
+        Ok(GitlabClient::Transport(client))
+    }
+
+    pub(crate) fn build_gitlab(&self) -> Result<Gitlab, ConfigError> {
+        let credentials = match &self.auth {
+            Some(auth) => Some(auth.credentials()?),
+            None => None,
+        };
+
+        let mut builder = match credentials {
+            None => GitlabBuilder::new_unauthenticated(&self.host),
+            Some(Credentials {
+                kind: TokenKind::Private,
+                token,
+            }) => GitlabBuilder::new(&self.host, token),
+            Some(Credentials {
+                kind: TokenKind::OAuth,
+                token,
+            }) => {
+                let mut builder = GitlabBuilder::new(&self.host, token);
+                builder.oauth2_token();
+                builder
+            }
+            Some(Credentials {
+                kind: TokenKind::Job,
+                ..
+            }) => {
+                let message = "job tokens are only sent by the transport client";
+                return Err(ConfigError::Invalid(message.to_string()));
+            }
+        };
+        if self.tls.insecure {
+            builder.cert_insecure();
+        }
+
+        builder.build().map_err(ConfigError::Gitlab)
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("GITLAB_CONFIG") {
+        return Some(expand_home(Path::new(&path)));
+    }
+
+    let default = expand_home(Path::new("~/.config/trustfall-gitlab/config.toml"));
+    if default.exists() {
+        Some(default)
+    } else {
+        None
+    }
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    MissingHost,
+    /// A token's environment variable isn't set.
+    MissingEnv(String),
+    Io(PathBuf, io::Error),
+    Invalid(String),
+    #[cfg(feature = "keyring")]
+    Keyring(String),
+    Gitlab(GitlabError),
+    HttpClient(reqwest::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHost => write!(
+                f,
+                "no GitLab host configured, set GITLAB_HOST or `host` in the config file"
+            ),
+            Self::MissingEnv(name) => write!(f, "environment variable {name} is not set"),
+            Self::Io(path, e) => write!(f, "failed to read {}: {e}", path.display()),
+            Self::Invalid(message) => write!(f, "invalid config: {message}"),
+            #[cfg(feature = "keyring")]
+            Self::Keyring(message) => write!(f, "failed to read token from keyring: {message}"),
+            Self::Gitlab(e) => write!(f, "failed to initialize the GitLab client: {e}"),
+            Self::HttpClient(e) => write!(f, "failed to initialize the HTTP client: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, e) => Some(e),
+            Self::Gitlab(e) => Some(e),
+            Self::HttpClient(e) => Some(e),
+            _ => None,
+        }
+    }
+}
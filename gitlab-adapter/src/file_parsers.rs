@@ -0,0 +1,132 @@
+//! Custom file formats, for organization-specific files like internal service descriptors.
+//!
+//! Implement [`FileParser`] and register it on the adapter with
+//! [`GitlabAdapter::with_file_parsers`](crate::GitlabAdapter::with_file_parsers).
+//! Files it matches are served as the `ParsedFile` subtype of `RepoFile`, with the
+//! parser's `format` and the properties it parsed out:
+//!
+//! ```graphql
+//! files(ref: "main") {
+//!   ... on ParsedFile {
+//!     format @filter(op: "=", value: ["$format"])
+//!     owner @output
+//!   }
+//! }
+//! ```
+//!
+//! Each parser declares the fields it adds to `ParsedFile`, so the schema to run queries
+//! against is [`FileParsers::schema`] rather than [`crate::schema`]. Properties a file's
+//! parser didn't produce, including other parsers' fields, are `null`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use trustfall::Schema;
+use trustfall_core::ir::FieldValue;
+
+use crate::vertex::{ParsedFile, RepoFile};
+
+pub type ParseError = Box<dyn Error + Send + Sync>;
+
+pub trait FileParser: Send + Sync {
+    /// Identifies the files' format in `ParsedFile.format`, e.g. `service-descriptor`.
+    fn format(&self) -> &str;
+
+    /// Whether this parser handles the file at `path`, relative to the repository root.
+    fn matches(&self, path: &str) -> bool;
+
+    /// The fields this parser adds to `ParsedFile`, as GraphQL field definitions,
+    /// e.g. `owner: String`. All of them must be nullable.
+    fn fields(&self) -> &str;
+
+    /// Parses the file's content into properties, keyed by field name.
+    fn parse(&self, content: &str) -> Result<BTreeMap<String, FieldValue>, ParseError>;
+}
+
+/// The registered parsers, tried in registration order before the built-in manifest
+/// parsers, so organizations can also override how those are handled.
+#[derive(Clone, Default)]
+pub struct FileParsers {
+    parsers: Vec<Arc<dyn FileParser>>,
+}
+
+impl fmt::Debug for FileParsers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.parsers.iter().map(|parser| parser.format()))
+            .finish()
+    }
+}
+
+impl FileParsers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, parser: impl FileParser + 'static) -> Self {
+        self.parsers.push(Arc::new(parser));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parsers.is_empty()
+    }
+
+    pub(crate) fn matches(&self, path: &str) -> bool {
+        self.parsers.iter().any(|parser| parser.matches(path))
+    }
+
+    /// `None` if no parser matches. Files that fail to parse are reported and stay
+    /// plain `RepoFile`s, like malformed manifests.
+    pub(crate) fn parse(&self, file: &RepoFile) -> Option<ParsedFile> {
+        let parser = self
+            .parsers
+            .iter()
+            .find(|parser| parser.matches(&file.path))?;
+
+        match parser.parse(file.content.text()?) {
+            Ok(properties) => Some(ParsedFile {
+                file: file.clone(),
+                format: parser.format().to_string(),
+                properties,
+            }),
+            Err(e) => {
+                eprintln!(
+                    "Failed to parse {} as {}: {}",
+                    file.path,
+                    parser.format(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// The crate's schema, with every registered parser's fields added to `ParsedFile`.
+    pub fn schema_text(&self) -> String {
+        // Parsers for related formats may share fields; each may only be declared once.
+        let fields: BTreeSet<&str> = self
+            .parsers
+            .iter()
+            .flat_map(|parser| parser.fields().lines())
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        let mut extension = String::from("extend type ParsedFile {\n");
+        for field in fields {
+            extension.push_str("  ");
+            extension.push_str(field);
+            extension.push('\n');
+        }
+        extension.push('}');
+
+        crate::schema::merge(crate::schema::schema_text(), &extension)
+    }
+
+    pub fn schema(&self) -> Result<Schema, ParseError> {
+        Schema::parse(self.schema_text()).map_err(|e| format!("{e:?}").into())
+    }
+}
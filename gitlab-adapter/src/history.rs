@@ -0,0 +1,431 @@
+//! Git history served from local object databases, behind the `history` feature.
+//!
+//! Walking history over the REST API costs a request per page of commits and per blamed
+//! file, so repositories are instead cloned (bare, and optionally shallow) into a cache
+//! directory the first time they're queried, and fetched once per adapter after that.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chrono::{FixedOffset, TimeZone};
+use git2::build::RepoBuilder;
+use git2::{
+    BlameOptions, BranchType, Cred, Delta, FetchOptions, Patch, RemoteCallbacks, Repository, Sort,
+};
+use trustfall_core::{
+    interpreter::VertexIterator,
+    ir::{EdgeParameters, FieldValue},
+};
+
+use crate::adapter::limit_param;
+use crate::vertex::{
+    BlameHunk, Branch, Commit, CommitSource, FileDiff, GitDir, GitlabRepo, Vertex,
+};
+
+/// Mirrors the remote's branches as local branches of the bare clone.
+const MIRROR_REFSPEC: &str = "+refs/heads/*:refs/heads/*";
+
+/// Where, and how deep, GitLab repositories are cloned for history queries.
+#[derive(Debug)]
+pub struct HistoryCache {
+    dir: PathBuf,
+    depth: Option<i32>,
+    fetched: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl Default for HistoryCache {
+    fn default() -> Self {
+        Self::new(std::env::temp_dir().join("trustfall-gitlab-adapter"))
+    }
+}
+
+impl HistoryCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            depth: None,
+            fetched: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// Only clone and fetch the latest `depth` commits of each branch.
+    pub fn depth(mut self, depth: i32) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    pub(crate) fn open(
+        &self,
+        repo: &GitlabRepo,
+        token: Option<String>,
+    ) -> Result<Repository, git2::Error> {
+        let path = self.dir.join(format!("{}.git", repo.id));
+
+        let repository = if path.exists() {
+            let repository = Repository::open_bare(&path)?;
+            if !self.fetched.lock().unwrap().contains(&path) {
+                repository.find_remote("origin")?.fetch(
+                    &[MIRROR_REFSPEC],
+                    Some(&mut self.fetch_options(token)),
+                    None,
+                )?;
+            }
+            repository
+        } else {
+            RepoBuilder::new()
+                .bare(true)
+                .fetch_options(self.fetch_options(token))
+                .remote_create(|repository, name, url| {
+                    repository.remote_with_fetch(name, url, MIRROR_REFSPEC)
+                })
+                .clone(&repo.url, &path)?
+        };
+
+        self.fetched.lock().unwrap().insert(path);
+        Ok(repository)
+    }
+
+    fn fetch_options(&self, token: Option<String>) -> FetchOptions<'static> {
+        let mut callbacks = RemoteCallbacks::new();
+        if let Some(token) = token {
+            callbacks.credentials(move |_, _, _| Cred::userpass_plaintext("oauth2", &token));
+        }
+
+        let mut options = FetchOptions::new();
+        options.remote_callbacks(callbacks);
+        if let Some(depth) = self.depth {
+            options.depth(depth);
+        }
+        options
+    }
+}
+
+/// The history edges of a repository, with their parameters.
+#[derive(Debug, Clone)]
+pub(crate) enum RepoEdge {
+    Commits {
+        ref_: Option<String>,
+        limit: Option<usize>,
+    },
+    Branches,
+    Blame {
+        path: String,
+        ref_: Option<String>,
+    },
+    /// An edge of the `RepoFile`s in the repository rather than of the repository.
+    LastCommit {
+        path: String,
+        ref_: Option<String>,
+    },
+}
+
+impl RepoEdge {
+    pub(crate) fn new(edge_name: &str, parameters: &EdgeParameters) -> Self {
+        let ref_ = match parameters.get("ref") {
+            Some(FieldValue::String(ref_)) => Some(ref_.clone()),
+            _ => None,
+        };
+
+        match edge_name {
+            "commits" => Self::Commits {
+                ref_,
+                limit: limit_param(parameters).ok().flatten(),
+            },
+            "branches" => Self::Branches,
+            "blame" => Self::Blame {
+                path: match parameters.get("path") {
+                    Some(FieldValue::String(path)) => path.clone(),
+                    _ => unreachable!(),
+                },
+                ref_,
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    /// Fills in `ref` with `default_ref` where it wasn't given, so history is read from
+    /// the branch the repository says is its default rather than from the clone's `HEAD`.
+    pub(crate) fn or_ref(&self, default_ref: Option<&str>) -> Self {
+        let mut edge = self.clone();
+        if let Self::Commits { ref_, .. }
+        | Self::Blame { ref_, .. }
+        | Self::LastCommit { ref_, .. } = &mut edge
+        {
+            if ref_.is_none() {
+                *ref_ = default_ref.map(str::to_string);
+            }
+        }
+        edge
+    }
+
+    /// Failing to open the repository, like failing to list its files, yields no vertices.
+    /// `project_id` is the GitLab project the repository is a clone of, if any.
+    pub(crate) fn resolve(
+        &self,
+        repository: Result<Repository, git2::Error>,
+        project_id: Option<&str>,
+    ) -> VertexIterator<'static, Vertex> {
+        let vertices = repository.and_then(|repository| {
+            let git_dir = Arc::new(GitDir {
+                path: repository.path().to_path_buf(),
+                project_id: project_id.map(str::to_string),
+            });
+
+            match self {
+                Self::Commits { ref_, limit } => {
+                    commits(&repository, &git_dir, ref_.as_deref(), *limit)
+                }
+                Self::Branches => branches(&repository, &git_dir),
+                Self::Blame { path, ref_ } => blame(&repository, &git_dir, path, ref_.as_deref()),
+                Self::LastCommit { path, ref_ } => {
+                    last_commit(&repository, &git_dir, path, ref_.as_deref())
+                }
+            }
+        });
+
+        match vertices {
+            Ok(vertices) => Box::new(vertices.into_iter()),
+            Err(e) => {
+                eprintln!("Failed to read git history: {}", e);
+                Box::new(std::iter::empty())
+            }
+        }
+    }
+}
+
+fn resolve_commit<'a>(
+    repository: &'a Repository,
+    ref_: Option<&str>,
+) -> Result<git2::Commit<'a>, git2::Error> {
+    repository
+        .revparse_single(ref_.unwrap_or("HEAD"))?
+        .peel_to_commit()
+}
+
+fn commits(
+    repository: &Repository,
+    git_dir: &Arc<GitDir>,
+    ref_: Option<&str>,
+    limit: Option<usize>,
+) -> Result<Vec<Vertex>, git2::Error> {
+    let mut revwalk = repository.revwalk()?;
+    revwalk.set_sorting(Sort::TIME)?;
+    revwalk.push(resolve_commit(repository, ref_)?.id())?;
+
+    revwalk
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|oid| {
+            let commit = repository.find_commit(oid?)?;
+            Ok(commit_vertex(&commit, git_dir))
+        })
+        .collect()
+}
+
+fn branches(repository: &Repository, git_dir: &Arc<GitDir>) -> Result<Vec<Vertex>, git2::Error> {
+    repository
+        .branches(Some(BranchType::Local))?
+        .map(|branch| {
+            let (branch, _) = branch?;
+            let name = String::from_utf8_lossy(branch.name_bytes()?).to_string();
+            let head = branch.get().peel_to_commit()?.id().to_string();
+
+            Ok(Vertex::Branch(Arc::new(Branch {
+                name,
+                head,
+                git_dir: git_dir.clone(),
+            })))
+        })
+        .collect()
+}
+
+fn blame(
+    repository: &Repository,
+    git_dir: &Arc<GitDir>,
+    path: &str,
+    ref_: Option<&str>,
+) -> Result<Vec<Vertex>, git2::Error> {
+    let mut options = BlameOptions::new();
+    options.newest_commit(resolve_commit(repository, ref_)?.id());
+    let blame = repository.blame_file(Path::new(path), Some(&mut options))?;
+
+    Ok(blame
+        .iter()
+        .map(|hunk| {
+            Vertex::BlameHunk(Arc::new(BlameHunk {
+                path: path.to_string(),
+                start_line: hunk.final_start_line() as i64,
+                line_count: hunk.lines_in_hunk() as i64,
+                commit: hunk.final_commit_id().to_string(),
+                git_dir: git_dir.clone(),
+            }))
+        })
+        .collect())
+}
+
+/// The newest commit reachable from `ref_` that changed `path`, like `git log -1 -- path`:
+/// renames aren't followed, and merges only count if the file differs from every parent.
+fn last_commit(
+    repository: &Repository,
+    git_dir: &Arc<GitDir>,
+    path: &str,
+    ref_: Option<&str>,
+) -> Result<Vec<Vertex>, git2::Error> {
+    let entry_id = |commit: &git2::Commit| -> Option<git2::Oid> {
+        let entry = commit.tree().ok()?.get_path(Path::new(path)).ok()?;
+        Some(entry.id())
+    };
+
+    let mut revwalk = repository.revwalk()?;
+    revwalk.set_sorting(Sort::TIME)?;
+    revwalk.push(resolve_commit(repository, ref_)?.id())?;
+
+    for oid in revwalk {
+        let commit = repository.find_commit(oid?)?;
+        let entry = entry_id(&commit);
+        // Parents missing from a shallow clone count as not having the file.
+        let unchanged = commit.parent_ids().any(|parent| {
+            let parent = repository.find_commit(parent).ok();
+            parent.and_then(|parent| entry_id(&parent)) == entry
+        });
+
+        if !unchanged {
+            return Ok(vec![commit_vertex(&commit, git_dir)]);
+        }
+    }
+    Ok(Vec::new())
+}
+
+fn commit_vertex(commit: &git2::Commit, git_dir: &Arc<GitDir>) -> Vertex {
+    let author = commit.author();
+    let committer = commit.committer();
+    let message = String::from_utf8_lossy(commit.message_bytes()).to_string();
+
+    Vertex::Commit(Arc::new(Commit {
+        hash: commit.id().to_string(),
+        summary: message.lines().next().unwrap_or_default().to_string(),
+        message,
+        author_name: String::from_utf8_lossy(author.name_bytes()).to_string(),
+        author_email: String::from_utf8_lossy(author.email_bytes()).to_string(),
+        authored_at: format_time(author.when()),
+        committer_name: String::from_utf8_lossy(committer.name_bytes()).to_string(),
+        committer_email: String::from_utf8_lossy(committer.email_bytes()).to_string(),
+        committed_at: format_time(committer.when()),
+        parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+        source: CommitSource::Git(git_dir.clone()),
+    }))
+}
+
+/// rfc3339, in the signature's own timezone.
+fn format_time(time: git2::Time) -> String {
+    FixedOffset::east_opt(time.offset_minutes() * 60)
+        .and_then(|offset| offset.timestamp_opt(time.seconds(), 0).single())
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Looks up commits by hash in the repository a vertex was read from. Commits missing
+/// from the object database, such as the parents of a shallow clone's oldest commits,
+/// are skipped.
+fn find_commits<'a>(
+    git_dir: &Arc<GitDir>,
+    hashes: impl Iterator<Item = &'a String>,
+) -> VertexIterator<'static, Vertex> {
+    let repository = match Repository::open(&git_dir.path) {
+        Ok(repository) => repository,
+        Err(e) => {
+            eprintln!("Failed to open {}: {}", git_dir.path.display(), e);
+            return Box::new(std::iter::empty());
+        }
+    };
+
+    let commits: Vec<Vertex> = hashes
+        .filter_map(|hash| {
+            let oid = git2::Oid::from_str(hash).ok()?;
+            let commit = repository.find_commit(oid).ok()?;
+            Some(commit_vertex(&commit, git_dir))
+        })
+        .collect();
+    Box::new(commits.into_iter())
+}
+
+/// Only for commits read from a clone; those from the API have their edges resolved
+/// by the adapter.
+pub(crate) fn commit_parents(vertex: &Vertex) -> VertexIterator<'static, Vertex> {
+    let commit = vertex.as_commit().unwrap();
+    match &commit.source {
+        CommitSource::Git(git_dir) => find_commits(git_dir, commit.parents.iter()),
+        CommitSource::Api { .. } => Box::new(std::iter::empty()),
+    }
+}
+
+pub(crate) fn branch_head(vertex: &Vertex) -> VertexIterator<'static, Vertex> {
+    let branch = vertex.as_branch().unwrap();
+    find_commits(&branch.git_dir, std::iter::once(&branch.head))
+}
+
+pub(crate) fn blame_hunk_commit(vertex: &Vertex) -> VertexIterator<'static, Vertex> {
+    let hunk = vertex.as_blame_hunk().unwrap();
+    find_commits(&hunk.git_dir, std::iter::once(&hunk.commit))
+}
+
+/// The files a commit changed, compared to its first parent, or all of its files for a
+/// root commit. Renames are detected like `git diff -M` does.
+pub(crate) fn commit_diff(vertex: &Vertex) -> VertexIterator<'static, Vertex> {
+    let commit = vertex.as_commit().unwrap();
+    let git_dir = match &commit.source {
+        CommitSource::Git(git_dir) => git_dir,
+        CommitSource::Api { .. } => return Box::new(std::iter::empty()),
+    };
+    match diff(git_dir, &commit.hash) {
+        Ok(vertices) => Box::new(vertices.into_iter()),
+        Err(e) => {
+            eprintln!("Failed to diff commit {}: {}", commit.hash, e);
+            Box::new(std::iter::empty())
+        }
+    }
+}
+
+fn diff(git_dir: &GitDir, hash: &str) -> Result<Vec<Vertex>, git2::Error> {
+    let repository = Repository::open(&git_dir.path)?;
+    let commit = repository.find_commit(git2::Oid::from_str(hash)?)?;
+    let parent_tree = match commit.parents().next() {
+        Some(parent) => Some(parent.tree()?),
+        None => None,
+    };
+
+    let mut diff =
+        repository.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+    diff.find_similar(None)?;
+
+    (0..diff.deltas().len())
+        .map(|index| {
+            let delta = diff.get_delta(index).unwrap();
+            let path = |file: git2::DiffFile| {
+                file.path()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            };
+            let status = match delta.status() {
+                Delta::Added => "added",
+                Delta::Deleted => "deleted",
+                Delta::Renamed => "renamed",
+                Delta::Typechange => "type_changed",
+                _ => "modified",
+            };
+            // Binary files have no lines to count.
+            let (_, additions, deletions) = match Patch::from_diff(&diff, index)? {
+                Some(patch) => patch.line_stats()?,
+                None => (0, 0, 0),
+            };
+
+            Ok(Vertex::FileDiff(Arc::new(FileDiff {
+                path: path(delta.new_file()),
+                old_path: (delta.status() == Delta::Renamed).then(|| path(delta.old_file())),
+                status: status.to_string(),
+                additions: additions as i64,
+                deletions: deletions as i64,
+            })))
+        })
+        .collect()
+}
@@ -0,0 +1,156 @@
+//! A [Trustfall](https://github.com/obi1kenobi/trustfall) adapter for the GitLab API.
+//!
+//! ```no_run
+//! use std::collections::BTreeMap;
+//! use std::rc::Rc;
+//!
+//! use trustfall::{execute_query, FieldValue};
+//! use trustfall_gitlab_adapter::{schema, GitlabAdapter};
+//!
+//! let query = r#"
+//! {
+//!   GitlabRepos(query: "trustfall") {
+//!     name @output
+//!   }
+//! }"#;
+//! let args: BTreeMap<&str, FieldValue> = BTreeMap::new();
+//!
+//! let adapter = Rc::new(GitlabAdapter::new());
+//! for row in execute_query(schema(), adapter, query, args).unwrap() {
+//!     println!("{row:?}");
+//! }
+//! ```
+//!
+//! By default, the client is configured from a config file and environment variables,
+//! see [`config`]. Private, OAuth and CI job tokens are supported. To send requests over a different HTTP stack, implement
+//! [`HttpTransport`] and use [`GitlabAdapter::with_client`]. Long-running processes can
+//! cache API responses with [`GitlabClient::cached`], see [`cache`].
+//!
+//! To query GitLab together with other data sources, see [`compose`]. [`LocalAdapter`]
+//! serves the same schema from local directories, for developing queries offline.
+//! Organization-specific file formats can be parsed with a [`file_parsers::FileParser`].
+//!
+//! ## Cargo features
+//!
+//! Repositories and their files are always available. Heavier parts of the schema are
+//! behind cargo features, all enabled by default, so embedders can opt out of compiling
+//! the parsers and resolvers they don't need:
+//!
+//! - `manifests`: dependency manifest `RepoFile` subtypes, e.g. `PythonRequirementsFile`
+//! - `ci`: CI/CD pipelines, jobs and runners
+//! - `operations`: environments, deployments, feature flags, error tracking, Terraform
+//!   states, Kubernetes agents and DORA metrics
+//! - `security`: project and personal access tokens
+//!
+//! The `history` feature, which is not enabled by default, adds commits, branches and
+//! blame, served from local clones of each repository through `libgit2`.
+
+#[macro_use]
+extern crate lazy_static;
+
+use trustfall::Schema;
+
+pub mod adapter;
+pub mod cache;
+pub mod client;
+pub mod compose;
+pub mod config;
+pub mod file_parsers;
+#[cfg(feature = "history")]
+pub mod history;
+pub mod local;
+pub mod redaction;
+#[cfg(feature = "ci")]
+mod reports;
+pub mod reqwest_transport;
+mod schema;
+pub mod vertex;
+
+pub use adapter::GitlabAdapter;
+pub use client::{GitlabClient, HttpTransport};
+pub use config::ClientConfig;
+pub use local::LocalAdapter;
+pub use vertex::Vertex;
+
+lazy_static! {
+    static ref SCHEMA: Schema = Schema::parse(&schema::schema_text()).unwrap();
+}
+
+/// Version of the schema served by this build, independent of the crate's version.
+///
+/// The major version is bumped when types, fields or edge parameters are removed or
+/// changed, and the minor version when they are added. Feature-gated parts of the
+/// schema are versioned as if all features were enabled.
+pub const SCHEMA_VERSION: &str = "1.75.0";
+
+// Vertices and adapters are thread-safe, so they can be used from multi-threaded executors.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Vertex>();
+    assert_send_sync::<GitlabAdapter>();
+    assert_send_sync::<LocalAdapter>();
+};
+
+/// The schema that [`GitlabAdapter`] serves queries for.
+pub fn schema() -> &'static Schema {
+    &SCHEMA
+}
+
+/// The schema served by this build, in GraphQL SDL.
+pub fn schema_text() -> String {
+    schema::schema_text()
+}
+
+/// Whether queries written against schema version `required` work with this build:
+/// the major versions must match, and `required` can't be newer than [`SCHEMA_VERSION`].
+pub fn is_schema_compatible(required: &str) -> bool {
+    fn parse(version: &str) -> Option<(u64, u64)> {
+        let mut parts = version.trim().split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().unwrap_or("0").parse().ok()?;
+        Some((major, minor))
+    }
+
+    match (parse(required), parse(SCHEMA_VERSION)) {
+        (Some((major, minor)), Some((our_major, our_minor))) => {
+            major == our_major && minor <= our_minor
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn our_minor() -> u64 {
+        SCHEMA_VERSION.split('.').nth(1).unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn missing_minor_version_is_zero() {
+        assert!(is_schema_compatible("1"));
+        assert!(is_schema_compatible("1.0"));
+        assert!(!is_schema_compatible("2"));
+    }
+
+    #[test]
+    fn patch_versions_are_ignored() {
+        assert!(is_schema_compatible(&format!("1.{}.999", our_minor())));
+        assert!(is_schema_compatible("1.0.0"));
+    }
+
+    #[test]
+    fn newer_minor_or_other_major_versions_are_incompatible() {
+        assert!(is_schema_compatible(SCHEMA_VERSION));
+        assert!(!is_schema_compatible(&format!("1.{}", our_minor() + 1)));
+        assert!(!is_schema_compatible("0.1"));
+    }
+
+    #[test]
+    fn garbage_is_incompatible() {
+        for version in ["", "latest", "v1", "1.x", "1.-1", "1..2"] {
+            assert!(!is_schema_compatible(version), "{version:?}");
+        }
+    }
+}
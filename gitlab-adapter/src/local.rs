@@ -0,0 +1,667 @@
+//! Serves the same repository and file schema as [`GitlabAdapter`] from local
+//! directories, so queries can be developed offline and then run against GitLab unmodified.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+use trustfall::provider::{
+    resolve_coercion_with, resolve_neighbors_with, resolve_property_with, BasicAdapter,
+};
+use trustfall_core::{
+    interpreter::{ContextIterator, ContextOutcomeIterator, VertexIterator},
+    ir::{EdgeParameters, FieldValue},
+};
+
+use crate::adapter::{self, NeighborResolver};
+use crate::compose::ComposableAdapter;
+use crate::file_parsers::FileParsers;
+#[cfg(feature = "history")]
+use crate::history::{self, RepoEdge};
+use crate::vertex::{Directory, FileContent, GitlabRepo, RepoFile, Vertex};
+use crate::GitlabAdapter;
+
+/// Each directory is served as one `GitlabRepo`.
+///
+/// Git checkouts resolve the `files` edge at the given `ref` through the `git` CLI,
+/// so uncommitted changes aren't visible. Plain directories ignore `ref` and serve
+/// the files on disk. With the `history` feature, git checkouts' history is read
+/// in place rather than cloned.
+#[derive(Debug, Clone)]
+pub struct LocalAdapter {
+    repos: Vec<PathBuf>,
+    file_parsers: FileParsers,
+    redact_secrets: bool,
+}
+
+impl LocalAdapter {
+    pub fn new<P: Into<PathBuf>>(repos: impl IntoIterator<Item = P>) -> Self {
+        Self {
+            repos: repos.into_iter().map(Into::into).collect(),
+            file_parsers: FileParsers::default(),
+            redact_secrets: true,
+        }
+    }
+
+    /// See [`GitlabAdapter::with_secret_redaction`].
+    pub fn with_secret_redaction(mut self, enabled: bool) -> Self {
+        self.redact_secrets = enabled;
+        self
+    }
+
+    /// See [`GitlabAdapter::with_file_parsers`].
+    pub fn with_file_parsers(mut self, parsers: FileParsers) -> Self {
+        self.file_parsers = parsers;
+        self
+    }
+
+    /// Only the `query` parameter applies locally, as a case-insensitive match on
+    /// the directory name. The other `GitlabRepos` filters are ignored.
+    fn get_repos(&self, parameters: &EdgeParameters) -> VertexIterator<'static, Vertex> {
+        let query = match parameters.get("query") {
+            Some(FieldValue::String(query)) => Some(query.to_lowercase()),
+            _ => None,
+        };
+
+        let vertices: Vec<Vertex> = self
+            .repos
+            .iter()
+            .map(|dir| {
+                let dir = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+                let name = dir
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                GitlabRepo {
+                    id: dir.display().to_string(),
+                    url: format!("file://{}", dir.display()),
+                    description: String::new(),
+                    repo_files: Vec::new(),
+                    default_branch: current_branch(&dir),
+                    raw_json: None,
+                    project: None,
+                    name,
+                }
+            })
+            .filter(|repo| match &query {
+                Some(query) => repo.name.to_lowercase().contains(query),
+                None => true,
+            })
+            .map(Vertex::GitlabRepo)
+            .collect();
+
+        Box::new(vertices.into_iter())
+    }
+
+    fn get_files_for_repo(
+        repo: &Arc<GitlabRepo>,
+        parsers: &FileParsers,
+        redact_secrets: bool,
+        ref_: Option<String>,
+        path: Option<String>,
+        recursive: bool,
+    ) -> VertexIterator<'static, Vertex> {
+        let dir = Path::new(&repo.id);
+        let files = if dir.join(".git").exists() {
+            git_files(repo, ref_, path.as_deref(), recursive)
+        } else {
+            let root = match &path {
+                Some(path) => dir.join(path),
+                None => dir.to_path_buf(),
+            };
+            let mut files = Vec::new();
+            walk_files(repo, &root, recursive, &mut files).map(|_| files)
+        };
+
+        match files {
+            Ok(files) => {
+                let nodes: Vec<Vertex> = files
+                    .into_iter()
+                    .map(|file| GitlabAdapter::file_vertex(file, parsers, redact_secrets))
+                    .collect();
+                Box::new(nodes.into_iter())
+            }
+            Err(e) => {
+                eprintln!("Failed to get files for {}: {}", dir.display(), e);
+                Box::new(std::iter::empty())
+            }
+        }
+    }
+
+    fn get_directories(
+        repo: &Arc<GitlabRepo>,
+        ref_: &str,
+        path: Option<&str>,
+    ) -> VertexIterator<'static, Vertex> {
+        let dir = Path::new(&repo.id);
+        let paths = if dir.join(".git").exists() {
+            git_tree(dir, ref_, path, false).map(|entries| {
+                entries
+                    .into_iter()
+                    .filter(|entry| entry.kind == "tree")
+                    .map(|entry| entry.path)
+                    .collect()
+            })
+        } else {
+            sub_directories(dir, path)
+        };
+
+        match paths {
+            Ok(paths) => {
+                let vertices: Vec<Vertex> = paths
+                    .into_iter()
+                    .map(|path| {
+                        Vertex::Directory(
+                            Directory {
+                                repo: repo.clone(),
+                                ref_: ref_.to_string(),
+                                path,
+                            }
+                            .into(),
+                        )
+                    })
+                    .collect();
+                Box::new(vertices.into_iter())
+            }
+            Err(e) => {
+                eprintln!("Failed to get directories for {}: {}", dir.display(), e);
+                Box::new(std::iter::empty())
+            }
+        }
+    }
+
+    fn neighbor_resolver(
+        &self,
+        type_name: &str,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> NeighborResolver {
+        if let Err(e) = adapter::limit_param(parameters) {
+            eprintln!("Skipping {type_name}.{edge_name}: {e}");
+            return Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                Box::new(std::iter::empty())
+            });
+        }
+
+        match (type_name, edge_name) {
+            ("GitlabRepo" | "Repository", "files") => {
+                let ref_ = match parameters.get("ref") {
+                    Some(FieldValue::String(ref_)) => Some(ref_.clone()),
+                    _ => None,
+                };
+                let path = match parameters.get("path") {
+                    Some(FieldValue::String(path)) => Some(path.clone()),
+                    _ => None,
+                };
+                let recursive = recursive_param(parameters);
+                let limit = limit_param(parameters);
+
+                let parsers = self.file_parsers.clone();
+                let redact_secrets = self.redact_secrets;
+                Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                    let repo = Arc::new(vertex.as_gitlab_repo().unwrap().clone());
+                    let files = Self::get_files_for_repo(
+                        &repo,
+                        &parsers,
+                        redact_secrets,
+                        ref_.clone(),
+                        path.clone(),
+                        recursive,
+                    );
+                    Box::new(files.take(limit))
+                })
+            }
+            ("GitlabRepo", "file") => {
+                let path = match parameters.get("path") {
+                    Some(FieldValue::String(path)) => path.to_string(),
+                    _ => unreachable!(),
+                };
+                // The checked out branch is what the repository reports as its default.
+                let ref_ = match parameters.get("ref") {
+                    Some(FieldValue::String(ref_)) => ref_.to_string(),
+                    _ => "HEAD".to_string(),
+                };
+
+                let parsers = self.file_parsers.clone();
+                let redact_secrets = self.redact_secrets;
+                Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                    let repo = Arc::new(vertex.as_gitlab_repo().unwrap().clone());
+                    let file = read_file(Path::new(&repo.id), &ref_, &path).map(|content| {
+                        GitlabAdapter::file_vertex(
+                            RepoFile {
+                                path: path.clone(),
+                                content: FileContent::read(&content),
+                                blob_id: None,
+                                mode: None,
+                                repo: repo.clone(),
+                                ref_: Some(ref_.clone()),
+                            },
+                            &parsers,
+                            redact_secrets,
+                        )
+                    });
+                    Box::new(file.into_iter())
+                })
+            }
+            ("GitlabRepo", "directories") => {
+                let ref_ = match parameters.get("ref") {
+                    Some(FieldValue::String(ref_)) => ref_.to_string(),
+                    _ => "HEAD".to_string(),
+                };
+                let path = match parameters.get("path") {
+                    Some(FieldValue::String(path)) => Some(path.to_string()),
+                    _ => None,
+                };
+                let limit = limit_param(parameters);
+
+                Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                    let repo = Arc::new(vertex.as_gitlab_repo().unwrap().clone());
+                    let directories = Self::get_directories(&repo, &ref_, path.as_deref());
+                    Box::new(directories.take(limit))
+                })
+            }
+            ("Directory", "files") => {
+                let recursive = recursive_param(parameters);
+                let limit = limit_param(parameters);
+
+                let parsers = self.file_parsers.clone();
+                let redact_secrets = self.redact_secrets;
+                Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                    let directory = vertex.as_directory().unwrap();
+                    let files = Self::get_files_for_repo(
+                        &directory.repo,
+                        &parsers,
+                        redact_secrets,
+                        Some(directory.ref_.clone()),
+                        Some(directory.path.clone()),
+                        recursive,
+                    );
+                    Box::new(files.take(limit))
+                })
+            }
+            ("Directory", "directories") => {
+                let limit = limit_param(parameters);
+                Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                    let directory = vertex.as_directory().unwrap();
+                    let directories = Self::get_directories(
+                        &directory.repo,
+                        &directory.ref_,
+                        Some(&directory.path),
+                    );
+                    Box::new(directories.take(limit))
+                })
+            }
+            // Local checkouts have none of GitLab's project metadata.
+            (
+                "GitlabRepo",
+                "namespace" | "badges" | "owner" | "creator" | "pages_domains"
+                | "pages_deployments" | "integrations" | "boards" | "events" | "access_requests"
+                | "requirements",
+            ) => Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                Box::new(std::iter::empty())
+            }),
+            // Nor its CI/CD.
+            #[cfg(feature = "ci")]
+            (
+                "GitlabRepo",
+                "pipelines" | "latest_pipeline" | "merged_ci_config" | "pipeline_schedules"
+                | "runners" | "merge_trains",
+            ) => Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                Box::new(std::iter::empty())
+            }),
+            // Or where it's deployed.
+            #[cfg(feature = "operations")]
+            (
+                "GitlabRepo",
+                "dora_metrics"
+                | "environments"
+                | "protected_environments"
+                | "feature_flags"
+                | "error_tracking_issues"
+                | "terraform_states"
+                | "cluster_agents",
+            ) => Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                Box::new(std::iter::empty())
+            }),
+            // Includes are resolved by GitLab's CI lint.
+            #[cfg(feature = "ci")]
+            ("GitlabCiFile", "included_files") => {
+                Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                    Box::new(std::iter::empty())
+                })
+            }
+            #[cfg(feature = "ci")]
+            ("Commit", "pipelines") => Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                Box::new(std::iter::empty())
+            }),
+            #[cfg(feature = "manifests")]
+            ("PythonRequirementsFile" | "CargoManifestFile" | "NpmPackageFile", "dependencies") => {
+                Box::new(GitlabAdapter::manifest_dependencies)
+            }
+            #[cfg(feature = "history")]
+            ("GitlabRepo", "commits" | "branches" | "blame") => {
+                let edge = RepoEdge::new(edge_name, parameters);
+                Box::new(move |vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                    let repo = vertex.as_gitlab_repo().unwrap();
+                    edge.resolve(git2::Repository::open(&repo.id), None)
+                })
+            }
+            #[cfg(feature = "history")]
+            (_, "last_commit") => Box::new(|vertex: &Vertex| -> VertexIterator<'static, Vertex> {
+                let file = vertex.as_repo_file().unwrap();
+                let edge = RepoEdge::LastCommit {
+                    path: file.path.clone(),
+                    ref_: file.ref_.clone(),
+                };
+                edge.resolve(git2::Repository::open(&file.repo.id), None)
+            }),
+            #[cfg(feature = "history")]
+            ("Commit", "parents") => Box::new(history::commit_parents),
+            #[cfg(feature = "history")]
+            ("Commit", "diff") => Box::new(history::commit_diff),
+            #[cfg(feature = "history")]
+            ("Branch", "head") => Box::new(history::branch_head),
+            #[cfg(feature = "history")]
+            ("BlameHunk", "commit") => Box::new(history::blame_hunk_commit),
+            // Vertex types only GitLab's API produces never show up here,
+            // and any edge not listed above has nothing to resolve locally.
+            _ => Box::new(|_: &Vertex| -> VertexIterator<'static, Vertex> {
+                Box::new(std::iter::empty())
+            }),
+        }
+    }
+}
+
+/// The checked out branch of a git checkout, standing in for GitLab's default branch.
+fn current_branch(dir: &Path) -> Option<String> {
+    if !dir.join(".git").exists() {
+        return None;
+    }
+
+    let output = git_output(Command::new("git").arg("-C").arg(dir).args([
+        "symbolic-ref",
+        "--short",
+        "HEAD",
+    ]))
+    .ok()?;
+    Some(String::from_utf8_lossy(&output).trim().to_string())
+}
+
+/// Lists the blobs under `path` at `ref_` (`HEAD` if not given), or only those directly
+/// in it without `recursive`, and reads each one.
+fn git_files(
+    repo: &Arc<GitlabRepo>,
+    ref_: Option<String>,
+    path: Option<&str>,
+    recursive: bool,
+) -> Result<Vec<RepoFile>, String> {
+    let dir = Path::new(&repo.id);
+    git_tree(dir, ref_.as_deref().unwrap_or("HEAD"), path, recursive)?
+        .into_iter()
+        .filter(|entry| entry.kind == "blob")
+        .map(|entry| {
+            let content = git_output(
+                Command::new("git")
+                    .arg("-C")
+                    .arg(dir)
+                    .args(["cat-file", "blob"])
+                    .arg(&entry.id),
+            )?;
+
+            Ok(RepoFile {
+                path: entry.path,
+                content: FileContent::read(&content),
+                blob_id: Some(entry.id),
+                mode: Some(entry.mode),
+                repo: repo.clone(),
+                ref_: ref_.clone(),
+            })
+        })
+        .collect()
+}
+
+/// An entry of a git tree, with the fields of GitLab's tree listing.
+struct TreeEntry {
+    mode: String,
+    /// `blob`, `tree` or `commit` for submodules
+    kind: String,
+    id: String,
+    path: String,
+}
+
+/// The entries of the tree at `path`.
+fn git_tree(
+    dir: &Path,
+    ref_: &str,
+    path: Option<&str>,
+    recursive: bool,
+) -> Result<Vec<TreeEntry>, String> {
+    let mut ls_tree = Command::new("git");
+    ls_tree.arg("-C").arg(dir).args(["ls-tree", "-z"]);
+    if recursive {
+        ls_tree.arg("-r");
+    }
+    ls_tree.arg(ref_);
+    if let Some(path) = path {
+        // With a trailing `/`, the directory's entries are listed rather than itself.
+        ls_tree
+            .arg("--")
+            .arg(format!("{}/", path.trim_end_matches('/')));
+    }
+
+    let listing = git_output(&mut ls_tree)?;
+    Ok(String::from_utf8_lossy(&listing)
+        .split('\0')
+        .filter_map(|entry| {
+            // `<mode> <type> <object>\t<path>`
+            let (info, path) = entry.split_once('\t')?;
+            let mut info = info.split(' ');
+            Some(TreeEntry {
+                mode: info.next()?.to_string(),
+                kind: info.next()?.to_string(),
+                id: info.next()?.to_string(),
+                path: path.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// The content of the file at `path`, or `None` if there's none.
+fn read_file(dir: &Path, ref_: &str, path: &str) -> Option<Vec<u8>> {
+    if dir.join(".git").exists() {
+        git_output(
+            Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["cat-file", "blob"])
+                .arg(format!("{ref_}:{path}")),
+        )
+        .ok()
+    } else {
+        fs::read(dir.join(path)).ok()
+    }
+}
+
+fn git_output(command: &mut Command) -> Result<Vec<u8>, String> {
+    let output = command.output().map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Collects the files under `current`, or without `recursive` only those directly in it,
+/// with paths relative to `root` and `/`-separated like GitLab's.
+fn walk_files(
+    repo: &Arc<GitlabRepo>,
+    current: &Path,
+    recursive: bool,
+    files: &mut Vec<RepoFile>,
+) -> Result<(), String> {
+    if current.is_file() {
+        let content = fs::read(current).map_err(|e| e.to_string())?;
+        files.push(RepoFile {
+            path: relative_path(Path::new(&repo.id), current),
+            content: FileContent::read(&content),
+            blob_id: None,
+            mode: None,
+            repo: repo.clone(),
+            ref_: None,
+        });
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(current)
+        .map_err(|e| format!("{}: {}", current.display(), e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        if entry.file_name() == ".git" || (!recursive && entry.path().is_dir()) {
+            continue;
+        }
+        walk_files(repo, &entry.path(), recursive, files)?;
+    }
+    Ok(())
+}
+
+/// The directories directly in `path` of a plain directory, or in `dir` itself.
+fn sub_directories(dir: &Path, path: Option<&str>) -> Result<Vec<String>, String> {
+    let current = match path {
+        Some(path) => dir.join(path),
+        None => dir.to_path_buf(),
+    };
+
+    let mut directories: Vec<String> = fs::read_dir(&current)
+        .map_err(|e| format!("{}: {}", current.display(), e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|entry| entry.file_name() != ".git" && entry.path().is_dir())
+        .map(|entry| relative_path(dir, &entry.path()))
+        .collect();
+    directories.sort();
+    Ok(directories)
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// `recursive` defaults to true, like for [`GitlabAdapter`].
+fn recursive_param(parameters: &EdgeParameters) -> bool {
+    !matches!(
+        parameters.get("recursive"),
+        Some(FieldValue::Boolean(false))
+    )
+}
+
+/// Files on disk are cheap, so `limit` only caps the number of results.
+fn limit_param(parameters: &EdgeParameters) -> usize {
+    adapter::limit_param(parameters)
+        .ok()
+        .flatten()
+        .unwrap_or(usize::MAX)
+}
+
+impl BasicAdapter<'static> for LocalAdapter {
+    type Vertex = Vertex;
+
+    fn resolve_starting_vertices(
+        &self,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, Self::Vertex> {
+        if let Err(e) = adapter::limit_param(parameters) {
+            eprintln!("Skipping {edge_name}: {e}");
+            return Box::new(std::iter::empty());
+        }
+
+        match edge_name {
+            "GitlabRepos" => self.get_repos(parameters),
+            // Local checkouts can't be looked up by their GitLab path, id, group or user,
+            // or searched, and have no authenticated user, instance or other GitLab-only roots.
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn resolve_property(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &str,
+        property_name: &str,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, FieldValue> {
+        resolve_property_with(
+            contexts,
+            GitlabAdapter::property_resolver(type_name, property_name),
+        )
+    }
+
+    fn resolve_neighbors(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &str,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, VertexIterator<'static, Self::Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            self.neighbor_resolver(type_name, edge_name, parameters),
+        )
+    }
+
+    fn resolve_coercion(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &str,
+        coerce_to_type: &str,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, bool> {
+        resolve_coercion_with(
+            contexts,
+            GitlabAdapter::coercion_resolver(type_name, coerce_to_type),
+        )
+    }
+}
+
+impl ComposableAdapter for LocalAdapter {
+    type Vertex = Vertex;
+
+    fn schema_text(&self) -> String {
+        self.file_parsers.schema_text()
+    }
+
+    fn starting_vertices(
+        &self,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, Vertex> {
+        self.resolve_starting_vertices(edge_name, parameters)
+    }
+
+    fn property(&self, vertex: &Vertex, type_name: &str, property_name: &str) -> FieldValue {
+        GitlabAdapter::property_resolver(type_name, property_name)(vertex)
+    }
+
+    fn neighbors(
+        &self,
+        vertex: &Vertex,
+        type_name: &str,
+        edge_name: &str,
+        parameters: &EdgeParameters,
+    ) -> VertexIterator<'static, Vertex> {
+        self.neighbor_resolver(type_name, edge_name, parameters)(vertex)
+    }
+
+    fn can_coerce(&self, vertex: &Vertex, type_name: &str, coerce_to_type: &str) -> bool {
+        GitlabAdapter::coercion_resolver(type_name, coerce_to_type)(vertex)
+    }
+}
@@ -0,0 +1,255 @@
+//! Parsers for the report artifacts CI jobs upload: JUnit XML test results and
+//! Cobertura XML coverage.
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::vertex::{CoverageReport, TestCase};
+
+/// The test cases of a JUnit XML report, from any number of `<testsuite>`s. A case's
+/// `<failure>` or `<error>` message becomes its `system_output`, and the element's text
+/// its `stack_trace`.
+pub(crate) fn junit_test_cases(xml: &str) -> Result<Vec<TestCase>, String> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut cases = Vec::new();
+    let mut current: Option<TestCase> = None;
+    let mut in_failure = false;
+    loop {
+        let event = reader.read_event().map_err(|e| e.to_string())?;
+        match &event {
+            Event::Start(element) | Event::Empty(element) => {
+                let empty = matches!(event, Event::Empty(_));
+                match element.name().as_ref() {
+                    b"testcase" if empty => cases.push(test_case(element)?),
+                    b"testcase" => current = Some(test_case(element)?),
+                    b"failure" | b"error" => {
+                        if let Some(case) = current.as_mut() {
+                            fail(case, element)?;
+                            in_failure = !empty;
+                        }
+                    }
+                    b"skipped" => {
+                        if let Some(case) = current.as_mut() {
+                            case.status = "skipped".to_string();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(text) if in_failure => {
+                let text = text.unescape().map_err(|e| e.to_string())?;
+                append_stack_trace(current.as_mut(), &text);
+            }
+            Event::CData(text) if in_failure => {
+                append_stack_trace(current.as_mut(), &String::from_utf8_lossy(text));
+            }
+            Event::End(element) => match element.name().as_ref() {
+                b"testcase" => cases.extend(current.take()),
+                b"failure" | b"error" => in_failure = false,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(cases)
+}
+
+/// The totals of a Cobertura XML report.
+pub(crate) fn cobertura_report(path: &str, xml: &str) -> Result<CoverageReport, String> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(element) | Event::Empty(element)
+                if element.name().as_ref() == b"coverage" =>
+            {
+                let number = |name: &str| -> Result<Option<f64>, String> {
+                    Ok(attribute(&element, name)?.and_then(|value| value.parse().ok()))
+                };
+                return Ok(CoverageReport {
+                    path: path.to_string(),
+                    line_rate: number("line-rate")?,
+                    branch_rate: number("branch-rate")?,
+                    lines_covered: number("lines-covered")?.map(|n| n as i64),
+                    lines_valid: number("lines-valid")?.map(|n| n as i64),
+                    branches_covered: number("branches-covered")?.map(|n| n as i64),
+                    branches_valid: number("branches-valid")?.map(|n| n as i64),
+                });
+            }
+            Event::Eof => return Err("no <coverage> element".to_string()),
+            _ => {}
+        }
+    }
+}
+
+fn test_case(element: &BytesStart) -> Result<TestCase, String> {
+    Ok(TestCase {
+        name: attribute(element, "name")?.unwrap_or_default(),
+        classname: attribute(element, "classname")?.unwrap_or_default(),
+        status: "success".to_string(),
+        execution_time: attribute(element, "time")?
+            .and_then(|time| time.parse().ok())
+            .unwrap_or_default(),
+        system_output: None,
+        stack_trace: None,
+        file: attribute(element, "file")?,
+        raw_json: None,
+    })
+}
+
+fn fail(case: &mut TestCase, element: &BytesStart) -> Result<(), String> {
+    case.status = match element.name().as_ref() {
+        b"error" => "error",
+        _ => "failed",
+    }
+    .to_string();
+    case.system_output = attribute(element, "message")?;
+    Ok(())
+}
+
+fn append_stack_trace(case: Option<&mut TestCase>, text: &str) {
+    if let Some(case) = case {
+        case.stack_trace
+            .get_or_insert_with(String::new)
+            .push_str(text);
+    }
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, String> {
+    match element.try_get_attribute(name).map_err(|e| e.to_string())? {
+        Some(attribute) => {
+            let value = attribute.unescape_value().map_err(|e| e.to_string())?;
+            Ok(Some(value.to_string()))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JUNIT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="all">
+  <testsuite name="api">
+    <testsuite name="api.users">
+      <testcase classname="api.users" name="lists users" time="0.25" file="spec/users_spec.rb"/>
+      <testcase classname="api.users" name="creates users" time="1.5">
+        <failure message="expected 201, got 500">Failure/Error: expect(response).to have_http_status(:created)
+  spec/users_spec.rb:12</failure>
+      </testcase>
+    </testsuite>
+    <testcase classname="api" name="boots" time="0.1">
+      <error message="connection refused"><![CDATA[Errno::ECONNREFUSED <localhost:5432>]]></error>
+    </testcase>
+  </testsuite>
+  <testsuite name="ui">
+    <testcase classname="ui" name="renders the sidebar">
+      <skipped/>
+    </testcase>
+  </testsuite>
+</testsuites>"#;
+
+    fn case<'a>(cases: &'a [TestCase], name: &str) -> &'a TestCase {
+        cases.iter().find(|case| case.name == name).unwrap()
+    }
+
+    #[test]
+    fn junit_reads_cases_from_nested_testsuites() {
+        let cases = junit_test_cases(JUNIT).unwrap();
+        let names: Vec<&str> = cases.iter().map(|case| case.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "lists users",
+                "creates users",
+                "boots",
+                "renders the sidebar"
+            ]
+        );
+
+        let passed = case(&cases, "lists users");
+        assert_eq!(passed.status, "success");
+        assert_eq!(passed.classname, "api.users");
+        assert_eq!(passed.execution_time, 0.25);
+        assert_eq!(passed.file.as_deref(), Some("spec/users_spec.rb"));
+        assert_eq!(passed.system_output, None);
+        assert_eq!(passed.stack_trace, None);
+    }
+
+    #[test]
+    fn junit_failures_and_errors() {
+        let cases = junit_test_cases(JUNIT).unwrap();
+
+        let failed = case(&cases, "creates users");
+        assert_eq!(failed.status, "failed");
+        assert_eq!(
+            failed.system_output.as_deref(),
+            Some("expected 201, got 500")
+        );
+        assert_eq!(
+            failed.stack_trace.as_deref(),
+            Some(
+                "Failure/Error: expect(response).to have_http_status(:created)\n  \
+                 spec/users_spec.rb:12"
+            )
+        );
+
+        let errored = case(&cases, "boots");
+        assert_eq!(errored.status, "error");
+        assert_eq!(errored.system_output.as_deref(), Some("connection refused"));
+        assert_eq!(
+            errored.stack_trace.as_deref(),
+            Some("Errno::ECONNREFUSED <localhost:5432>")
+        );
+    }
+
+    #[test]
+    fn junit_skipped() {
+        let cases = junit_test_cases(JUNIT).unwrap();
+        let skipped = case(&cases, "renders the sidebar");
+        assert_eq!(skipped.status, "skipped");
+        assert_eq!(skipped.execution_time, 0.0);
+        assert_eq!(skipped.stack_trace, None);
+    }
+
+    #[test]
+    fn junit_rejects_malformed_xml() {
+        assert!(junit_test_cases("<testsuite><testcase name=\"a\"></testsuite>").is_err());
+    }
+
+    #[test]
+    fn cobertura_totals() {
+        let xml = r#"<?xml version="1.0" ?>
+<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">
+<coverage line-rate="0.8125" branch-rate="0.5" lines-covered="13" lines-valid="16"
+          branches-covered="2" branches-valid="4" complexity="0" version="7.2.7" timestamp="1700000000">
+  <packages>
+    <package name="app" line-rate="0.8125" branch-rate="0.5" complexity="0"/>
+  </packages>
+</coverage>"#;
+        let report = cobertura_report("coverage.xml", xml).unwrap();
+        assert_eq!(report.path, "coverage.xml");
+        assert_eq!(report.line_rate, Some(0.8125));
+        assert_eq!(report.branch_rate, Some(0.5));
+        assert_eq!(report.lines_covered, Some(13));
+        assert_eq!(report.lines_valid, Some(16));
+        assert_eq!(report.branches_covered, Some(2));
+        assert_eq!(report.branches_valid, Some(4));
+    }
+
+    #[test]
+    fn cobertura_without_totals() {
+        let report = cobertura_report("coverage.xml", r#"<coverage line-rate="1"/>"#).unwrap();
+        assert_eq!(report.line_rate, Some(1.0));
+        assert_eq!(report.branch_rate, None);
+        assert_eq!(report.lines_covered, None);
+    }
+
+    #[test]
+    fn cobertura_without_coverage_element() {
+        assert!(cobertura_report("coverage.xml", "<packages/>").is_err());
+    }
+}
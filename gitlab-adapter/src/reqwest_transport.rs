@@ -0,0 +1,89 @@
+//! An [`HttpTransport`] over a `reqwest` client, for configurations the `gitlab` crate's
+//! own client doesn't support, like explicit proxies and extra root certificates.
+
+use std::fs;
+
+use reqwest::blocking::Client;
+use reqwest::{Certificate, NoProxy, Proxy};
+
+use crate::client::{HttpTransport, TransportError};
+use crate::config::{ClientConfig, ConfigError};
+
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// Applies the config's proxy and TLS settings.
+    pub fn from_config(config: &ClientConfig) -> Result<Self, ConfigError> {
+        let mut builder = Client::builder();
+
+        if let Some(proxy) = &config.proxy {
+            let mut all = Proxy::all(&proxy.url).map_err(ConfigError::HttpClient)?;
+            if let Some(no_proxy) = &proxy.no_proxy {
+                all = all.no_proxy(NoProxy::from_string(no_proxy));
+            }
+            builder = builder.proxy(all);
+        }
+
+        if let Some(path) = &config.tls.ca_bundle {
+            let bundle = fs::read_to_string(path).map_err(|e| ConfigError::Io(path.clone(), e))?;
+            for pem in split_pem_bundle(&bundle) {
+                let certificate =
+                    Certificate::from_pem(pem.as_bytes()).map_err(ConfigError::HttpClient)?;
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        if config.tls.insecure {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        builder
+            .build()
+            .map(Self::new)
+            .map_err(ConfigError::HttpClient)
+    }
+}
+
+/// `Certificate::from_pem` only reads the first certificate of a bundle.
+fn split_pem_bundle(bundle: &str) -> Vec<String> {
+    const END: &str = "-----END CERTIFICATE-----";
+
+    bundle
+        .split_inclusive(END)
+        .filter(|pem| pem.contains(END))
+        .map(|pem| pem.trim().to_string())
+        .collect()
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(
+        &self,
+        request: http::Request<Vec<u8>>,
+    ) -> Result<http::Response<Vec<u8>>, TransportError> {
+        let (parts, body) = request.into_parts();
+
+        let response = self
+            .client
+            .request(parts.method, parts.uri.to_string())
+            .headers(parts.headers)
+            .body(body)
+            .send()
+            .map_err(|e| TransportError(Box::new(e)))?;
+
+        let mut builder = http::Response::builder().status(response.status());
+        for (name, value) in response.headers() {
+            builder = builder.header(name, value);
+        }
+        let body = response.bytes().map_err(|e| TransportError(Box::new(e)))?;
+
+        builder
+            .body(body.to_vec())
+            .map_err(|e| TransportError(Box::new(e)))
+    }
+}
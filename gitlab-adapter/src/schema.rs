@@ -0,0 +1,88 @@
+//! Assembles the schema text from the base schema and the fragments of enabled features.
+//!
+//! Fragments may define new types, and may add fields to types defined elsewhere
+//! with `extend type Name { ... }`. Trustfall doesn't support type extensions,
+//! so they are merged into the extended type's definition here.
+
+const BASE: &str = include_str!("schema.graphql");
+
+/// Fragments for the enabled entity groups, in the order they are merged.
+fn fragments() -> Vec<&'static str> {
+    #[allow(unused_mut)]
+    let mut fragments = Vec::new();
+
+    #[cfg(feature = "manifests")]
+    fragments.push(include_str!("schema/manifests.graphql"));
+
+    #[cfg(feature = "ci")]
+    fragments.push(include_str!("schema/ci.graphql"));
+
+    #[cfg(feature = "operations")]
+    fragments.push(include_str!("schema/operations.graphql"));
+
+    #[cfg(feature = "security")]
+    fragments.push(include_str!("schema/security.graphql"));
+
+    #[cfg(feature = "history")]
+    fragments.push(include_str!("schema/history.graphql"));
+
+    fragments
+}
+
+pub(crate) fn schema_text() -> String {
+    fragments()
+        .into_iter()
+        .fold(BASE.to_string(), |schema, fragment| merge(schema, fragment))
+}
+
+pub(crate) fn merge(mut schema: String, fragment: &str) -> String {
+    let mut rest = fragment;
+    let mut definitions = String::new();
+
+    while let Some(start) = rest.find("extend type ") {
+        definitions.push_str(&rest[..start]);
+
+        let extension = &rest[start + "extend type ".len()..];
+        let name_end = extension
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .expect("unterminated type extension");
+        let name = &extension[..name_end];
+        let body_start = extension.find('{').expect("type extension without a body") + 1;
+        let body_end = body_start
+            + extension[body_start..]
+                .find('}')
+                .expect("unterminated type extension");
+
+        let insert_at = closing_brace_of(&schema, name)
+            .unwrap_or_else(|| panic!("cannot extend unknown type {name}"));
+        schema.insert_str(insert_at, &extension[body_start..body_end]);
+
+        rest = &extension[body_end + 1..];
+    }
+    definitions.push_str(rest);
+
+    schema.push('\n');
+    schema.push_str(definitions.trim());
+    schema.push('\n');
+    schema
+}
+
+/// Byte offset of the `}` closing the definition of the named type or interface.
+fn closing_brace_of(schema: &str, name: &str) -> Option<usize> {
+    ["type ", "interface "].iter().find_map(|keyword| {
+        let mut offset = 0;
+        while let Some(found) = schema[offset..].find(&format!("{keyword}{name}")) {
+            let start = offset + found;
+            let after_name = start + keyword.len() + name.len();
+            let at_line_start = start == 0 || schema[..start].ends_with('\n');
+            let whole_name =
+                schema[after_name..].starts_with(|c: char| c.is_whitespace() || c == '{');
+
+            if at_line_start && whole_name {
+                return schema[after_name..].find('}').map(|end| after_name + end);
+            }
+            offset = after_name;
+        }
+        None
+    })
+}
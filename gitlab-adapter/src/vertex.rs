@@ -0,0 +1,1691 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "history")]
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use gitlab::types::Project;
+#[cfg(feature = "manifests")]
+use manifest_parsers::{Dependency, ManifestKind};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use trustfall::provider::TrustfallEnumVertex;
+use trustfall_core::ir::FieldValue;
+
+use crate::client::GitlabClient;
+
+/// One variant per schema type. `TrustfallEnumVertex` derives the `Typename` impl,
+/// which is the variant name, and an `as_<variant>()` accessor for each variant.
+/// Those are crate-private, so the ones embedders use are hand-written below instead.
+#[derive(Debug, Clone, TrustfallEnumVertex)]
+pub enum Vertex {
+    #[trustfall(skip_conversion)]
+    RootGitlabRepos(RootGitlabRepos),
+    #[trustfall(skip_conversion)]
+    GitlabRepo(GitlabRepo),
+    Namespace(Arc<Namespace>),
+    Badge(Arc<Badge>),
+    PagesDomain(Arc<PagesDomain>),
+    PagesDeployment(Arc<PagesDeployment>),
+    Integration(Arc<Integration>),
+    #[cfg(feature = "security")]
+    AccessToken(Arc<AccessToken>),
+    Label(Arc<Label>),
+    Milestone(Arc<Milestone>),
+    MilestoneStats(Arc<MilestoneStats>),
+    Timelog(Arc<Timelog>),
+    EmailParticipant(Arc<EmailParticipant>),
+    LabelEvent(Arc<LabelEvent>),
+    StateEvent(Arc<StateEvent>),
+    Requirement(Arc<Requirement>),
+    Iteration(Arc<Iteration>),
+    Board(Arc<Board>),
+    BoardList(Arc<BoardList>),
+    Note(Arc<Note>),
+    AwardEmoji(Arc<AwardEmoji>),
+    Event(Arc<Event>),
+    ContributionStats(Arc<ContributionStats>),
+    Epic(Arc<Epic>),
+    GroupMember(Arc<GroupMember>),
+    BillableMember(Arc<BillableMember>),
+    AccessRequest(Arc<AccessRequest>),
+    // `as_gitlab_user` is hand-written below, to also match `CurrentUser`.
+    #[trustfall(skip_conversion)]
+    GitlabUser(Arc<GitlabUser>),
+    CurrentUser(Arc<GitlabUser>),
+    Todo(Arc<Todo>),
+    Issue(Arc<Issue>),
+    MergeRequest(Arc<MergeRequest>),
+    SearchBlob(Arc<SearchBlob>),
+    GitlabInstance(Arc<GitlabInstance>),
+    #[cfg(feature = "ci")]
+    Pipeline(Arc<Pipeline>),
+    #[cfg(feature = "ci")]
+    PipelineJob(Arc<PipelineJob>),
+    #[cfg(feature = "ci")]
+    PipelineCoverage(Arc<PipelineCoverage>),
+    #[cfg(feature = "ci")]
+    PipelineVariable(Arc<PipelineVariable>),
+    #[cfg(feature = "ci")]
+    PipelineSchedule(Arc<PipelineSchedule>),
+    #[cfg(feature = "ci")]
+    JobArtifact(Arc<JobArtifact>),
+    #[cfg(feature = "operations")]
+    DoraMetric(Arc<DoraMetric>),
+    #[cfg(feature = "ci")]
+    MergeTrainCar(Arc<MergeTrainCar>),
+    #[cfg(feature = "operations")]
+    Environment(Arc<Environment>),
+    #[cfg(feature = "operations")]
+    Deployment(Arc<Deployment>),
+    #[cfg(feature = "operations")]
+    ProtectedEnvironment(Arc<ProtectedEnvironment>),
+    #[cfg(feature = "operations")]
+    EnvironmentAccessRule(Arc<EnvironmentAccessRule>),
+    #[cfg(feature = "operations")]
+    FeatureFlag(Arc<FeatureFlag>),
+    #[cfg(feature = "operations")]
+    FeatureFlagStrategy(Arc<FeatureFlagStrategy>),
+    #[cfg(feature = "operations")]
+    ErrorTrackingIssue(Arc<ErrorTrackingIssue>),
+    #[cfg(feature = "operations")]
+    TerraformState(Arc<TerraformState>),
+    #[cfg(feature = "operations")]
+    ClusterAgent(Arc<ClusterAgent>),
+    #[cfg(feature = "operations")]
+    ClusterAgentToken(Arc<ClusterAgentToken>),
+    #[cfg(feature = "ci")]
+    Runner(Arc<Runner>),
+    #[cfg(feature = "ci")]
+    TraceLine(Arc<TraceLine>),
+    #[cfg(feature = "ci")]
+    TestCase(Arc<TestCase>),
+    #[cfg(feature = "ci")]
+    CoverageReport(Arc<CoverageReport>),
+    #[cfg(feature = "ci")]
+    CiConfig(Arc<CiConfig>),
+    #[cfg(feature = "ci")]
+    CiConfigJob(Arc<CiConfigJob>),
+    #[cfg(feature = "ci")]
+    CiInclude(Arc<CiInclude>),
+    #[cfg(feature = "ci")]
+    GitlabCiFile(Arc<RepoFile>),
+    // `as_repo_file` is hand-written below, to also match the `RepoFile` subtypes.
+    #[trustfall(skip_conversion)]
+    RepoFile(Arc<RepoFile>),
+    ParsedFile(Arc<ParsedFile>),
+    Directory(Arc<Directory>),
+    #[cfg(feature = "manifests")]
+    PythonRequirementsFile(Arc<ManifestFile>),
+    #[cfg(feature = "manifests")]
+    CargoManifestFile(Arc<ManifestFile>),
+    #[cfg(feature = "manifests")]
+    NpmPackageFile(Arc<ManifestFile>),
+    #[cfg(feature = "manifests")]
+    #[trustfall(skip_conversion)]
+    ManifestDependency(Arc<Dependency>),
+    #[trustfall(skip_conversion)]
+    Commit(Arc<Commit>),
+    #[cfg(feature = "history")]
+    #[trustfall(skip_conversion)]
+    Branch(Arc<Branch>),
+    #[cfg(feature = "history")]
+    #[trustfall(skip_conversion)]
+    BlameHunk(Arc<BlameHunk>),
+    FileDiff(Arc<FileDiff>),
+}
+
+impl Vertex {
+    pub fn as_repo_list(&self) -> Option<&RootGitlabRepos> {
+        match self {
+            Self::RootGitlabRepos(repos) => Some(repos),
+            _ => None,
+        }
+    }
+
+    pub fn as_gitlab_repo(&self) -> Option<&GitlabRepo> {
+        match self {
+            Self::GitlabRepo(repo) => Some(repo),
+            _ => None,
+        }
+    }
+
+    /// Manifest, CI and parsed files are `RepoFile`s too, so this also matches all of its
+    /// subtypes.
+    pub fn as_repo_file(&self) -> Option<&RepoFile> {
+        match self {
+            Self::RepoFile(file) => Some(file),
+            Self::ParsedFile(parsed) => Some(&parsed.file),
+            #[cfg(feature = "ci")]
+            Self::GitlabCiFile(file) => Some(file),
+            #[cfg(feature = "manifests")]
+            _ => self.as_manifest_file().map(|manifest| &manifest.file),
+            #[cfg(not(feature = "manifests"))]
+            _ => None,
+        }
+    }
+
+    /// The authenticated user is a `GitlabUser` too, with more edges.
+    pub fn as_gitlab_user(&self) -> Option<&GitlabUser> {
+        match self {
+            Self::GitlabUser(user) | Self::CurrentUser(user) => Some(user),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "manifests")]
+    pub fn as_manifest_file(&self) -> Option<&ManifestFile> {
+        match self {
+            Self::PythonRequirementsFile(manifest)
+            | Self::CargoManifestFile(manifest)
+            | Self::NpmPackageFile(manifest) => Some(manifest),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "manifests")]
+    pub fn as_manifest_dependency(&self) -> Option<&Dependency> {
+        match self {
+            Self::ManifestDependency(dependency) => Some(dependency),
+            _ => None,
+        }
+    }
+
+    pub fn as_commit(&self) -> Option<&Commit> {
+        match self {
+            Self::Commit(commit) => Some(commit),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "history")]
+    pub fn as_branch(&self) -> Option<&Branch> {
+        match self {
+            Self::Branch(branch) => Some(branch),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "history")]
+    pub fn as_blame_hunk(&self) -> Option<&BlameHunk> {
+        match self {
+            Self::BlameHunk(hunk) => Some(hunk),
+            _ => None,
+        }
+    }
+}
+
+impl From<GitlabRepo> for Vertex {
+    fn from(repo: GitlabRepo) -> Self {
+        Self::GitlabRepo(repo)
+    }
+}
+
+impl From<RepoFile> for Vertex {
+    fn from(file: RepoFile) -> Self {
+        Self::RepoFile(file.into())
+    }
+}
+
+impl From<ParsedFile> for Vertex {
+    fn from(parsed: ParsedFile) -> Self {
+        Self::ParsedFile(parsed.into())
+    }
+}
+
+#[cfg(feature = "manifests")]
+impl From<ManifestFile> for Vertex {
+    fn from(manifest: ManifestFile) -> Self {
+        match manifest.kind {
+            ManifestKind::PythonRequirements => Self::PythonRequirementsFile(manifest.into()),
+            ManifestKind::CargoManifest => Self::CargoManifestFile(manifest.into()),
+            ManifestKind::NpmPackage => Self::NpmPackageFile(manifest.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RootGitlabRepos {
+    pub repos: Vec<GitlabRepo>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GitlabRepo {
+    pub id: String,
+    pub url: String,
+    pub description: String,
+    pub repo_files: Vec<Arc<RepoFile>>,
+    pub name: String,
+    pub default_branch: Option<String>,
+    /// The project as GitLab's API returned it; `None` when not read from the API.
+    pub raw_json: Option<String>,
+    /// `None` for repositories that weren't read from the API.
+    pub project: Option<Arc<Project>>,
+}
+
+/// A user or group owning projects.
+#[derive(Debug, Clone)]
+pub struct Namespace {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub full_path: String,
+    /// `user` or `group`.
+    pub kind: String,
+    pub web_url: String,
+    pub raw_json: Option<String>,
+}
+
+/// Vertex data deserialized from an API object, which is also kept as `raw_json`.
+pub(crate) trait ApiObject: DeserializeOwned {
+    fn set_raw_json(&mut self, raw_json: String);
+}
+
+macro_rules! impl_api_object {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl ApiObject for $ty {
+                fn set_raw_json(&mut self, raw_json: String) {
+                    self.raw_json = Some(raw_json);
+                }
+            }
+        )*
+    };
+}
+
+impl_api_object!(
+    Badge,
+    PagesDomain,
+    PagesDeployment,
+    Integration,
+    Label,
+    Milestone,
+    Timelog,
+    EmailParticipant,
+    LabelEvent,
+    StateEvent,
+    Requirement,
+    Iteration,
+    Board,
+    BoardList,
+    Note,
+    AwardEmoji,
+    Event,
+    Epic,
+    GroupMember,
+    BillableMember,
+    AccessRequest,
+    GitlabUser,
+    Issue,
+    MergeRequest,
+    SearchBlob,
+    GitlabInstance
+);
+#[cfg(feature = "security")]
+impl_api_object!(AccessToken);
+
+#[cfg(feature = "ci")]
+impl_api_object!(
+    Pipeline,
+    PipelineJob,
+    PipelineVariable,
+    PipelineSchedule,
+    JobArtifact,
+    MergeTrainCar,
+    Runner,
+    TestCase,
+    CiConfig,
+    CiConfigJob,
+    CiInclude
+);
+#[cfg(feature = "operations")]
+impl_api_object!(
+    DoraMetric,
+    Environment,
+    Deployment,
+    ProtectedEnvironment,
+    EnvironmentAccessRule,
+    FeatureFlag,
+    FeatureFlagStrategy,
+    ErrorTrackingIssue,
+    TerraformState,
+    ClusterAgent,
+    ClusterAgentToken
+);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Badge {
+    pub id: u64,
+    pub name: Option<String>,
+    pub link_url: String,
+    pub image_url: String,
+    /// `project`, or `group` for badges inherited from a group.
+    pub kind: String,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A custom domain serving a project's Pages site.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PagesDomain {
+    pub domain: String,
+    pub url: String,
+    pub verified: bool,
+    /// When the domain is disabled if it still isn't verified.
+    pub enabled_until: Option<String>,
+    /// Whether the certificate is managed through Let's Encrypt.
+    pub auto_ssl_enabled: bool,
+    /// `None` for domains served over plain HTTP.
+    pub certificate: Option<PagesCertificate>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PagesCertificate {
+    pub expired: bool,
+    pub expiration: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PagesDeployment {
+    pub created_at: String,
+    pub url: String,
+    /// Set for parallel deployments, served under this path.
+    pub path_prefix: Option<String>,
+    pub root_directory: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// An integration with another service, e.g. Slack or Jira, formerly called a service.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Integration {
+    pub id: u64,
+    pub title: String,
+    /// E.g. `slack` or `jira`, as in the integration's API path.
+    pub slug: String,
+    pub active: bool,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+    /// The events it's triggered by, from its `<event>_events` flags, e.g. `push`.
+    #[serde(skip)]
+    pub events: Vec<String>,
+    /// The integration's settings; GitLab leaves out secrets like tokens and passwords.
+    #[serde(default)]
+    pub properties: Option<serde_json::Value>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A personal or project access token, as listed by the API: never the secret itself.
+#[cfg(feature = "security")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccessToken {
+    pub id: u64,
+    pub name: String,
+    /// E.g. `api` or `read_repository`.
+    pub scopes: Vec<String>,
+    pub active: bool,
+    pub revoked: bool,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    pub expires_at: Option<String>,
+    /// The token's user, a bot user for project access tokens.
+    pub user_id: u64,
+    /// Project access tokens' role in the project.
+    pub access_level: Option<u64>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitlabUser {
+    pub id: u64,
+    pub username: String,
+    pub name: String,
+    /// `active`, `blocked`, `deactivated`, etc.
+    pub state: String,
+    pub web_url: String,
+    /// Only in full user objects, e.g. as listed for administrators; the rest only
+    /// to administrators.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub last_activity_on: Option<String>,
+    #[serde(default)]
+    pub is_admin: Option<bool>,
+    #[serde(default)]
+    pub two_factor_enabled: Option<bool>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Issue {
+    pub id: u64,
+    /// The issue's number within its project.
+    pub iid: u64,
+    pub project_id: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub web_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_at: Option<String>,
+    #[serde(default)]
+    pub upvotes: i64,
+    #[serde(default)]
+    pub downvotes: i64,
+    #[serde(default)]
+    pub time_stats: TimeStats,
+    /// Only on GitLab Premium and Ultimate, like `health_status` and the iteration.
+    pub weight: Option<i64>,
+    /// `on_track`, `needs_attention` or `at_risk`.
+    pub health_status: Option<String>,
+    /// The address of whoever emailed the issue in, for Service Desk issues.
+    #[serde(default)]
+    pub service_desk_reply_to: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// An issue's or merge request's time tracking totals, in seconds.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TimeStats {
+    #[serde(default)]
+    pub time_estimate: i64,
+    #[serde(default)]
+    pub total_time_spent: i64,
+}
+
+/// A project's or group's issue board, optionally scoped to a milestone, assignee, labels
+/// or weight.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Board {
+    pub id: u64,
+    pub name: Option<String>,
+    pub weight: Option<i64>,
+    #[serde(default)]
+    pub hide_backlog_list: bool,
+    #[serde(default)]
+    pub hide_closed_list: bool,
+    /// Also where the edges read the lists and the scope from.
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A column of an issue board, for the issues with a label, assignee, milestone or
+/// iteration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BoardList {
+    pub id: u64,
+    /// `None` for the backlog and closed lists.
+    pub position: Option<i64>,
+    /// The list's WIP limits, 0 for none.
+    #[serde(default)]
+    pub max_issue_count: i64,
+    #[serde(default)]
+    pub max_issue_weight: i64,
+    /// `all_metrics`, `issue_count` or `issue_weights`.
+    pub limit_metric: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A comment on an issue or merge request, or a system note recording a change to it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Note {
+    pub id: u64,
+    pub body: String,
+    /// Whether GitLab wrote it, e.g. for a label change.
+    #[serde(default)]
+    pub system: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub internal: bool,
+    #[serde(default)]
+    pub resolvable: bool,
+    pub resolved: Option<bool>,
+    pub project_id: Option<u64>,
+    /// `Issue`, `MergeRequest`, `Snippet`, etc.
+    pub noteable_type: String,
+    pub noteable_iid: Option<u64>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AwardEmoji {
+    pub id: u64,
+    /// The emoji's name, e.g. `thumbsup`.
+    pub name: String,
+    pub created_at: String,
+    pub updated_at: String,
+    /// `Issue`, `MergeRequest` or `Note`.
+    pub awardable_type: String,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// An entry of a project's or user's activity feed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Event {
+    pub id: u64,
+    /// `pushed to`, `opened`, `commented on`, `accepted`, etc.
+    pub action_name: String,
+    pub target_id: Option<u64>,
+    pub target_type: Option<String>,
+    pub target_title: Option<String>,
+    pub created_at: String,
+    pub project_id: Option<u64>,
+    pub push_data: Option<EventPushData>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventPushData {
+    pub commit_count: u64,
+    /// `pushed`, `created` or `removed`.
+    pub action: String,
+    #[serde(rename = "ref")]
+    pub ref_: Option<String>,
+    pub commit_title: Option<String>,
+}
+
+/// A user's activity since a date, counted from their events.
+#[derive(Debug, Clone)]
+pub struct ContributionStats {
+    pub since: String,
+    pub push_count: u64,
+    pub commit_count: u64,
+    pub merge_requests_opened: u64,
+    pub merge_requests_merged: u64,
+    /// Distinct merge requests approved or commented on.
+    pub review_count: u64,
+    pub comment_count: u64,
+}
+
+/// A group's epic, grouping issues and child epics, on GitLab Premium and Ultimate.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Epic {
+    pub id: u64,
+    /// The epic's number within its group.
+    pub iid: u64,
+    pub group_id: u64,
+    pub title: String,
+    pub description: Option<String>,
+    /// `opened` or `closed`.
+    pub state: String,
+    pub web_url: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub start_date: Option<String>,
+    pub due_date: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_at: Option<String>,
+    /// Also where the `parent_epic` edge reads the parent's API path from.
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A user's membership of a group: the user's fields and the membership's.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GroupMember {
+    pub id: u64,
+    pub username: String,
+    pub name: String,
+    pub state: String,
+    pub web_url: String,
+    pub access_level: u64,
+    pub created_at: Option<String>,
+    pub expires_at: Option<String>,
+    /// The identity the member signed in with through the group's SAML SSO, if any.
+    pub group_saml_identity: Option<SamlIdentity>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SamlIdentity {
+    pub provider: String,
+    pub extern_uid: String,
+}
+
+/// A user taking up a seat of a top-level group's subscription.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BillableMember {
+    pub id: u64,
+    pub username: String,
+    pub name: String,
+    pub state: String,
+    pub web_url: String,
+    /// `group_member`, `project_member` or `group_invite`, etc.
+    pub membership_type: String,
+    pub last_activity_on: Option<String>,
+    pub last_login_at: Option<String>,
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub removable: bool,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A user's pending request to join a project or group: the user's fields and when
+/// they asked.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccessRequest {
+    pub id: u64,
+    pub username: String,
+    pub name: String,
+    pub state: String,
+    pub requested_at: String,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A timebox of an iteration cadence, in a group.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Iteration {
+    pub id: u64,
+    pub iid: u64,
+    /// The iteration's number within its cadence.
+    pub sequence: Option<u64>,
+    pub group_id: Option<u64>,
+    /// `None` for iterations of automatically scheduled cadences.
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// 1 for upcoming, 2 for current and 3 for closed iterations.
+    pub state: u64,
+    pub start_date: Option<String>,
+    pub due_date: Option<String>,
+    pub web_url: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+impl Iteration {
+    pub fn state_name(&self) -> Option<&'static str> {
+        match self.state {
+            1 => Some("upcoming"),
+            2 => Some("current"),
+            3 => Some("closed"),
+            _ => None,
+        }
+    }
+}
+
+/// Time someone spent on an issue or merge request, from the GraphQL API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timelog {
+    /// In seconds; negative for time subtracted from the total.
+    pub time_spent: i64,
+    pub spent_at: Option<String>,
+    pub summary: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// Someone outside GitLab following an issue by email, e.g. a Service Desk issue's
+/// reporter, from the GraphQL API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailParticipant {
+    pub email: String,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A label added to or removed from an issue or merge request.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LabelEvent {
+    pub id: u64,
+    /// `add` or `remove`.
+    pub action: String,
+    pub created_at: String,
+    /// `Issue`, `MergeRequest` or `Epic`.
+    pub resource_type: String,
+    /// Also where the `user` and `label` edges read them from.
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// An issue or merge request being closed, reopened or merged.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StateEvent {
+    pub id: u64,
+    /// The state it changed to, e.g. `closed`, `reopened` or `merged`.
+    pub state: String,
+    pub created_at: String,
+    pub resource_type: String,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A project's requirement, on GitLab Ultimate, from the GraphQL API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Requirement {
+    /// The requirement's number within its project, e.g. `REQ-4` is 4.
+    pub iid: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// `OPENED` or `ARCHIVED`.
+    pub state: String,
+    /// `PASSED` or `FAILED`, `None` if it was never tested.
+    pub last_test_report_state: Option<String>,
+    pub last_test_report_manually_created: Option<bool>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Label {
+    pub id: u64,
+    pub name: String,
+    /// E.g. `#d9534f`.
+    pub color: String,
+    pub text_color: Option<String>,
+    pub description: Option<String>,
+    /// Only set for prioritized labels, lower first.
+    pub priority: Option<i64>,
+    /// `false` for labels inherited from a group.
+    pub is_project_label: Option<bool>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Milestone {
+    pub id: u64,
+    /// The milestone's number within its project or group.
+    pub iid: u64,
+    /// One of `project_id` and `group_id` is set.
+    pub project_id: Option<u64>,
+    pub group_id: Option<u64>,
+    pub title: String,
+    pub description: Option<String>,
+    /// `active` or `closed`.
+    pub state: String,
+    pub start_date: Option<String>,
+    pub due_date: Option<String>,
+    pub expired: Option<bool>,
+    pub web_url: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// How far along a milestone is, counted from its issues.
+#[derive(Debug, Clone)]
+pub struct MilestoneStats {
+    pub open_issues: u64,
+    pub closed_issues: u64,
+    /// The issues' weights, unweighted issues counting as 0.
+    pub total_weight: i64,
+    pub closed_weight: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeRequest {
+    pub id: u64,
+    /// The merge request's number within its project.
+    pub iid: u64,
+    pub project_id: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    pub source_branch: String,
+    pub target_branch: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub web_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub merged_at: Option<String>,
+    pub closed_at: Option<String>,
+    #[serde(default)]
+    pub upvotes: i64,
+    #[serde(default)]
+    pub downvotes: i64,
+    #[serde(default)]
+    pub time_stats: TimeStats,
+    /// `can_be_merged`, `cannot_be_merged`, `unchecked`, etc.; deprecated for
+    /// `detailed_merge_status`.
+    pub merge_status: Option<String>,
+    /// E.g. `mergeable`, `not_approved`, `ci_must_pass` or `draft_status`.
+    pub detailed_merge_status: Option<String>,
+    #[serde(default)]
+    pub has_conflicts: bool,
+    pub blocking_discussions_resolved: Option<bool>,
+    /// Whether it'll be squashed when merged.
+    #[serde(default)]
+    pub squash: bool,
+    /// The same, taking the project's squash setting into account.
+    pub squash_on_merge: Option<bool>,
+    /// Set to auto-merge when its pipeline succeeds.
+    #[serde(default)]
+    pub merge_when_pipeline_succeeds: bool,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A file matching a code search, with the matching lines.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchBlob {
+    pub path: String,
+    #[serde(rename = "ref")]
+    pub ref_: String,
+    /// The first line of `data`.
+    pub startline: i64,
+    pub data: String,
+    pub project_id: u64,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// The GitLab server itself: its metadata, and for administrators its settings.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitlabInstance {
+    /// E.g. `16.8.1-ee`.
+    pub version: String,
+    pub revision: String,
+    pub enterprise: bool,
+    pub kas: InstanceKas,
+    /// `None` unless the user is an administrator.
+    #[serde(skip)]
+    pub settings: Option<InstanceSettings>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// The agent server for Kubernetes.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstanceKas {
+    pub enabled: bool,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstanceSettings {
+    pub signup_enabled: Option<bool>,
+    pub require_two_factor_authentication: Option<bool>,
+    pub password_authentication_enabled_for_web: Option<bool>,
+    /// `public`, `internal` or `private`.
+    pub default_project_visibility: Option<String>,
+    #[serde(default)]
+    pub restricted_visibility_levels: Vec<String>,
+}
+
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Pipeline {
+    pub id: u64,
+    /// The pipeline's number within its project.
+    pub iid: u64,
+    pub project_id: u64,
+    pub status: String,
+    /// What triggered the pipeline, e.g. `push` or `schedule`.
+    pub source: Option<String>,
+    #[serde(rename = "ref")]
+    pub ref_: String,
+    pub sha: String,
+    pub web_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Only in the single pipeline endpoint's response; see `details` for the others.
+    #[serde(flatten)]
+    pub timings: PipelineTimings,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+    /// Set by the edges listing pipelines, whose responses leave `timings` out.
+    #[serde(skip)]
+    pub details: Option<Arc<LazyPipelineDetails>>,
+    /// The pipeline whose bridge job triggered this one, when this one was listed as
+    /// one of its downstream pipelines.
+    #[serde(skip)]
+    pub upstream: Option<Arc<Pipeline>>,
+}
+
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineJob {
+    pub id: u64,
+    pub name: String,
+    pub stage: String,
+    pub status: String,
+    #[serde(rename = "ref")]
+    pub ref_: String,
+    pub allow_failure: bool,
+    /// Why the job failed, e.g. `script_failure`.
+    pub failure_reason: Option<String>,
+    pub web_url: String,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    /// In seconds.
+    pub duration: Option<f64>,
+    /// How long the job waited for a runner, in seconds.
+    pub queued_duration: Option<f64>,
+    /// The percentage parsed from the log with the job's `coverage` regex.
+    pub coverage: Option<f64>,
+    /// The tags a runner needs to pick the job up.
+    #[serde(default)]
+    pub tag_list: Vec<String>,
+    /// `None` when the artifacts are kept forever, or there are none.
+    pub artifacts_expire_at: Option<String>,
+    /// Whether the job was retried, so a newer job replaces it in its pipeline.
+    #[serde(default)]
+    pub retried: bool,
+    pub pipeline: JobPipeline,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+    /// Set by the edge listing the job, since reading the log needs the client.
+    #[serde(skip)]
+    pub trace: Option<Arc<LazyTrace>>,
+}
+
+/// A pipeline's coverage, the average of its jobs' coverage.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone)]
+pub struct PipelineCoverage {
+    pub project_id: u64,
+    pub pipeline_id: u64,
+    /// `None` when no job reported coverage.
+    pub percentage: Option<f64>,
+}
+
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PipelineTimings {
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    /// How long the pipeline waited for its first job to be picked up, in seconds.
+    pub queued_duration: Option<f64>,
+}
+
+/// The timings of a listed pipeline, fetched when first read rather than with the pipeline,
+/// and kept after.
+#[cfg(feature = "ci")]
+#[derive(Debug)]
+pub struct LazyPipelineDetails {
+    pub(crate) client: Arc<GitlabClient>,
+    pub(crate) timings: Mutex<Option<PipelineTimings>>,
+}
+
+/// One of a job's artifact files, e.g. its `archive` or `junit` report.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobArtifact {
+    /// E.g. `archive`, `metadata`, `trace` or `junit`.
+    pub file_type: String,
+    pub filename: String,
+    pub file_format: Option<String>,
+    /// In bytes.
+    pub size: u64,
+    /// The job's `artifacts_expire_at`, which all its artifacts share.
+    #[serde(skip)]
+    pub expire_at: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A DORA metric's value for one period.
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct DoraMetric {
+    /// Set by the edge, since the API only returns the values.
+    #[serde(skip)]
+    pub metric: String,
+    /// The period's first day, e.g. `2024-03-01` for March with a monthly interval.
+    pub date: String,
+    pub value: Option<f64>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Environment {
+    pub id: u64,
+    /// Set by the edges, since environments don't include it.
+    #[serde(skip)]
+    pub project_id: u64,
+    /// E.g. `production` or `review/my-branch`.
+    pub name: String,
+    pub slug: String,
+    pub external_url: Option<String>,
+    /// `available`, `stopping` or `stopped`.
+    pub state: String,
+    /// `production`, `staging`, `testing`, `development` or `other`.
+    pub tier: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// When it'll be stopped, for environments with an `auto_stop_in`.
+    pub auto_stop_at: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Deployment {
+    pub id: u64,
+    pub iid: u64,
+    /// Set by the edges, like an environment's.
+    #[serde(skip)]
+    pub project_id: u64,
+    #[serde(rename = "ref")]
+    pub ref_: String,
+    pub sha: String,
+    /// `created`, `running`, `success`, `failed`, `canceled` or `blocked`.
+    pub status: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub environment: DeploymentEnvironment,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// Who may deploy to the environments matching `name`, and who must approve it first.
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProtectedEnvironment {
+    /// An environment name, or a wildcard like `review/*`.
+    pub name: String,
+    pub required_approval_count: Option<u64>,
+    /// Also where the edges read the access levels and approval rules from.
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A deploy access level or approval rule of a [`ProtectedEnvironment`], for a role, a
+/// user or a group.
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnvironmentAccessRule {
+    pub access_level: Option<u64>,
+    /// E.g. `Maintainers`, or the user's or group's name.
+    pub access_level_description: Option<String>,
+    pub user_id: Option<u64>,
+    pub group_id: Option<u64>,
+    /// Only for approval rules.
+    pub required_approvals: Option<u64>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeatureFlag {
+    pub name: String,
+    pub description: Option<String>,
+    pub active: bool,
+    /// `new_version_flag`; legacy flags were removed in GitLab 15.0.
+    pub version: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub strategies: Vec<FeatureFlagStrategy>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[cfg(feature = "operations")]
+impl FeatureFlag {
+    /// The environments any of the flag's strategies apply to, `*` meaning all of them.
+    pub fn environment_scopes(&self) -> Vec<String> {
+        let mut scopes: Vec<String> = self
+            .strategies
+            .iter()
+            .flat_map(|strategy| strategy.environment_scopes())
+            .collect();
+        scopes.sort();
+        scopes.dedup();
+        scopes
+    }
+}
+
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeatureFlagStrategy {
+    pub id: u64,
+    /// `default`, `gradualRolloutUserId`, `flexibleRollout`, `userWithId` or `gitlabUserList`.
+    pub name: String,
+    /// E.g. `{"percentage": "50", "groupId": "default"}`, depending on `name`.
+    #[serde(default)]
+    pub parameters: serde_json::Value,
+    #[serde(default)]
+    pub scopes: Vec<FeatureFlagScope>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[cfg(feature = "operations")]
+impl FeatureFlagStrategy {
+    pub fn environment_scopes(&self) -> Vec<String> {
+        self.scopes
+            .iter()
+            .map(|scope| scope.environment_scope.clone())
+            .collect()
+    }
+}
+
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeatureFlagScope {
+    pub environment_scope: String,
+}
+
+/// An error grouped by the project's error tracking, either GitLab's integrated one or Sentry.
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorTrackingIssue {
+    pub sentry_id: String,
+    pub short_id: Option<String>,
+    pub title: String,
+    /// E.g. `error` or `default`.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub message: Option<String>,
+    /// Where it was raised, e.g. a function or route.
+    pub culprit: Option<String>,
+    /// `UNRESOLVED`, `RESOLVED`, `RESOLVED_IN_NEXT_RELEASE` or `IGNORED`.
+    pub status: String,
+    pub count: u64,
+    pub user_count: u64,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub external_url: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A Terraform state stored in GitLab's HTTP backend.
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerraformState {
+    pub name: String,
+    pub locked_at: Option<String>,
+    pub locked_by_user: Option<GraphqlUser>,
+    /// `None` for states that were created but never written to.
+    pub latest_version: Option<TerraformStateVersion>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerraformStateVersion {
+    pub serial: Option<u64>,
+    pub created_at: String,
+}
+
+/// A GitLab agent for Kubernetes registered in a project.
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClusterAgent {
+    pub id: u64,
+    /// Set by the edge, like an environment's.
+    #[serde(skip)]
+    pub project_id: u64,
+    pub name: String,
+    pub created_at: String,
+    pub created_by_user_id: Option<u64>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClusterAgentToken {
+    pub id: u64,
+    pub name: String,
+    pub description: Option<String>,
+    /// `active` or `revoked`.
+    pub status: String,
+    pub created_at: String,
+    pub created_by_user_id: Option<u64>,
+    /// When the agent last connected with it.
+    pub last_used_at: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A user as the GraphQL API nests it in other objects.
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphqlUser {
+    pub username: String,
+}
+
+/// The part of the environment nested in a deployment.
+#[cfg(feature = "operations")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeploymentEnvironment {
+    pub id: u64,
+    pub name: String,
+}
+
+/// A merge request's place on a merge train, called a car.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeTrainCar {
+    pub id: u64,
+    /// `idle`, `stale`, `fresh`, `merging`, `merged` or `skip_merged`.
+    pub status: String,
+    pub target_branch: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub merged_at: Option<String>,
+    /// In seconds.
+    pub duration: Option<f64>,
+    /// From 1 at the front of its train, counted by the edge; `None` once merged.
+    #[serde(skip)]
+    pub position: Option<u64>,
+    pub merge_request: CarMergeRequest,
+    pub pipeline: Option<JobPipeline>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// The part of the merge request nested in a merge train car.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct CarMergeRequest {
+    pub iid: u64,
+    pub project_id: u64,
+}
+
+/// A variable a pipeline was run with, e.g. one given when triggering it by hand.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineVariable {
+    pub key: String,
+    /// `env_var` or `file`.
+    pub variable_type: String,
+    pub value: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineSchedule {
+    pub id: u64,
+    pub description: Option<String>,
+    #[serde(rename = "ref")]
+    pub ref_: String,
+    pub cron: String,
+    pub cron_timezone: String,
+    pub next_run_at: Option<String>,
+    pub active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Pipelines run as the owner, so they stop running once the owner can't.
+    pub owner: Option<GitlabUser>,
+    #[serde(default)]
+    pub last_pipeline: Option<ScheduleLastPipeline>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Runner {
+    pub id: u64,
+    pub description: Option<String>,
+    /// `instance_type`, `group_type` or `project_type`.
+    pub runner_type: String,
+    pub is_shared: bool,
+    /// `online`, `offline`, `stale` or `never_contacted`.
+    pub status: String,
+    #[serde(default)]
+    pub paused: bool,
+    /// Only in a runner's own endpoint's response, not the listings'.
+    #[serde(default)]
+    pub tag_list: Vec<String>,
+    /// Whether it also picks up jobs without tags.
+    pub run_untagged: Option<bool>,
+    pub locked: Option<bool>,
+    pub version: Option<String>,
+    pub contacted_at: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// The part of the pipeline nested in a schedule.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduleLastPipeline {
+    pub id: u64,
+    pub status: String,
+}
+
+/// The part of the pipeline nested in a job or merge train car that's needed to look
+/// them up again.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobPipeline {
+    pub id: u64,
+    pub project_id: u64,
+    pub sha: Option<String>,
+}
+
+/// A job's log, fetched when it's first read rather than with the job, and kept after.
+#[cfg(feature = "ci")]
+#[derive(Debug)]
+pub struct LazyTrace {
+    pub(crate) client: Arc<GitlabClient>,
+    /// How many bytes to keep from the end of the log.
+    pub(crate) limit: usize,
+    pub(crate) redact_secrets: bool,
+    /// `Some(None)` once the log failed to be read.
+    pub(crate) text: Mutex<Option<Option<Arc<str>>>>,
+}
+
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone)]
+pub struct TraceLine {
+    pub text: String,
+}
+
+/// A test case as in a pipeline's test report, or read from a JUnit XML report.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct TestCase {
+    pub name: String,
+    pub classname: String,
+    /// `success`, `failed`, `skipped` or `error`.
+    pub status: String,
+    /// In seconds.
+    pub execution_time: f64,
+    pub system_output: Option<String>,
+    pub stack_trace: Option<String>,
+    pub file: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A project's CI configuration as GitLab's CI lint expands it, with its includes and
+/// `extends` resolved.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct CiConfig {
+    pub valid: bool,
+    #[serde(default)]
+    pub errors: Vec<String>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    pub merged_yaml: Option<String>,
+    /// Also where the edges read the `jobs` and `includes` from, so each gets its own
+    /// `raw_json`.
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A job of a [`CiConfig`], as it'd be created, not as written.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct CiConfigJob {
+    pub name: String,
+    pub stage: String,
+    #[serde(default)]
+    pub before_script: Vec<String>,
+    #[serde(default)]
+    pub script: Vec<String>,
+    #[serde(default)]
+    pub after_script: Vec<String>,
+    #[serde(default)]
+    pub tag_list: Vec<String>,
+    /// E.g. `on_success` or `manual`.
+    pub when: Option<String>,
+    #[serde(default)]
+    pub allow_failure: bool,
+    /// `None` when the job waits for the previous stages instead.
+    pub needs: Option<Vec<CiNeed>>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// A job in another job's `needs:`, written either as its name or with options.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum CiNeed {
+    Name(String),
+    Job {
+        #[serde(alias = "job")]
+        name: String,
+    },
+}
+
+#[cfg(feature = "ci")]
+impl CiNeed {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Name(name) | Self::Job { name } => name,
+        }
+    }
+}
+
+/// A file included by a [`CiConfig`], directly or by another include.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct CiInclude {
+    /// `local`, `file`, `remote`, `template` or `component`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub location: String,
+    /// The web URL of the included file.
+    pub blob: Option<String>,
+    /// The URL of its raw content.
+    pub raw: Option<String>,
+    /// The project and ref of `file` includes.
+    #[serde(default)]
+    pub extra: CiIncludeExtra,
+    /// The project and commit the include was resolved from.
+    pub context_project: Option<String>,
+    pub context_sha: Option<String>,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CiIncludeExtra {
+    pub project: Option<String>,
+    #[serde(rename = "ref")]
+    pub ref_: Option<String>,
+}
+
+/// The totals of a Cobertura XML report, from the attributes of its root element.
+#[cfg(feature = "ci")]
+#[derive(Debug, Clone)]
+pub struct CoverageReport {
+    /// Within the job's artifacts.
+    pub path: String,
+    pub line_rate: Option<f64>,
+    pub branch_rate: Option<f64>,
+    pub lines_covered: Option<i64>,
+    pub lines_valid: Option<i64>,
+    pub branches_covered: Option<i64>,
+    pub branches_valid: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Todo {
+    pub id: u64,
+    pub action_name: String,
+    pub target_type: String,
+    pub target_title: Option<String>,
+    pub target_url: String,
+    pub body: String,
+    pub state: String,
+    pub created_at: String,
+    pub project_full_path: Option<String>,
+    pub project_id: Option<u64>,
+    pub raw_json: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RepoFile {
+    pub path: String,
+    pub content: FileContent,
+    /// The blob's object id and mode, e.g. "100755" for executables, when the file was
+    /// found by listing a git tree.
+    pub blob_id: Option<String>,
+    pub mode: Option<String>,
+    /// The repository the file is in, and the ref it was read at unless that's the
+    /// default branch, for reading the file's history.
+    pub repo: Arc<GitlabRepo>,
+    pub ref_: Option<String>,
+}
+
+/// Files found by listing a tree are only fetched when their content or size is read,
+/// so filtering a listing on paths or modes doesn't download the repository.
+#[derive(Debug, Clone)]
+pub enum FileContent {
+    /// Read with the file. `size` is in bytes, before any redaction.
+    Read { text: Arc<str>, size: usize },
+    /// The blob `blob_id` of a listed file.
+    Listed(Arc<LazyBlob>),
+}
+
+impl FileContent {
+    pub fn read(content: &[u8]) -> Self {
+        Self::Read {
+            text: String::from_utf8_lossy(content).into(),
+            size: content.len(),
+        }
+    }
+
+    /// The content, if it was read with the file.
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            Self::Read { text, .. } => Some(text),
+            Self::Listed(_) => None,
+        }
+    }
+}
+
+/// A listed file's blob, fetched the first time it's read, and kept after.
+#[derive(Debug)]
+pub struct LazyBlob {
+    pub(crate) client: Arc<GitlabClient>,
+    pub(crate) redact_secrets: bool,
+    /// `Some(None)` once the content failed to be read.
+    pub(crate) text: Mutex<Option<Option<Arc<str>>>>,
+    pub(crate) size: Mutex<Option<Option<usize>>>,
+}
+
+/// A directory of a repository at a ref, remembering both so its edges can list its
+/// contents.
+#[derive(Debug, Clone)]
+pub struct Directory {
+    pub repo: Arc<GitlabRepo>,
+    pub ref_: String,
+    /// Relative to the repository root and `/`-separated, without a trailing `/`.
+    pub path: String,
+}
+
+/// A `RepoFile` matched by a custom [`FileParser`](crate::file_parsers::FileParser).
+#[derive(Debug, Clone)]
+pub struct ParsedFile {
+    pub file: RepoFile,
+    pub format: String,
+    pub properties: BTreeMap<String, FieldValue>,
+}
+
+/// A `RepoFile` recognized as a dependency manifest, with its dependencies parsed upfront.
+#[cfg(feature = "manifests")]
+#[derive(Debug, Clone)]
+pub struct ManifestFile {
+    pub file: RepoFile,
+    pub kind: ManifestKind,
+    pub dependencies: Vec<Arc<Dependency>>,
+}
+
+/// The object database commits, branches and blame hunks were read from, so their
+/// edges can be resolved from the same one.
+#[cfg(feature = "history")]
+#[derive(Debug, Clone)]
+pub struct GitDir {
+    pub path: PathBuf,
+    /// The GitLab project cloned into `path`; `None` for local checkouts.
+    pub project_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Commit {
+    pub hash: String,
+    pub summary: String,
+    pub message: String,
+    pub author_name: String,
+    pub author_email: String,
+    pub authored_at: String,
+    pub committer_name: String,
+    pub committer_email: String,
+    pub committed_at: String,
+    /// Hashes of the parent commits.
+    pub parents: Vec<String>,
+    pub source: CommitSource,
+}
+
+/// Where a commit was read from, so its edges are resolved from the same place.
+#[derive(Debug, Clone)]
+pub enum CommitSource {
+    /// GitLab's API, for the project with this id or full path.
+    Api { project: String },
+    /// A clone of the repository, with the `history` feature.
+    #[cfg(feature = "history")]
+    Git(Arc<GitDir>),
+}
+
+#[cfg(feature = "history")]
+#[derive(Debug, Clone)]
+pub struct Branch {
+    pub name: String,
+    /// Hash of the commit the branch points to.
+    pub head: String,
+    pub git_dir: Arc<GitDir>,
+}
+
+#[cfg(feature = "history")]
+#[derive(Debug, Clone)]
+pub struct BlameHunk {
+    pub path: String,
+    pub start_line: i64,
+    pub line_count: i64,
+    /// Hash of the commit that last changed these lines.
+    pub commit: String,
+    pub git_dir: Arc<GitDir>,
+}
+
+/// A file changed by a commit, compared to its first parent.
+#[derive(Debug, Clone)]
+pub struct FileDiff {
+    pub path: String,
+    /// The path before a rename.
+    pub old_path: Option<String>,
+    pub status: String,
+    pub additions: i64,
+    pub deletions: i64,
+}
@@ -0,0 +1,70 @@
+use toml::{Table, Value};
+
+use crate::{Dependency, DependencyKind, ParseError};
+
+const DEPENDENCY_TABLES: [(&str, DependencyKind); 3] = [
+    ("dependencies", DependencyKind::Normal),
+    ("dev-dependencies", DependencyKind::Dev),
+    ("build-dependencies", DependencyKind::Build),
+];
+
+/// Parses a `Cargo.toml`, including target-specific dependency tables.
+///
+/// Workspace-level `[workspace.dependencies]` are reported as normal dependencies.
+pub(crate) fn parse(content: &str) -> Result<Vec<Dependency>, ParseError> {
+    let manifest: Table = content.parse()?;
+
+    let mut dependencies = Vec::new();
+    collect_tables(&manifest, &mut dependencies);
+
+    if let Some(Value::Table(targets)) = manifest.get("target") {
+        for target in targets.values() {
+            if let Value::Table(target) = target {
+                collect_tables(target, &mut dependencies);
+            }
+        }
+    }
+
+    if let Some(Value::Table(workspace)) = manifest.get("workspace") {
+        if let Some(Value::Table(table)) = workspace.get("dependencies") {
+            collect(table, DependencyKind::Normal, &mut dependencies);
+        }
+    }
+
+    Ok(dependencies)
+}
+
+fn collect_tables(parent: &Table, dependencies: &mut Vec<Dependency>) {
+    for (table_name, kind) in DEPENDENCY_TABLES {
+        if let Some(Value::Table(table)) = parent.get(table_name) {
+            collect(table, kind, dependencies);
+        }
+    }
+}
+
+fn collect(table: &Table, kind: DependencyKind, dependencies: &mut Vec<Dependency>) {
+    for (key, spec) in table {
+        let (name, requirement) = match spec {
+            Value::String(version) => (key.clone(), Some(version.clone())),
+            Value::Table(spec) => {
+                // Renamed dependencies: `foo = { package = "bar", ... }`
+                let name = match spec.get("package") {
+                    Some(Value::String(package)) => package.clone(),
+                    _ => key.clone(),
+                };
+                let requirement = match spec.get("version") {
+                    Some(Value::String(version)) => Some(version.clone()),
+                    _ => None,
+                };
+                (name, requirement)
+            }
+            _ => continue,
+        };
+
+        dependencies.push(Dependency {
+            name,
+            requirement,
+            kind,
+        });
+    }
+}
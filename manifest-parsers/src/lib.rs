@@ -0,0 +1,119 @@
+//! Parsers for dependency manifests commonly found in repositories,
+//! shared between Trustfall adapters that serve repository files.
+//!
+//! ```
+//! use manifest_parsers::ManifestKind;
+//!
+//! let kind = ManifestKind::from_path("service/requirements.txt").unwrap();
+//! let dependencies = kind.parse("requests>=2.28\n").unwrap();
+//! assert_eq!(dependencies[0].name, "requests");
+//! ```
+
+use std::fmt;
+
+mod cargo;
+mod npm;
+mod requirements;
+
+/// The manifest formats understood by this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ManifestKind {
+    /// `requirements.txt` and friends (`requirements-dev.txt`, `requirements/base.txt`, ...)
+    PythonRequirements,
+    /// `Cargo.toml`
+    CargoManifest,
+    /// `package.json`
+    NpmPackage,
+}
+
+impl ManifestKind {
+    /// Recognizes a manifest by its path within the repository.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+
+        match file_name {
+            "Cargo.toml" => Some(Self::CargoManifest),
+            "package.json" => Some(Self::NpmPackage),
+            name if name.ends_with(".txt")
+                && (name.starts_with("requirements") || path.contains("requirements/")) =>
+            {
+                Some(Self::PythonRequirements)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn parse(&self, content: &str) -> Result<Vec<Dependency>, ParseError> {
+        match self {
+            Self::PythonRequirements => Ok(requirements::parse(content)),
+            Self::CargoManifest => cargo::parse(content),
+            Self::NpmPackage => npm::parse(content),
+        }
+    }
+}
+
+/// A single dependency declared in a manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub name: String,
+    /// The version requirement exactly as written, if any, e.g. `>=2.28` or `^1.0`.
+    pub requirement: Option<String>,
+    pub kind: DependencyKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+    Peer,
+    Optional,
+}
+
+impl DependencyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Dev => "dev",
+            Self::Build => "build",
+            Self::Peer => "peer",
+            Self::Optional => "optional",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Toml(e) => write!(f, "invalid TOML: {e}"),
+            Self::Json(e) => write!(f, "invalid JSON: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Toml(e) => Some(e),
+            Self::Json(e) => Some(e),
+        }
+    }
+}
+
+impl From<toml::de::Error> for ParseError {
+    fn from(e: toml::de::Error) -> Self {
+        Self::Toml(e)
+    }
+}
+
+impl From<serde_json::Error> for ParseError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
@@ -0,0 +1,30 @@
+use serde_json::Value;
+
+use crate::{Dependency, DependencyKind, ParseError};
+
+const DEPENDENCY_OBJECTS: [(&str, DependencyKind); 4] = [
+    ("dependencies", DependencyKind::Normal),
+    ("devDependencies", DependencyKind::Dev),
+    ("peerDependencies", DependencyKind::Peer),
+    ("optionalDependencies", DependencyKind::Optional),
+];
+
+/// Parses the dependency sections of a `package.json`.
+pub(crate) fn parse(content: &str) -> Result<Vec<Dependency>, ParseError> {
+    let package: Value = serde_json::from_str(content)?;
+
+    let mut dependencies = Vec::new();
+    for (object_name, kind) in DEPENDENCY_OBJECTS {
+        if let Some(Value::Object(object)) = package.get(object_name) {
+            for (name, requirement) in object {
+                dependencies.push(Dependency {
+                    name: name.clone(),
+                    requirement: requirement.as_str().map(|r| r.to_string()),
+                    kind,
+                });
+            }
+        }
+    }
+
+    Ok(dependencies)
+}
@@ -0,0 +1,48 @@
+use crate::{Dependency, DependencyKind};
+
+/// Parses a pip requirements file.
+///
+/// Options (`-r other.txt`, `--index-url ...`), editable installs and direct URLs
+/// don't name a package in a way we can reliably extract, so they are skipped.
+pub(crate) fn parse(content: &str) -> Vec<Dependency> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = match line.find(" #") {
+                Some(comment) => &line[..comment],
+                None => line,
+            }
+            .trim();
+
+            if line.is_empty() || line.starts_with('#') || line.starts_with('-') {
+                return None;
+            }
+
+            // Environment markers (`; python_version < "3.8"`) don't affect the requirement itself.
+            let line = line.split(';').next().unwrap_or(line).trim();
+
+            let name_end = line
+                .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+                .unwrap_or(line.len());
+            let name = &line[..name_end];
+            if name.is_empty() || line[name_end..].trim_start().starts_with('@') {
+                return None;
+            }
+
+            let mut rest = line[name_end..].trim();
+            if rest.starts_with('[') {
+                // Skip over extras, e.g. `requests[socks]`.
+                rest = rest
+                    .find(']')
+                    .map(|end| rest[end + 1..].trim())
+                    .unwrap_or("");
+            }
+
+            Some(Dependency {
+                name: name.to_string(),
+                requirement: (!rest.is_empty()).then(|| rest.to_string()),
+                kind: DependencyKind::Normal,
+            })
+        })
+        .collect()
+}
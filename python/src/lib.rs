@@ -0,0 +1,106 @@
+//! Python bindings, so the same Trustfall queries can be run from notebooks.
+//!
+//! ```python
+//! import trustfall_gitlab
+//!
+//! rows = trustfall_gitlab.execute_query(
+//!     '''
+//!     {
+//!       GitlabRepos(query: "trustfall") {
+//!         name @output @filter(op: "has_substring", value: ["$needle"])
+//!       }
+//!     }''',
+//!     {"needle": "adapter"},
+//! )
+//! ```
+
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use trustfall::FieldValue;
+use trustfall_gitlab_adapter::{schema, GitlabAdapter};
+
+/// Runs a query against GitLab, returning one dict per result.
+///
+/// The client is configured from the `GITLAB_HOST` and `GITLAB_API_TOKEN` environment variables.
+#[pyfunction]
+#[pyo3(signature = (query, args = None))]
+fn execute_query(py: Python<'_>, query: &str, args: Option<&PyDict>) -> PyResult<Vec<PyObject>> {
+    let mut variables: BTreeMap<Arc<str>, FieldValue> = BTreeMap::new();
+    if let Some(args) = args {
+        for (key, value) in args.iter() {
+            let key: &str = key.extract()?;
+            variables.insert(key.into(), to_field_value(value)?);
+        }
+    }
+
+    let adapter = Rc::new(GitlabAdapter::new());
+    let results = trustfall::execute_query(schema(), adapter, query, variables)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+    results
+        .map(|row| {
+            let dict = PyDict::new(py);
+            for (key, value) in row {
+                dict.set_item(key.as_ref(), to_python(py, value))?;
+            }
+            Ok(dict.into())
+        })
+        .collect()
+}
+
+fn to_field_value(value: &PyAny) -> PyResult<FieldValue> {
+    // `bool` is a subclass of `int` in Python, so it must be checked first.
+    if value.is_none() {
+        Ok(FieldValue::Null)
+    } else if let Ok(b) = value.extract::<bool>() {
+        Ok(FieldValue::Boolean(b))
+    } else if let Ok(i) = value.extract::<i64>() {
+        Ok(FieldValue::Int64(i))
+    } else if let Ok(u) = value.extract::<u64>() {
+        Ok(FieldValue::Uint64(u))
+    } else if let Ok(f) = value.extract::<f64>() {
+        Ok(FieldValue::Float64(f))
+    } else if let Ok(s) = value.extract::<String>() {
+        Ok(FieldValue::String(s))
+    } else if let Ok(list) = value.downcast::<PyList>() {
+        list.iter()
+            .map(to_field_value)
+            .collect::<PyResult<Vec<_>>>()
+            .map(FieldValue::List)
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "unsupported query argument type: {}",
+            value.get_type().name()?
+        )))
+    }
+}
+
+fn to_python(py: Python<'_>, value: FieldValue) -> PyObject {
+    match value {
+        FieldValue::Null => py.None(),
+        FieldValue::Int64(i) => i.into_py(py),
+        FieldValue::Uint64(u) => u.into_py(py),
+        FieldValue::Float64(f) => f.into_py(py),
+        FieldValue::String(s) => s.into_py(py),
+        FieldValue::Boolean(b) => b.into_py(py),
+        // ISO 8601, which `datetime.fromisoformat()` can read back.
+        FieldValue::DateTimeUtc(dt) => dt.to_rfc3339().into_py(py),
+        FieldValue::Enum(s) => s.into_py(py),
+        FieldValue::List(values) => values
+            .into_iter()
+            .map(|v| to_python(py, v))
+            .collect::<Vec<_>>()
+            .into_py(py),
+    }
+}
+
+#[pymodule]
+fn trustfall_gitlab(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(execute_query, m)?)?;
+    Ok(())
+}